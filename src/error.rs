@@ -52,6 +52,8 @@ pub enum Error {
     },
     #[error("text in node {tag:?} is empty")]
     NodeTextEmpty { tag: &'static str },
+    #[error("musicxml version {version:?} is not supported")]
+    UnsupportedVersion { version: String },
 }
 
 pub type Result<T, E = Error> = core::result::Result<T, E>;
@@ -1,3 +1,4 @@
 pub mod error;
 pub mod mxl;
 pub mod score;
+pub mod writer;
@@ -1,7 +1,7 @@
-use std::io::{self, Read};
+use std::io::{self, Read, Write};
 
 use roxmltree::Document;
-use zip::ZipArchive;
+use zip::{write::FileOptions, CompressionMethod, ZipArchive, ZipWriter};
 
 use crate::{
     error::{
@@ -9,8 +9,14 @@ use crate::{
         Result,
     },
     score::Score,
+    writer::{Element, WriteOptions},
 };
 
+const MIMETYPE: &str = "application/vnd.recordare.musicxml";
+const MUSIC_XML_MEDIA_TYPE: &str = "application/vnd.recordare.musicxml+xml";
+const CONTAINER_PATH: &str = "META-INF/container.xml";
+const SCORE_PATH: &str = "score.musicxml";
+
 pub struct Mxl<R> {
     archive: ZipArchive<R>,
 }
@@ -40,7 +46,7 @@ impl<R: Read + io::Seek> Mxl<R> {
 
     fn parse_music_xml_path(archive: &mut ZipArchive<R>) -> Result<String> {
         // TODO: should consider wrapping it to a struct
-        let xml = Self::extra_text_file(archive, "META-INF/container.xml")?;
+        let xml = Self::extra_text_file(archive, CONTAINER_PATH)?;
 
         let doc = Document::parse(&xml)?;
         let root = doc.root_element();
@@ -54,12 +60,7 @@ impl<R: Read + io::Seek> Mxl<R> {
         rootfiles
             .children()
             .filter(|c| c.tag_name().name() == "rootfile")
-            .find(|c| {
-                matches!(
-                    c.attribute("media-type"),
-                    Some("application/vnd.recordare.musicxml+xml") | None
-                )
-            })
+            .find(|c| matches!(c.attribute("media-type"), Some(MUSIC_XML_MEDIA_TYPE) | None))
             .ok_or(NodeNotFound {
                 tag: "rootfile",
                 parent_tag: rootfiles.tag_name().name().to_owned(),
@@ -72,3 +73,96 @@ impl<R: Read + io::Seek> Mxl<R> {
             })
     }
 }
+
+pub struct MxlWriter<W: Write + io::Seek> {
+    archive: ZipWriter<W>,
+    options: WriteOptions,
+}
+
+impl<W: Write + io::Seek> MxlWriter<W> {
+    pub fn new(writer: W) -> MxlWriter<W> {
+        MxlWriter {
+            archive: ZipWriter::new(writer),
+            options: WriteOptions::default(),
+        }
+    }
+
+    /// Format the written xml files with the given options
+    pub fn with_options(mut self, options: WriteOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    pub fn write(mut self, score: &Score) -> Result<W> {
+        // the mimetype must be the first file and stored uncompressed
+        let stored = FileOptions::default().compression_method(CompressionMethod::Stored);
+        self.archive.start_file("mimetype", stored)?;
+        self.archive.write_all(MIMETYPE.as_bytes())?;
+
+        let deflated = FileOptions::default().compression_method(CompressionMethod::Deflated);
+        self.archive.start_file(CONTAINER_PATH, deflated)?;
+        self.archive
+            .write_all(self.container_xml(SCORE_PATH).as_bytes())?;
+
+        self.archive.start_file(SCORE_PATH, deflated)?;
+        self.archive
+            .write_all(score.to_xml_with(&self.options).as_bytes())?;
+
+        Ok(self.archive.finish()?)
+    }
+
+    fn container_xml(&self, path: &str) -> String {
+        let rootfile = Element::new("rootfile")
+            .with_attr("full-path", path)
+            .with_attr("media-type", MUSIC_XML_MEDIA_TYPE);
+        let container =
+            Element::new("container").with_child(Element::new("rootfiles").with_child(rootfile));
+
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>");
+        xml.push_str(self.options.newline.as_str());
+        container.write_with(&mut xml, 0, &self.options);
+
+        xml
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+
+    #[test]
+    fn write_then_read_ok() {
+        let xml = r#"
+            <score-partwise>
+                <part-list>
+                    <score-part id="P1">
+                        <part-name>Flute</part-name>
+                    </score-part>
+                </part-list>
+                <part id="P1">
+                    <measure number="1">
+                        <note>
+                            <rest />
+                            <duration>4</duration>
+                        </note>
+                    </measure>
+                </part>
+            </score-partwise>"#;
+        let score = Score::from_xml(xml).unwrap();
+
+        let bytes = MxlWriter::new(Cursor::new(Vec::new()))
+            .write(&score)
+            .unwrap()
+            .into_inner();
+
+        let mut archive = ZipArchive::new(Cursor::new(&bytes)).unwrap();
+        assert_eq!(archive.by_index(0).unwrap().name(), "mimetype");
+
+        let mut mxl = Mxl::new(Cursor::new(&bytes)).unwrap();
+        let read = mxl.parse_music_xml();
+        assert!(read.is_ok());
+        assert_eq!(read.unwrap(), score);
+    }
+}
@@ -0,0 +1,1574 @@
+use std::num::{NonZeroU32, NonZeroU8};
+
+use roxmltree::Node;
+
+use super::{
+    children_to_nodes, count_chd, parse_attr, parse_chd_text, parse_children, parse_option_chd,
+    parse_optional_attr, parse_optional_chd_text, parse_optional_text, parse_text, Editorial,
+    FromNode, GroupSymbolValue, StartStop, Step, ToNode, Unknown, YesNo,
+};
+use crate::{
+    error::{
+        Error::{ExclusiveNodeGroupNotFound, NodeNotFound},
+        Result,
+    },
+    writer::Element,
+};
+
+xml_enum! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum ClefSign {
+        G => "G",
+        F => "F",
+        C => "C",
+        Percussion => "percussion",
+        Tab => "TAB",
+        Jianpu => "jianpu",
+        None => "none",
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Clef {
+    /// Staff the clef applies to, absent for the first staff
+    pub number: Option<u8>,
+    pub sign: ClefSign,
+    pub line: Option<u8>,
+    /// Octaves the clef sounds from its written pitch, e.g. -1 for a treble 8vb clef
+    pub clef_octave_change: Option<i8>,
+    pub unknown: Unknown,
+}
+
+impl FromNode for Clef {
+    fn tag() -> &'static str {
+        "clef"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        Ok(Clef {
+            number: parse_optional_attr(node, "number")?,
+            sign: parse_chd_text(node, "sign")?,
+            line: parse_optional_chd_text(node, "line")?,
+            clef_octave_change: parse_optional_chd_text(node, "clef-octave-change")?,
+            unknown: Unknown::parse(node, &["number"], &["sign", "line", "clef-octave-change"]),
+        })
+    }
+}
+
+impl ToNode for Clef {
+    fn to_node(&self) -> Element {
+        let node = Element::new(Self::tag())
+            .with_optional_attr("number", self.number)
+            .with_chd_text("sign", self.sign)
+            .with_optional_chd_text("line", self.line)
+            .with_optional_chd_text("clef-octave-change", self.clef_octave_change);
+        self.unknown.write(node)
+    }
+}
+
+impl Clef {
+    /// Staff line of the clef counted from the bottom, defaulting to the usual line of the sign
+    pub fn staff_line(&self) -> Option<u8> {
+        self.line.or(match self.sign {
+            ClefSign::G => Some(2),
+            ClefSign::F => Some(4),
+            ClefSign::C => Some(3),
+            _ => None,
+        })
+    }
+}
+
+xml_enum! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum Mode {
+        Major => "major",
+        Minor => "minor",
+        Dorian => "dorian",
+        Phrygian => "phrygian",
+        Lydian => "lydian",
+        Mixolydian => "mixolydian",
+        Aeolian => "aeolian",
+        Ionian => "ionian",
+        Locrian => "locrian",
+        None => "none",
+    }
+}
+
+/// Altered step of a non-traditional key signature
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeyAlteration {
+    pub step: Step,
+    /// Semitones, may be microtonal
+    pub alter: f64,
+    /// Accidental displayed if it differs from the one implied by the alter
+    pub accidental: Option<String>,
+}
+
+xml_enum! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum CancelLocation {
+        Left => "left",
+        Right => "right",
+        BeforeBarline => "before-barline",
+    }
+}
+
+/// Steps in the order sharps are added to a key signature
+const SHARPS: [Step; 7] = [
+    Step::F,
+    Step::C,
+    Step::G,
+    Step::D,
+    Step::A,
+    Step::E,
+    Step::B,
+];
+
+/// Steps in the order flats are added to a key signature
+const FLATS: [Step; 7] = [
+    Step::B,
+    Step::E,
+    Step::A,
+    Step::D,
+    Step::G,
+    Step::C,
+    Step::F,
+];
+
+/// Naturals cancelling the previous key signature
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cancel {
+    /// Fifths of the cancelled key signature
+    pub fifths: i8,
+    /// Where the naturals are drawn relative to the new key, left if absent
+    pub location: Option<CancelLocation>,
+    pub unknown: Unknown,
+}
+
+impl FromNode for Cancel {
+    fn tag() -> &'static str {
+        "cancel"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        Ok(Cancel {
+            fifths: parse_text(node, Self::tag())?,
+            location: parse_optional_attr(node, "location")?,
+            unknown: Unknown::parse_attrs(node, &["location"]),
+        })
+    }
+}
+
+impl ToNode for Cancel {
+    fn to_node(&self) -> Element {
+        let node = Element::new(Self::tag())
+            .with_optional_attr("location", self.location)
+            .with_text(self.fifths);
+        self.unknown.write(node)
+    }
+}
+
+impl Cancel {
+    /// Steps of the cancelled accidentals in key signature order
+    pub fn steps(&self) -> Vec<Step> {
+        let (order, count) = match self.fifths {
+            f if f < 0 => (FLATS, f.unsigned_abs()),
+            f => (SHARPS, f.unsigned_abs()),
+        };
+        order[..(count as usize).min(7)].to_vec()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum KeyType {
+    Traditional {
+        cancel: Option<Cancel>,
+        /// Number of flats (negative) or sharps (positive)
+        fifths: i8,
+        mode: Option<Mode>,
+    },
+    NonTraditional(Vec<KeyAlteration>),
+}
+
+/// Octave to display an accidental of the key signature in
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyOctave {
+    /// Position of the accidental in the key signature, starting from 1
+    pub number: u8,
+    pub octave: u8,
+    /// Applies to the cancellation of the previous key if yes
+    pub cancel: Option<YesNo>,
+    pub unknown: Unknown,
+}
+
+impl FromNode for KeyOctave {
+    fn tag() -> &'static str {
+        "key-octave"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        Ok(KeyOctave {
+            number: parse_attr(node, "number")?,
+            octave: parse_text(node, Self::tag())?,
+            cancel: parse_optional_attr(node, "cancel")?,
+            unknown: Unknown::parse_attrs(node, &["number", "cancel"]),
+        })
+    }
+}
+
+impl ToNode for KeyOctave {
+    fn to_node(&self) -> Element {
+        let node = Element::new(Self::tag())
+            .with_attr("number", self.number)
+            .with_optional_attr("cancel", self.cancel)
+            .with_text(self.octave);
+        self.unknown.write(node)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Key {
+    /// Applies to all staves if absent
+    pub number: Option<u8>,
+    pub key_type: KeyType,
+    pub key_octave: Vec<KeyOctave>,
+    pub unknown: Unknown,
+}
+
+impl FromNode for Key {
+    fn tag() -> &'static str {
+        "key"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        let key_type = if count_chd(node, "fifths") > 0 {
+            KeyType::Traditional {
+                cancel: parse_option_chd(node)?,
+                fifths: parse_chd_text(node, "fifths")?,
+                mode: parse_optional_chd_text(node, "mode")?,
+            }
+        } else {
+            let mut alterations: Vec<KeyAlteration> = Vec::new();
+            for c in node.children() {
+                let name = c.tag_name().name();
+                match (name, alterations.last_mut()) {
+                    ("key-step", _) => alterations.push(KeyAlteration {
+                        step: parse_text(&c, "key-step")?,
+                        alter: 0.,
+                        accidental: None,
+                    }),
+                    ("key-alter", Some(a)) => a.alter = parse_text(&c, "key-alter")?,
+                    ("key-accidental", Some(a)) => a.accidental = parse_optional_text(&c),
+                    _ => {}
+                }
+            }
+            if alterations.is_empty() {
+                return Err(ExclusiveNodeGroupNotFound {
+                    tags: vec!["fifths", "key-step"],
+                    parent_tag: Self::tag(),
+                });
+            }
+
+            KeyType::NonTraditional(alterations)
+        };
+
+        Ok(Key {
+            number: parse_optional_attr(node, "number")?,
+            key_type,
+            key_octave: parse_children(node)?,
+            unknown: Unknown::parse(
+                node,
+                &["number"],
+                &[
+                    "cancel",
+                    "fifths",
+                    "mode",
+                    "key-step",
+                    "key-alter",
+                    "key-accidental",
+                    "key-octave",
+                ],
+            ),
+        })
+    }
+}
+
+impl ToNode for Key {
+    fn to_node(&self) -> Element {
+        let el = Element::new(Self::tag()).with_optional_attr("number", self.number);
+
+        let el = match &self.key_type {
+            KeyType::Traditional {
+                cancel,
+                fifths,
+                mode,
+            } => el
+                .with_optional_child(cancel.as_ref().map(ToNode::to_node))
+                .with_chd_text("fifths", fifths)
+                .with_optional_chd_text("mode", *mode),
+            KeyType::NonTraditional(alterations) => {
+                el.with_children(alterations.iter().flat_map(|a| {
+                    [
+                        Some(Element::new("key-step").with_text(a.step)),
+                        Some(Element::new("key-alter").with_text(a.alter)),
+                        a.accidental
+                            .as_ref()
+                            .map(|acc| Element::new("key-accidental").with_text(acc)),
+                    ]
+                    .into_iter()
+                    .flatten()
+                }))
+            }
+        };
+        let node = el.with_children(children_to_nodes(&self.key_octave));
+        self.unknown.write(node)
+    }
+}
+
+impl Key {
+    pub fn fifths(&self) -> Option<i8> {
+        match self.key_type {
+            KeyType::Traditional { fifths, .. } => Some(fifths),
+            KeyType::NonTraditional(_) => None,
+        }
+    }
+
+    /// Alteration in semitones the key signature applies to the given step
+    pub fn alter(&self, step: Step) -> f64 {
+        match &self.key_type {
+            KeyType::Traditional { fifths, .. } => {
+                // beyond 7 sharps or flats every step is already altered
+                let fifths = (*fifths).clamp(-7, 7);
+                match SHARPS.iter().position(|s| *s == step) {
+                    Some(i) if (i as i8) < fifths => 1.,
+                    Some(i) if (6 - i as i8) < -fifths => -1.,
+                    _ => 0.,
+                }
+            }
+            KeyType::NonTraditional(alterations) => alterations
+                .iter()
+                .find(|a| a.step == step)
+                .map_or(0., |a| a.alter),
+        }
+    }
+}
+
+xml_enum! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum TimeSymbol {
+        Common => "common",
+        Cut => "cut",
+        SingleNumber => "single-number",
+        Note => "note",
+        DottedNote => "dotted-note",
+        Normal => "normal",
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TimeSignature {
+    /// May be a composite value such as "3+2"
+    pub beats: String,
+    pub beat_type: u8,
+}
+
+impl TimeSignature {
+    /// Beats and beat-type pairs in document order
+    fn parse_all(node: &Node) -> Result<Vec<Self>> {
+        let beats: Vec<_> = node
+            .children()
+            .filter(|c| c.tag_name().name() == "beats")
+            .collect();
+        let beat_types: Vec<_> = node
+            .children()
+            .filter(|c| c.tag_name().name() == "beat-type")
+            .collect();
+        if beats.len() != beat_types.len() {
+            return Err(NodeNotFound {
+                tag: if beats.len() > beat_types.len() {
+                    "beat-type"
+                } else {
+                    "beats"
+                },
+                parent_tag: node.tag_name().name().to_owned(),
+            });
+        }
+
+        beats
+            .iter()
+            .zip(&beat_types)
+            .map(|(b, t)| {
+                Ok(TimeSignature {
+                    beats: parse_text(b, "beats")?,
+                    // a zero beat-type has no meaning and would divide by zero
+                    beat_type: parse_text::<NonZeroU8>(t, "beat-type")?.get(),
+                })
+            })
+            .collect()
+    }
+
+    fn to_nodes(signatures: &[Self]) -> impl Iterator<Item = Element> + '_ {
+        signatures.iter().flat_map(|s| {
+            [
+                Element::new("beats").with_text(&s.beats),
+                Element::new("beat-type").with_text(s.beat_type),
+            ]
+        })
+    }
+
+    /// Total beats, summing composite values like "3+2"
+    pub fn beat_count(&self) -> Option<u32> {
+        self.beats
+            .split('+')
+            .try_fold(0u32, |sum, b| sum.checked_add(b.trim().parse().ok()?))
+    }
+
+    /// Length in the given divisions per quarter note, none if it overflows
+    pub fn duration(&self, divisions: u32) -> Option<u32> {
+        self.beat_count()?
+            .checked_mul(divisions)?
+            .checked_mul(4)?
+            .checked_div(self.beat_type as u32)
+    }
+}
+
+/// Alternative time signature shown alongside, e.g. 6/8 (3/4)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Interchangeable {
+    /// Separator between the signatures, e.g. "parentheses" or "equals"
+    pub time_relation: Option<String>,
+    pub signatures: Vec<TimeSignature>,
+    pub unknown: Unknown,
+}
+
+impl FromNode for Interchangeable {
+    fn tag() -> &'static str {
+        "interchangeable"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        Ok(Interchangeable {
+            time_relation: parse_optional_chd_text(node, "time-relation")?,
+            signatures: TimeSignature::parse_all(node)?,
+            unknown: Unknown::parse(node, &[], &["time-relation", "beats", "beat-type"]),
+        })
+    }
+}
+
+impl ToNode for Interchangeable {
+    fn to_node(&self) -> Element {
+        let node = Element::new(Self::tag())
+            .with_optional_chd_text("time-relation", self.time_relation.as_ref())
+            .with_children(TimeSignature::to_nodes(&self.signatures));
+        self.unknown.write(node)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TimeType {
+    /// One signature or several added together like 3/4 + 6/8
+    Signatures {
+        signatures: Vec<TimeSignature>,
+        interchangeable: Option<Interchangeable>,
+    },
+    /// Unmeasured music, with an optional symbol to display
+    SenzaMisura(Option<String>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Time {
+    /// Applies to all staves if absent
+    pub number: Option<u8>,
+    pub symbol: Option<TimeSymbol>,
+    pub time_type: TimeType,
+    pub unknown: Unknown,
+}
+
+impl FromNode for Time {
+    fn tag() -> &'static str {
+        "time"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        let senza_misura = node
+            .children()
+            .find(|c| c.tag_name().name() == "senza-misura");
+
+        let time_type = match senza_misura {
+            Some(s) => TimeType::SenzaMisura(parse_optional_text(&s)),
+            None => {
+                let signatures = TimeSignature::parse_all(node)?;
+                if signatures.is_empty() {
+                    return Err(ExclusiveNodeGroupNotFound {
+                        tags: vec!["beats", "senza-misura"],
+                        parent_tag: Self::tag(),
+                    });
+                }
+
+                TimeType::Signatures {
+                    signatures,
+                    interchangeable: parse_option_chd(node)?,
+                }
+            }
+        };
+
+        Ok(Time {
+            number: parse_optional_attr(node, "number")?,
+            symbol: parse_optional_attr(node, "symbol")?,
+            time_type,
+            unknown: Unknown::parse(
+                node,
+                &["number", "symbol"],
+                &["beats", "beat-type", "interchangeable", "senza-misura"],
+            ),
+        })
+    }
+}
+
+impl ToNode for Time {
+    fn to_node(&self) -> Element {
+        let el = Element::new(Self::tag())
+            .with_optional_attr("number", self.number)
+            .with_optional_attr("symbol", self.symbol);
+        let node = match &self.time_type {
+            TimeType::Signatures {
+                signatures,
+                interchangeable,
+            } => el
+                .with_children(TimeSignature::to_nodes(signatures))
+                .with_optional_child(interchangeable.as_ref().map(ToNode::to_node)),
+            TimeType::SenzaMisura(symbol) => {
+                el.with_child(Element::new("senza-misura").with_optional_text(symbol.as_ref()))
+            }
+        };
+        self.unknown.write(node)
+    }
+}
+
+impl Time {
+    /// Time signature of a single simple meter like 3/4
+    pub fn new(beats: u32, beat_type: u8) -> Self {
+        Time {
+            number: None,
+            symbol: None,
+            time_type: TimeType::Signatures {
+                signatures: vec![TimeSignature {
+                    beats: beats.to_string(),
+                    beat_type,
+                }],
+                interchangeable: None,
+            },
+            unknown: Unknown::default(),
+        }
+    }
+
+    pub fn signatures(&self) -> &[TimeSignature] {
+        match &self.time_type {
+            TimeType::Signatures { signatures, .. } => signatures,
+            TimeType::SenzaMisura(_) => &[],
+        }
+    }
+
+    /// Length of a measure in the given divisions per quarter note,
+    /// None for senza-misura
+    pub fn measure_duration(&self, divisions: u32) -> Option<u32> {
+        match &self.time_type {
+            TimeType::Signatures { signatures, .. } => signatures
+                .iter()
+                .try_fold(0u32, |sum, s| sum.checked_add(s.duration(divisions)?)),
+            TimeType::SenzaMisura(_) => None,
+        }
+    }
+}
+
+xml_enum! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum StaffType {
+        Ossia => "ossia",
+        Editorial => "editorial",
+        Cue => "cue",
+        Alternate => "alternate",
+        Regular => "regular",
+    }
+}
+
+/// Staff size as a percentage of the default size
+#[derive(Debug, Clone, PartialEq)]
+pub struct StaffSize {
+    pub value: f64,
+    /// Percentage the staff spacing is scaled by, if not the same as the staff size
+    pub scaling: Option<f64>,
+    pub unknown: Unknown,
+}
+
+impl FromNode for StaffSize {
+    fn tag() -> &'static str {
+        "staff-size"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        Ok(StaffSize {
+            value: parse_text(node, Self::tag())?,
+            scaling: parse_optional_attr(node, "scaling")?,
+            unknown: Unknown::parse_attrs(node, &["scaling"]),
+        })
+    }
+}
+
+impl ToNode for StaffSize {
+    fn to_node(&self) -> Element {
+        let node = Element::new(Self::tag())
+            .with_optional_attr("scaling", self.scaling)
+            .with_text(self.value);
+        self.unknown.write(node)
+    }
+}
+
+/// Open string tuning of a tablature staff line, numbered from the bottom
+#[derive(Debug, Clone, PartialEq)]
+pub struct StaffTuning {
+    pub line: u8,
+    pub tuning_step: Step,
+    pub tuning_alter: Option<f64>,
+    pub tuning_octave: u8,
+    pub unknown: Unknown,
+}
+
+impl FromNode for StaffTuning {
+    fn tag() -> &'static str {
+        "staff-tuning"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        Ok(StaffTuning {
+            line: parse_attr(node, "line")?,
+            tuning_step: parse_chd_text(node, "tuning-step")?,
+            tuning_alter: parse_optional_chd_text(node, "tuning-alter")?,
+            tuning_octave: parse_chd_text(node, "tuning-octave")?,
+            unknown: Unknown::parse(
+                node,
+                &["line"],
+                &["tuning-step", "tuning-alter", "tuning-octave"],
+            ),
+        })
+    }
+}
+
+impl ToNode for StaffTuning {
+    fn to_node(&self) -> Element {
+        let node = Element::new(Self::tag())
+            .with_attr("line", self.line)
+            .with_chd_text("tuning-step", self.tuning_step)
+            .with_optional_chd_text("tuning-alter", self.tuning_alter)
+            .with_chd_text("tuning-octave", self.tuning_octave);
+        self.unknown.write(node)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct StaffDetails {
+    /// Applies to all staves if absent
+    pub number: Option<u8>,
+    pub staff_type: Option<StaffType>,
+    pub staff_lines: Option<u8>,
+    pub staff_tuning: Vec<StaffTuning>,
+    /// Fret of the capo on a tablature staff
+    pub capo: Option<u8>,
+    pub staff_size: Option<StaffSize>,
+    pub unknown: Unknown,
+}
+
+impl FromNode for StaffDetails {
+    fn tag() -> &'static str {
+        "staff-details"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        Ok(StaffDetails {
+            number: parse_optional_attr(node, "number")?,
+            staff_type: parse_optional_chd_text(node, "staff-type")?,
+            staff_lines: parse_optional_chd_text(node, "staff-lines")?,
+            staff_tuning: parse_children(node)?,
+            capo: parse_optional_chd_text(node, "capo")?,
+            staff_size: parse_option_chd(node)?,
+            unknown: Unknown::parse(
+                node,
+                &["number"],
+                &[
+                    "staff-type",
+                    "staff-lines",
+                    "staff-tuning",
+                    "capo",
+                    "staff-size",
+                ],
+            ),
+        })
+    }
+}
+
+impl ToNode for StaffDetails {
+    fn to_node(&self) -> Element {
+        let node = Element::new(Self::tag())
+            .with_optional_attr("number", self.number)
+            .with_optional_chd_text("staff-type", self.staff_type)
+            .with_optional_chd_text("staff-lines", self.staff_lines)
+            .with_children(children_to_nodes(&self.staff_tuning))
+            .with_optional_chd_text("capo", self.capo)
+            .with_optional_child(self.staff_size.as_ref().map(ToNode::to_node));
+        self.unknown.write(node)
+    }
+}
+
+impl StaffDetails {
+    /// Number of staff lines, 5 if not specified
+    pub fn line_count(&self) -> u8 {
+        self.staff_lines.unwrap_or(5)
+    }
+}
+
+/// Symbol joining the staves of a multi-staff part, e.g. the brace of a piano
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartSymbol {
+    pub value: GroupSymbolValue,
+    /// Staff the symbol starts at, the first staff if absent
+    pub top_staff: Option<u8>,
+    /// Staff the symbol ends at, the last staff if absent
+    pub bottom_staff: Option<u8>,
+    pub unknown: Unknown,
+}
+
+impl FromNode for PartSymbol {
+    fn tag() -> &'static str {
+        "part-symbol"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        Ok(PartSymbol {
+            value: parse_text(node, Self::tag())?,
+            top_staff: parse_optional_attr(node, "top-staff")?,
+            bottom_staff: parse_optional_attr(node, "bottom-staff")?,
+            unknown: Unknown::parse_attrs(node, &["top-staff", "bottom-staff"]),
+        })
+    }
+}
+
+impl ToNode for PartSymbol {
+    fn to_node(&self) -> Element {
+        let node = Element::new(Self::tag())
+            .with_optional_attr("top-staff", self.top_staff)
+            .with_optional_attr("bottom-staff", self.bottom_staff)
+            .with_text(self.value);
+        self.unknown.write(node)
+    }
+}
+
+/// Interval from the written to the sounding pitch of a transposing instrument
+#[derive(Debug, Clone, PartialEq)]
+pub struct Transpose {
+    /// Applies to all staves if absent
+    pub number: Option<u8>,
+    /// Steps in the diatonic scale, e.g. -1 for a Bb clarinet
+    pub diatonic: Option<i8>,
+    /// Semitones, e.g. -2 for a Bb clarinet
+    pub chromatic: f64,
+    pub octave_change: Option<i8>,
+    /// Doubled an octave lower, e.g. for a contrabass
+    pub double: bool,
+    pub unknown: Unknown,
+}
+
+impl FromNode for Transpose {
+    fn tag() -> &'static str {
+        "transpose"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        Ok(Transpose {
+            number: parse_optional_attr(node, "number")?,
+            diatonic: parse_optional_chd_text(node, "diatonic")?,
+            chromatic: parse_chd_text(node, "chromatic")?,
+            octave_change: parse_optional_chd_text(node, "octave-change")?,
+            double: count_chd(node, "double") > 0,
+            unknown: Unknown::parse(
+                node,
+                &["number"],
+                &["diatonic", "chromatic", "octave-change", "double"],
+            ),
+        })
+    }
+}
+
+impl ToNode for Transpose {
+    fn to_node(&self) -> Element {
+        let node = Element::new(Self::tag())
+            .with_optional_attr("number", self.number)
+            .with_optional_chd_text("diatonic", self.diatonic)
+            .with_chd_text("chromatic", self.chromatic)
+            .with_optional_chd_text("octave-change", self.octave_change)
+            .with_optional_child(self.double.then(|| Element::new("double")));
+        self.unknown.write(node)
+    }
+}
+
+/// Clef of a part extracted from a concert score
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartClef {
+    pub sign: ClefSign,
+    pub line: Option<u8>,
+    pub clef_octave_change: Option<i8>,
+    pub unknown: Unknown,
+}
+
+impl FromNode for PartClef {
+    fn tag() -> &'static str {
+        "part-clef"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        Ok(PartClef {
+            sign: parse_chd_text(node, "sign")?,
+            line: parse_optional_chd_text(node, "line")?,
+            clef_octave_change: parse_optional_chd_text(node, "clef-octave-change")?,
+            unknown: Unknown::parse(node, &[], &["sign", "line", "clef-octave-change"]),
+        })
+    }
+}
+
+impl ToNode for PartClef {
+    fn to_node(&self) -> Element {
+        let node = Element::new(Self::tag())
+            .with_chd_text("sign", self.sign)
+            .with_optional_chd_text("line", self.line)
+            .with_optional_chd_text("clef-octave-change", self.clef_octave_change);
+        self.unknown.write(node)
+    }
+}
+
+/// Clef and transposition of a part written at concert pitch in the score,
+/// for extracting the part at transposed pitch
+#[derive(Debug, Clone, PartialEq)]
+pub struct ForPart {
+    /// Applies to all staves if absent
+    pub number: Option<u8>,
+    pub part_clef: Option<PartClef>,
+    /// Transposition of the extracted part, the number is always absent
+    pub part_transpose: Transpose,
+    pub unknown: Unknown,
+}
+
+impl FromNode for ForPart {
+    fn tag() -> &'static str {
+        "for-part"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        let part_transpose = node
+            .children()
+            .find(|c| c.tag_name().name() == "part-transpose")
+            .ok_or(NodeNotFound {
+                tag: "part-transpose",
+                parent_tag: Self::tag().to_owned(),
+            })?;
+
+        Ok(ForPart {
+            number: parse_optional_attr(node, "number")?,
+            part_clef: parse_option_chd(node)?,
+            part_transpose: Transpose::from_node(&part_transpose)?,
+            unknown: Unknown::parse(node, &["number"], &["part-clef", "part-transpose"]),
+        })
+    }
+}
+
+impl ToNode for ForPart {
+    fn to_node(&self) -> Element {
+        let mut part_transpose = self.part_transpose.to_node();
+        part_transpose.name = "part-transpose".to_owned();
+        let node = Element::new(Self::tag())
+            .with_optional_attr("number", self.number)
+            .with_optional_child(self.part_clef.as_ref().map(ToNode::to_node))
+            .with_child(part_transpose);
+        self.unknown.write(node)
+    }
+}
+
+/// Condensed notation spanning measures or beats
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MeasureStyleType {
+    /// Multi-measure rest over the given count of measures
+    MultipleRest {
+        count: u32,
+        use_symbols: Option<YesNo>,
+    },
+    /// Repeat of the previous measures, stopping at the first measure no longer repeated
+    MeasureRepeat {
+        repeat_type: StartStop,
+        /// Number of measures repeated, 1 if absent
+        measures: Option<u32>,
+        slashes: Option<u32>,
+    },
+    BeatRepeat {
+        repeat_type: StartStop,
+        slashes: Option<u32>,
+        use_dots: Option<YesNo>,
+    },
+    Slash {
+        slash_type: StartStop,
+        use_dots: Option<YesNo>,
+        use_stems: Option<YesNo>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MeasureStyle {
+    /// Applies to all staves if absent
+    pub number: Option<u8>,
+    pub style: MeasureStyleType,
+    pub unknown: Unknown,
+}
+
+impl FromNode for MeasureStyle {
+    fn tag() -> &'static str {
+        "measure-style"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        let tags = vec!["multiple-rest", "measure-repeat", "beat-repeat", "slash"];
+        let c = node
+            .children()
+            .find(|c| tags.contains(&c.tag_name().name()))
+            .ok_or(ExclusiveNodeGroupNotFound {
+                tags,
+                parent_tag: Self::tag(),
+            })?;
+
+        let style = match c.tag_name().name() {
+            "multiple-rest" => MeasureStyleType::MultipleRest {
+                count: parse_text(&c, "multiple-rest")?,
+                use_symbols: parse_optional_attr(&c, "use-symbols")?,
+            },
+            "measure-repeat" => MeasureStyleType::MeasureRepeat {
+                repeat_type: parse_attr(&c, "type")?,
+                measures: match parse_optional_text(&c) {
+                    Some(_) => Some(parse_text(&c, "measure-repeat")?),
+                    None => None,
+                },
+                slashes: parse_optional_attr(&c, "slashes")?,
+            },
+            "beat-repeat" => MeasureStyleType::BeatRepeat {
+                repeat_type: parse_attr(&c, "type")?,
+                slashes: parse_optional_attr(&c, "slashes")?,
+                use_dots: parse_optional_attr(&c, "use-dots")?,
+            },
+            _ => MeasureStyleType::Slash {
+                slash_type: parse_attr(&c, "type")?,
+                use_dots: parse_optional_attr(&c, "use-dots")?,
+                use_stems: parse_optional_attr(&c, "use-stems")?,
+            },
+        };
+
+        Ok(MeasureStyle {
+            number: parse_optional_attr(node, "number")?,
+            style,
+            unknown: Unknown::parse(
+                node,
+                &["number"],
+                &["multiple-rest", "measure-repeat", "beat-repeat", "slash"],
+            ),
+        })
+    }
+}
+
+impl ToNode for MeasureStyle {
+    fn to_node(&self) -> Element {
+        let style = match &self.style {
+            MeasureStyleType::MultipleRest { count, use_symbols } => Element::new("multiple-rest")
+                .with_optional_attr("use-symbols", *use_symbols)
+                .with_text(count),
+            MeasureStyleType::MeasureRepeat {
+                repeat_type,
+                measures,
+                slashes,
+            } => Element::new("measure-repeat")
+                .with_attr("type", repeat_type)
+                .with_optional_attr("slashes", *slashes)
+                .with_optional_text(*measures),
+            MeasureStyleType::BeatRepeat {
+                repeat_type,
+                slashes,
+                use_dots,
+            } => Element::new("beat-repeat")
+                .with_attr("type", repeat_type)
+                .with_optional_attr("slashes", *slashes)
+                .with_optional_attr("use-dots", *use_dots),
+            MeasureStyleType::Slash {
+                slash_type,
+                use_dots,
+                use_stems,
+            } => Element::new("slash")
+                .with_attr("type", slash_type)
+                .with_optional_attr("use-dots", *use_dots)
+                .with_optional_attr("use-stems", *use_stems),
+        };
+        let node = Element::new(Self::tag())
+            .with_optional_attr("number", self.number)
+            .with_child(style);
+        self.unknown.write(node)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Attribute {
+    pub editorial: Editorial,
+    /// Divisions per quarter note, absent if unchanged
+    pub divisions: Option<NonZeroU32>,
+    pub key: Vec<Key>,
+    pub time: Vec<Time>,
+    /// Number of staves in the part, 1 if never set
+    pub staves: Option<u8>,
+    pub part_symbol: Option<PartSymbol>,
+    pub clef: Vec<Clef>,
+    pub staff_details: Vec<StaffDetails>,
+    pub transpose: Vec<Transpose>,
+    /// Transpositions of parts in a concert score, replacing transpose
+    pub for_part: Vec<ForPart>,
+    pub measure_style: Vec<MeasureStyle>,
+    pub unknown: Unknown,
+}
+
+impl FromNode for Attribute {
+    fn tag() -> &'static str {
+        "attributes"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        Ok(Attribute {
+            editorial: Editorial::parse(node)?,
+            divisions: parse_optional_chd_text(node, "divisions")?,
+            key: parse_children(node)?,
+            time: parse_children(node)?,
+            staves: parse_optional_chd_text(node, "staves")?,
+            part_symbol: parse_option_chd(node)?,
+            clef: parse_children(node)?,
+            staff_details: parse_children(node)?,
+            transpose: parse_children(node)?,
+            for_part: parse_children(node)?,
+            measure_style: parse_children(node)?,
+            unknown: Unknown::parse(
+                node,
+                &[],
+                &[
+                    "footnote",
+                    "level",
+                    "divisions",
+                    "key",
+                    "time",
+                    "staves",
+                    "part-symbol",
+                    "clef",
+                    "staff-details",
+                    "transpose",
+                    "for-part",
+                    "measure-style",
+                ],
+            ),
+        })
+    }
+}
+
+impl ToNode for Attribute {
+    fn to_node(&self) -> Element {
+        let node = Element::new(Self::tag())
+            .with_children(self.editorial.to_nodes())
+            .with_optional_chd_text("divisions", self.divisions)
+            .with_children(children_to_nodes(&self.key))
+            .with_children(children_to_nodes(&self.time))
+            .with_optional_chd_text("staves", self.staves)
+            .with_optional_child(self.part_symbol.as_ref().map(ToNode::to_node))
+            .with_children(children_to_nodes(&self.clef))
+            .with_children(children_to_nodes(&self.staff_details))
+            .with_children(children_to_nodes(&self.transpose))
+            .with_children(children_to_nodes(&self.for_part))
+            .with_children(children_to_nodes(&self.measure_style));
+        self.unknown.write(node)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use roxmltree::Document;
+
+    use super::*;
+    use crate::error::Error::NodeTextParseFailed;
+
+    #[test]
+    fn clef_octave_change_ok() {
+        let xml = r#"
+            <clef number="2">
+                <sign>G</sign>
+                <clef-octave-change>-1</clef-octave-change>
+            </clef>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let clef = Clef::from_node(&node);
+        assert!(clef.is_ok());
+        assert_eq!(
+            clef.unwrap(),
+            Clef {
+                number: Some(2),
+                sign: ClefSign::G,
+                line: None,
+                clef_octave_change: Some(-1),
+                unknown: Unknown::default()
+            }
+        );
+
+        let xml = r#"
+            <clef>
+                <sign>F</sign>
+                <line>4</line>
+            </clef>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let clef = Clef::from_node(&node).unwrap();
+        assert_eq!(clef.number, None);
+        assert_eq!(clef.to_node(), Element::from(&node));
+    }
+
+    #[test]
+    fn attribute_divisions_ok() {
+        // Finale exports commonly use 768 or 1024 divisions per quarter
+        for divisions in [768, 1024, 100800] {
+            let xml = format!(
+                "<attributes><divisions>{}</divisions></attributes>",
+                divisions
+            );
+            let doc = Document::parse(&xml).unwrap();
+            let attr = Attribute::from_node(&doc.root_element()).unwrap();
+            assert_eq!(attr.divisions.map(NonZeroU32::get), Some(divisions));
+        }
+
+        let xml = "<attributes><divisions>0</divisions></attributes>";
+        let doc = Document::parse(xml).unwrap();
+        assert!(Attribute::from_node(&doc.root_element()).is_err());
+    }
+
+    #[test]
+    fn attribute_key_ok() {
+        let xml = r#"
+            <attributes>
+                <divisions>4</divisions>
+                <key>
+                    <fifths>-3</fifths>
+                    <mode>minor</mode>
+                </key>
+                <staves>1</staves>
+            </attributes>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let attr = Attribute::from_node(&node);
+        assert!(attr.is_ok());
+        assert_eq!(
+            attr.unwrap().key,
+            vec![Key {
+                number: None,
+                key_type: KeyType::Traditional {
+                    cancel: None,
+                    fifths: -3,
+                    mode: Some(Mode::Minor)
+                },
+                key_octave: vec![],
+                unknown: Unknown::default()
+            }]
+        );
+    }
+
+    #[test]
+    fn key_alter_ok() {
+        let key = Key {
+            number: None,
+            key_type: KeyType::Traditional {
+                cancel: None,
+                fifths: -3,
+                mode: None,
+            },
+            key_octave: vec![],
+            unknown: Unknown::default(),
+        };
+        assert_eq!(key.alter(Step::B), -1.);
+        assert_eq!(key.alter(Step::A), -1.);
+        assert_eq!(key.alter(Step::D), 0.);
+        assert_eq!(key.alter(Step::F), 0.);
+
+        let key = Key {
+            key_type: KeyType::Traditional {
+                cancel: None,
+                fifths: i8::MIN,
+                mode: None,
+            },
+            ..key
+        };
+        assert_eq!(key.alter(Step::F), -1.);
+    }
+
+    #[test]
+    fn key_cancel_ok() {
+        let xml = r#"
+            <key>
+                <cancel location="before-barline">-2</cancel>
+                <fifths>1</fifths>
+            </key>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let key = Key::from_node(&node).unwrap();
+        let cancel = match &key.key_type {
+            KeyType::Traditional { cancel, .. } => cancel.as_ref().unwrap(),
+            _ => panic!("traditional key expected"),
+        };
+        assert_eq!(cancel.location, Some(CancelLocation::BeforeBarline));
+        assert_eq!(cancel.steps(), vec![Step::B, Step::E]);
+        assert_eq!(key.fifths(), Some(1));
+        assert_eq!(key.to_node(), Element::from(&node));
+
+        let cancel = Cancel {
+            fifths: i8::MIN,
+            ..cancel.clone()
+        };
+        assert_eq!(cancel.steps(), FLATS.to_vec());
+    }
+
+    #[test]
+    fn key_non_traditional_ok() {
+        let xml = r#"
+            <key>
+                <key-step>B</key-step>
+                <key-alter>-1</key-alter>
+                <key-step>E</key-step>
+                <key-alter>-0.5</key-alter>
+                <key-accidental>slash-flat</key-accidental>
+                <key-step>F</key-step>
+                <key-alter>1</key-alter>
+                <key-octave number="1">4</key-octave>
+                <key-octave number="3" cancel="yes">5</key-octave>
+            </key>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let key = Key::from_node(&node);
+        assert!(key.is_ok());
+        let key = key.unwrap();
+        assert_eq!(key.fifths(), None);
+        assert_eq!(key.alter(Step::E), -0.5);
+        assert_eq!(key.alter(Step::F), 1.);
+        assert_eq!(key.alter(Step::C), 0.);
+        match &key.key_type {
+            KeyType::NonTraditional(alterations) => {
+                assert_eq!(alterations.len(), 3);
+                assert_eq!(alterations[1].accidental.as_deref(), Some("slash-flat"));
+            }
+            _ => panic!("non-traditional key expected"),
+        }
+        assert_eq!(key.key_octave[1].octave, 5);
+        assert_eq!(key.key_octave[1].cancel, Some(YesNo::Yes));
+
+        let el = key.to_node();
+        assert_eq!(el.children.len(), 9);
+        assert_eq!(
+            el.children[4],
+            Element::new("key-accidental").with_text("slash-flat")
+        );
+    }
+
+    #[test]
+    fn key_without_fifths_or_steps_err() {
+        let doc = Document::parse("<key><mode>major</mode></key>").unwrap();
+        let node = doc.root_element();
+
+        assert!(matches!(
+            Key::from_node(&node),
+            Err(ExclusiveNodeGroupNotFound { .. })
+        ));
+    }
+
+    #[test]
+    fn attribute_time_ok() {
+        let xml = r#"
+            <attributes>
+                <divisions>4</divisions>
+                <time symbol="common">
+                    <beats>4</beats>
+                    <beat-type>4</beat-type>
+                </time>
+                <time number="2">
+                    <beats>3+2</beats>
+                    <beat-type>8</beat-type>
+                </time>
+                <staves>2</staves>
+            </attributes>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let attr = Attribute::from_node(&node);
+        assert!(attr.is_ok());
+        let time = attr.unwrap().time;
+        assert_eq!(time.len(), 2);
+        assert_eq!(time[0].symbol, Some(TimeSymbol::Common));
+        assert_eq!(time[0].signatures()[0].beat_count(), Some(4));
+        assert_eq!(time[1].number, Some(2));
+        assert_eq!(time[1].signatures()[0].beat_count(), Some(5));
+        assert_eq!(time[1].signatures()[0].beat_type, 8);
+        assert_eq!(time[1].measure_duration(4), Some(10));
+    }
+
+    #[test]
+    fn time_invalid_err() {
+        let xml = "<time><beats>4</beats><beat-type>0</beat-type></time>";
+        let doc = Document::parse(xml).unwrap();
+        let time = Time::from_node(&doc.root_element());
+        assert!(matches!(time, Err(NodeTextParseFailed { tag, .. }) if tag == "beat-type"));
+
+        let xml = "<time><beats>3</beats><beat-type>4</beat-type><beats>2</beats></time>";
+        let doc = Document::parse(xml).unwrap();
+        let time = Time::from_node(&doc.root_element());
+        assert!(matches!(time, Err(NodeNotFound { tag, .. }) if tag == "beat-type"));
+
+        let time = Time::new(u32::MAX, 4);
+        assert_eq!(time.measure_duration(2), None);
+    }
+
+    #[test]
+    fn time_composite_interchangeable_ok() {
+        let xml = r#"
+            <time>
+                <beats>3</beats>
+                <beat-type>4</beat-type>
+                <beats>6</beats>
+                <beat-type>8</beat-type>
+                <interchangeable>
+                    <time-relation>parentheses</time-relation>
+                    <beats>12</beats>
+                    <beat-type>8</beat-type>
+                </interchangeable>
+            </time>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let time = Time::from_node(&node);
+        assert!(time.is_ok());
+        let time = time.unwrap();
+        assert_eq!(time.signatures().len(), 2);
+        assert_eq!(time.measure_duration(2), Some(12));
+        match &time.time_type {
+            TimeType::Signatures {
+                interchangeable: Some(i),
+                ..
+            } => {
+                assert_eq!(i.time_relation.as_deref(), Some("parentheses"));
+                assert_eq!(i.signatures[0].beats, "12");
+            }
+            _ => panic!("interchangeable time expected"),
+        }
+        assert_eq!(time.to_node().children.len(), 5);
+    }
+
+    #[test]
+    fn time_senza_misura_ok() {
+        let xml = r#"
+            <time print-object="no">
+                <senza-misura>X</senza-misura>
+            </time>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let time = Time::from_node(&node).unwrap();
+        assert_eq!(time.time_type, TimeType::SenzaMisura(Some("X".to_owned())));
+        assert!(time.signatures().is_empty());
+        assert_eq!(time.measure_duration(4), None);
+    }
+
+    #[test]
+    fn attribute_tab_staff_ok() {
+        let xml = r#"
+            <attributes>
+                <divisions>1</divisions>
+                <staves>1</staves>
+                <clef>
+                    <sign>TAB</sign>
+                    <line>5</line>
+                </clef>
+                <staff-details>
+                    <staff-lines>6</staff-lines>
+                    <staff-tuning line="1">
+                        <tuning-step>E</tuning-step>
+                        <tuning-octave>2</tuning-octave>
+                    </staff-tuning>
+                    <staff-tuning line="2">
+                        <tuning-step>A</tuning-step>
+                        <tuning-octave>2</tuning-octave>
+                    </staff-tuning>
+                </staff-details>
+            </attributes>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let attr = Attribute::from_node(&node).unwrap();
+        assert_eq!(attr.clef[0].sign, ClefSign::Tab);
+        assert_eq!(attr.clef[0].staff_line(), Some(5));
+        assert_eq!(
+            attr.staff_details,
+            vec![StaffDetails {
+                number: None,
+                staff_type: None,
+                staff_lines: Some(6),
+                staff_tuning: vec![
+                    StaffTuning {
+                        line: 1,
+                        tuning_step: Step::E,
+                        tuning_alter: None,
+                        tuning_octave: 2,
+                        unknown: Unknown::default()
+                    },
+                    StaffTuning {
+                        line: 2,
+                        tuning_step: Step::A,
+                        tuning_alter: None,
+                        tuning_octave: 2,
+                        unknown: Unknown::default()
+                    }
+                ],
+                capo: None,
+                staff_size: None,
+                unknown: Unknown::default()
+            }]
+        );
+    }
+
+    #[test]
+    fn attribute_part_symbol_ok() {
+        let xml = r#"
+            <attributes>
+                <staves>3</staves>
+                <part-symbol top-staff="1" bottom-staff="2">brace</part-symbol>
+            </attributes>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let attr = Attribute::from_node(&node).unwrap();
+        assert_eq!(
+            attr.part_symbol,
+            Some(PartSymbol {
+                value: GroupSymbolValue::Brace,
+                top_staff: Some(1),
+                bottom_staff: Some(2),
+                unknown: Unknown::default()
+            })
+        );
+        assert_eq!(attr.to_node(), Element::from(&node));
+    }
+
+    #[test]
+    fn staff_details_ok() {
+        let xml = r#"
+            <staff-details number="2">
+                <staff-type>cue</staff-type>
+                <staff-lines>1</staff-lines>
+                <capo>3</capo>
+                <staff-size scaling="80">75</staff-size>
+            </staff-details>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let details = StaffDetails::from_node(&node);
+        assert!(details.is_ok());
+        let details = details.unwrap();
+        assert_eq!(details.number, Some(2));
+        assert_eq!(details.staff_type, Some(StaffType::Cue));
+        assert_eq!(details.line_count(), 1);
+        assert_eq!(details.capo, Some(3));
+        assert_eq!(
+            details.staff_size,
+            Some(StaffSize {
+                value: 75.,
+                scaling: Some(80.),
+                unknown: Unknown::default()
+            })
+        );
+    }
+
+    #[test]
+    fn measure_style_ok() {
+        let xml = r#"
+            <attributes>
+                <measure-style>
+                    <multiple-rest use-symbols="no">4</multiple-rest>
+                </measure-style>
+                <measure-style number="2">
+                    <measure-repeat type="start" slashes="2">2</measure-repeat>
+                </measure-style>
+                <measure-style>
+                    <slash type="stop" />
+                </measure-style>
+            </attributes>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let attr = Attribute::from_node(&node).unwrap();
+        assert_eq!(
+            attr.measure_style[0].style,
+            MeasureStyleType::MultipleRest {
+                count: 4,
+                use_symbols: Some(YesNo::No)
+            }
+        );
+        assert_eq!(
+            attr.measure_style[1],
+            MeasureStyle {
+                number: Some(2),
+                style: MeasureStyleType::MeasureRepeat {
+                    repeat_type: StartStop::Start,
+                    measures: Some(2),
+                    slashes: Some(2)
+                },
+                unknown: Unknown::default()
+            }
+        );
+        assert!(matches!(
+            attr.measure_style[2].style,
+            MeasureStyleType::Slash {
+                slash_type: StartStop::Stop,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn attributes_for_part_ok() {
+        let xml = r#"
+            <attributes>
+                <for-part number="1">
+                    <part-clef>
+                        <sign>G</sign>
+                        <line>2</line>
+                    </part-clef>
+                    <part-transpose>
+                        <diatonic>-1</diatonic>
+                        <chromatic>-2</chromatic>
+                    </part-transpose>
+                </for-part>
+            </attributes>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let attributes = Attribute::from_node(&node).unwrap();
+        assert_eq!(
+            attributes.for_part,
+            vec![ForPart {
+                number: Some(1),
+                part_clef: Some(PartClef {
+                    sign: ClefSign::G,
+                    line: Some(2),
+                    clef_octave_change: None,
+                    unknown: Unknown::default()
+                }),
+                part_transpose: Transpose {
+                    number: None,
+                    diatonic: Some(-1),
+                    chromatic: -2.,
+                    octave_change: None,
+                    double: false,
+                    unknown: Unknown::default()
+                },
+                unknown: Unknown::default()
+            }]
+        );
+        assert_eq!(attributes.to_node(), Element::from(&node));
+    }
+}
@@ -0,0 +1,270 @@
+use roxmltree::Node;
+
+use super::{
+    children_to_nodes, parse_attr, parse_children, parse_option_chd, parse_optional_attr,
+    parse_optional_chd_text, parse_optional_text, Fermata, FromNode, ToNode, Unknown, WavyLine,
+};
+use crate::{error::Result, writer::Element};
+
+xml_enum! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum BarStyle {
+        Regular => "regular",
+        Dotted => "dotted",
+        Dashed => "dashed",
+        Heavy => "heavy",
+        LightLight => "light-light",
+        LightHeavy => "light-heavy",
+        HeavyLight => "heavy-light",
+        HeavyHeavy => "heavy-heavy",
+        Tick => "tick",
+        Short => "short",
+        None => "none",
+    }
+}
+
+xml_enum! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum RightLeftMiddle {
+        Right => "right",
+        Left => "left",
+        Middle => "middle",
+    }
+}
+
+xml_enum! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum BackwardForward {
+        Backward => "backward",
+        Forward => "forward",
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Repeat {
+    pub direction: BackwardForward,
+    /// How many times a backward repeat is played
+    pub times: Option<u32>,
+    pub unknown: Unknown,
+}
+
+impl FromNode for Repeat {
+    fn tag() -> &'static str {
+        "repeat"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        Ok(Repeat {
+            direction: parse_attr(node, "direction")?,
+            times: parse_optional_attr(node, "times")?,
+            unknown: Unknown::parse(node, &["direction", "times"], &[]),
+        })
+    }
+}
+
+impl ToNode for Repeat {
+    fn to_node(&self) -> Element {
+        let node = Element::new(Self::tag())
+            .with_attr("direction", self.direction)
+            .with_optional_attr("times", self.times);
+        self.unknown.write(node)
+    }
+}
+
+xml_enum! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum EndingType {
+        Start => "start",
+        Stop => "stop",
+        Discontinue => "discontinue",
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ending {
+    /// Comma separated list of the passes this ending is played on, e.g. "1, 2"
+    pub number: String,
+    pub ending_type: EndingType,
+    /// Displayed text, defaults to the number if absent
+    pub text: Option<String>,
+    pub unknown: Unknown,
+}
+
+impl FromNode for Ending {
+    fn tag() -> &'static str {
+        "ending"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        Ok(Ending {
+            number: parse_attr(node, "number")?,
+            ending_type: parse_attr(node, "type")?,
+            text: parse_optional_text(node),
+            unknown: Unknown::parse_attrs(node, &["number", "type"]),
+        })
+    }
+}
+
+impl ToNode for Ending {
+    fn to_node(&self) -> Element {
+        let node = Element::new(Self::tag())
+            .with_attr("number", &self.number)
+            .with_attr("type", self.ending_type)
+            .with_optional_text(self.text.as_ref());
+        self.unknown.write(node)
+    }
+}
+
+impl Ending {
+    /// Passes this ending is played on, empty for an ending with a blank number
+    pub fn numbers(&self) -> Vec<u32> {
+        self.number
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter_map(|n| n.parse().ok())
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Barline {
+    pub location: RightLeftMiddle,
+    pub bar_style: Option<BarStyle>,
+    /// Trill or vibrato line continuing across the barline
+    pub wavy_line: Option<WavyLine>,
+    pub fermata: Vec<Fermata>,
+    pub ending: Option<Ending>,
+    pub repeat: Option<Repeat>,
+    pub unknown: Unknown,
+}
+
+impl FromNode for Barline {
+    fn tag() -> &'static str {
+        "barline"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        Ok(Barline {
+            location: parse_optional_attr(node, "location")?.unwrap_or(RightLeftMiddle::Right),
+            bar_style: parse_optional_chd_text(node, "bar-style")?,
+            wavy_line: parse_option_chd(node)?,
+            fermata: parse_children(node)?,
+            ending: parse_option_chd(node)?,
+            repeat: parse_option_chd(node)?,
+            unknown: Unknown::parse(
+                node,
+                &["location"],
+                &["bar-style", "wavy-line", "fermata", "ending", "repeat"],
+            ),
+        })
+    }
+}
+
+impl ToNode for Barline {
+    fn to_node(&self) -> Element {
+        let node = Element::new(Self::tag())
+            .with_attr("location", self.location)
+            .with_optional_chd_text("bar-style", self.bar_style)
+            .with_optional_child(self.wavy_line.as_ref().map(ToNode::to_node))
+            .with_children(children_to_nodes(&self.fermata))
+            .with_optional_child(self.ending.as_ref().map(ToNode::to_node))
+            .with_optional_child(self.repeat.as_ref().map(ToNode::to_node));
+        self.unknown.write(node)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use roxmltree::Document;
+
+    use super::*;
+    use crate::score::{FermataShape, NumberLevel, Position, StartStopContinue, UprightInverted};
+
+    #[test]
+    fn barline_repeat_ok() {
+        let xml = r#"
+            <barline>
+                <bar-style>light-heavy</bar-style>
+                <repeat direction="backward" times="3" />
+            </barline>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let barline = Barline::from_node(&node);
+        assert!(barline.is_ok());
+        assert_eq!(
+            barline.unwrap(),
+            Barline {
+                location: RightLeftMiddle::Right,
+                bar_style: Some(BarStyle::LightHeavy),
+                wavy_line: None,
+                fermata: vec![],
+                ending: None,
+                repeat: Some(Repeat {
+                    direction: BackwardForward::Backward,
+                    times: Some(3),
+                    unknown: Unknown::default()
+                }),
+                unknown: Unknown::default()
+            }
+        );
+    }
+
+    #[test]
+    fn barline_ending_ok() {
+        let xml = r#"
+            <barline location="left">
+                <ending number="1, 2" type="start">1.-2.</ending>
+            </barline>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let barline = Barline::from_node(&node).unwrap();
+        assert_eq!(barline.location, RightLeftMiddle::Left);
+        let ending = barline.ending.unwrap();
+        assert_eq!(ending.ending_type, EndingType::Start);
+        assert_eq!(ending.text.as_deref(), Some("1.-2."));
+        assert_eq!(ending.numbers(), vec![1, 2]);
+    }
+
+    #[test]
+    fn barline_fermata_ok() {
+        let xml = r#"
+            <barline location="right">
+                <bar-style>light-heavy</bar-style>
+                <fermata type="upright">square</fermata>
+            </barline>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let barline = Barline::from_node(&node).unwrap();
+        assert_eq!(
+            barline.fermata,
+            vec![Fermata {
+                fermata_type: Some(UprightInverted::Upright),
+                shape: FermataShape::Square,
+                position: Position::default(),
+                unknown: Unknown::default()
+            }]
+        );
+    }
+
+    #[test]
+    fn barline_wavy_line_ok() {
+        let xml = r#"
+            <barline location="right">
+                <bar-style>regular</bar-style>
+                <wavy-line type="continue" number="1" />
+            </barline>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let barline = Barline::from_node(&node).unwrap();
+        assert_eq!(
+            barline.wavy_line,
+            Some(WavyLine {
+                wavy_line_type: StartStopContinue::Continue,
+                number: Some(NumberLevel(1)),
+                placement: None,
+                unknown: Unknown::default()
+            })
+        );
+        assert_eq!(barline.to_node(), Element::from(&node));
+    }
+}
@@ -0,0 +1,789 @@
+use std::{
+    cmp::Ordering,
+    collections::HashMap,
+    fmt,
+    iter::Sum,
+    num::{NonZeroU32, NonZeroU64, ParseFloatError},
+    ops::{Add, Sub},
+    str::FromStr,
+};
+
+use roxmltree::Node;
+
+use super::{
+    parse_option_chd, parse_optional_attr, parse_optional_chd_text, parse_optional_text,
+    parse_text, FromNode, ToNode,
+};
+use crate::{
+    error::Result,
+    writer::{attr_name, Element},
+};
+
+xml_enum! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum AboveBelow {
+        Above => "above",
+        Below => "below",
+    }
+}
+
+xml_enum! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum OverUnder {
+        Over => "over",
+        Under => "under",
+    }
+}
+
+xml_enum! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum StartStop {
+        Start => "start",
+        Stop => "stop",
+    }
+}
+
+xml_enum! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum StartStopSingle {
+        Start => "start",
+        Stop => "stop",
+        Single => "single",
+    }
+}
+
+xml_enum! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum StartStopContinue {
+        Start => "start",
+        Stop => "stop",
+        Continue => "continue",
+    }
+}
+
+xml_enum! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum Step {
+        C => "C",
+        D => "D",
+        E => "E",
+        F => "F",
+        G => "G",
+        A => "A",
+        B => "B",
+    }
+}
+
+impl Step {
+    /// Jianpu number of the step, 1 for C up to 7 for B
+    pub fn to_jianpu(self) -> u8 {
+        self as u8 + 1
+    }
+}
+
+xml_enum! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum YesNo {
+        Yes => "yes",
+        No => "no",
+    }
+}
+
+xml_enum! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum UpDown {
+        Up => "up",
+        Down => "down",
+    }
+}
+
+xml_enum! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum UpDownStopContinue {
+        Up => "up",
+        Down => "down",
+        Stop => "stop",
+        Continue => "continue",
+    }
+}
+
+xml_enum! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum TopBottom {
+        Top => "top",
+        Bottom => "bottom",
+    }
+}
+
+xml_enum! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum LineType {
+        Solid => "solid",
+        Dashed => "dashed",
+        Dotted => "dotted",
+        Wavy => "wavy",
+    }
+}
+
+xml_enum! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum SymbolSize {
+        Full => "full",
+        Cue => "cue",
+        GraceCue => "grace-cue",
+        Large => "large",
+    }
+}
+
+xml_enum! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum EnclosureShape {
+        Rectangle => "rectangle",
+        Square => "square",
+        Oval => "oval",
+        Circle => "circle",
+        Bracket => "bracket",
+        InvertedBracket => "inverted-bracket",
+        Triangle => "triangle",
+        Diamond => "diamond",
+        Pentagon => "pentagon",
+        Hexagon => "hexagon",
+        Heptagon => "heptagon",
+        Octagon => "octagon",
+        Nonagon => "nonagon",
+        Decagon => "decagon",
+        None => "none",
+    }
+}
+
+xml_enum! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum FontStyle {
+        Normal => "normal",
+        Italic => "italic",
+    }
+}
+
+xml_enum! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum FontWeight {
+        Normal => "normal",
+        Bold => "bold",
+    }
+}
+
+/// Color written as "#RRGGBB" or "#AARRGGBB" in hexadecimal
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Color {
+    /// Opacity, opaque if absent
+    pub alpha: Option<u8>,
+    pub red: u8,
+    pub green: u8,
+    pub blue: u8,
+}
+
+impl FromStr for Color {
+    type Err = ();
+
+    fn from_str(s: &str) -> core::result::Result<Self, Self::Err> {
+        let hex = s.strip_prefix('#').ok_or(())?;
+        if !hex.is_ascii() {
+            return Err(());
+        }
+        let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).map_err(|_| ());
+
+        match hex.len() {
+            6 => Ok(Color {
+                alpha: None,
+                red: channel(0)?,
+                green: channel(2)?,
+                blue: channel(4)?,
+            }),
+            8 => Ok(Color {
+                alpha: Some(channel(0)?),
+                red: channel(2)?,
+                green: channel(4)?,
+                blue: channel(6)?,
+            }),
+            _ => Err(()),
+        }
+    }
+}
+
+impl fmt::Display for Color {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("#")?;
+        if let Some(alpha) = self.alpha {
+            write!(f, "{alpha:02X}")?;
+        }
+        write!(f, "{:02X}{:02X}{:02X}", self.red, self.green, self.blue)
+    }
+}
+
+/// Offset in divisions from the current musical position
+#[derive(Debug, Clone, PartialEq)]
+pub struct Offset {
+    pub value: f64,
+    /// Whether playback happens at the offset too, otherwise only the display is moved
+    pub sound: Option<YesNo>,
+    pub unknown: Unknown,
+}
+
+impl FromNode for Offset {
+    fn tag() -> &'static str {
+        "offset"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        Ok(Offset {
+            value: parse_text(node, Self::tag())?,
+            sound: parse_optional_attr(node, "sound")?,
+            unknown: Unknown::parse_attrs(node, &["sound"]),
+        })
+    }
+}
+
+impl ToNode for Offset {
+    fn to_node(&self) -> Element {
+        let node = Element::new(Self::tag())
+            .with_optional_attr("sound", self.sound)
+            .with_text(self.value);
+        self.unknown.write(node)
+    }
+}
+
+/// Editorial level of an element, e.g. a suggested accidental
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Level {
+    /// Source of the element, e.g. a manuscript
+    pub text: Option<String>,
+    /// Taken from the level's source rather than added by the editor
+    pub reference: Option<YesNo>,
+    /// Applies to a span of elements if start or stop
+    pub level_type: Option<StartStopSingle>,
+    pub parentheses: Option<YesNo>,
+    pub bracket: Option<YesNo>,
+    pub unknown: Unknown,
+}
+
+impl FromNode for Level {
+    fn tag() -> &'static str {
+        "level"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        Ok(Level {
+            text: parse_optional_text(node),
+            reference: parse_optional_attr(node, "reference")?,
+            level_type: parse_optional_attr(node, "type")?,
+            parentheses: parse_optional_attr(node, "parentheses")?,
+            bracket: parse_optional_attr(node, "bracket")?,
+            unknown: Unknown::parse_attrs(node, &["reference", "type", "parentheses", "bracket"]),
+        })
+    }
+}
+
+impl ToNode for Level {
+    fn to_node(&self) -> Element {
+        let node = Element::new(Self::tag())
+            .with_optional_attr("reference", self.reference)
+            .with_optional_attr("type", self.level_type)
+            .with_optional_attr("parentheses", self.parentheses)
+            .with_optional_attr("bracket", self.bracket)
+            .with_optional_text(self.text.as_ref());
+        self.unknown.write(node)
+    }
+}
+
+/// Footnote and level of critical editions
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Editorial {
+    pub footnote: Option<String>,
+    pub level: Option<Level>,
+}
+
+impl Editorial {
+    pub(super) fn parse(node: &Node) -> Result<Self> {
+        Ok(Editorial {
+            footnote: parse_optional_chd_text(node, "footnote")?,
+            level: parse_option_chd(node)?,
+        })
+    }
+
+    pub(super) fn to_nodes(&self) -> impl Iterator<Item = Element> {
+        [
+            self.footnote
+                .as_ref()
+                .map(|f| Element::new("footnote").with_text(f)),
+            self.level.as_ref().map(ToNode::to_node),
+        ]
+        .into_iter()
+        .flatten()
+    }
+}
+
+/// Length in tenths of the interline space of a staff, the unit of layout values
+#[derive(Debug, Default, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Tenths(pub f64);
+
+impl FromStr for Tenths {
+    type Err = ParseFloatError;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        s.trim().parse().map(Tenths)
+    }
+}
+
+impl fmt::Display for Tenths {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// Distinguishes overlapping elements of the same kind, e.g. slurs, from 1 to 16
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct NumberLevel(pub u8);
+
+impl Default for NumberLevel {
+    fn default() -> Self {
+        NumberLevel(1)
+    }
+}
+
+impl FromStr for NumberLevel {
+    type Err = ();
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.trim().parse() {
+            Ok(n @ 1..=16) => Ok(NumberLevel(n)),
+            _ => Err(()),
+        }
+    }
+}
+
+impl fmt::Display for NumberLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// Manual placement of an element in tenths, engraving tools keep these
+/// to preserve adjustments made by hand
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Position {
+    /// Offset from the default origin of the element, ignoring other elements
+    pub default_x: Option<Tenths>,
+    pub default_y: Option<Tenths>,
+    /// Offset from the position computed by the application
+    pub relative_x: Option<Tenths>,
+    pub relative_y: Option<Tenths>,
+}
+
+impl Position {
+    pub(super) fn parse(node: &Node) -> Result<Self> {
+        Ok(Position {
+            default_x: parse_optional_attr(node, "default-x")?,
+            default_y: parse_optional_attr(node, "default-y")?,
+            relative_x: parse_optional_attr(node, "relative-x")?,
+            relative_y: parse_optional_attr(node, "relative-y")?,
+        })
+    }
+
+    pub(super) fn write_attrs(&self, el: Element) -> Element {
+        el.with_optional_attr("default-x", self.default_x)
+            .with_optional_attr("default-y", self.default_y)
+            .with_optional_attr("relative-x", self.relative_x)
+            .with_optional_attr("relative-y", self.relative_y)
+    }
+
+    /// Whether no position was given, leaving placement to the application
+    pub fn is_default(&self) -> bool {
+        *self == Position::default()
+    }
+}
+
+/// Whether an element and its parts are printed, e.g. hidden rests used for spacing
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Printout {
+    pub print_object: Option<YesNo>,
+    /// Whether the space of the element is kept when it is not printed
+    pub print_spacing: Option<YesNo>,
+    pub print_dot: Option<YesNo>,
+    pub print_lyric: Option<YesNo>,
+}
+
+impl Printout {
+    pub(super) fn parse(node: &Node) -> Result<Self> {
+        Ok(Printout {
+            print_object: parse_optional_attr(node, "print-object")?,
+            print_spacing: parse_optional_attr(node, "print-spacing")?,
+            print_dot: parse_optional_attr(node, "print-dot")?,
+            print_lyric: parse_optional_attr(node, "print-lyric")?,
+        })
+    }
+
+    pub(super) fn write_attrs(&self, el: Element) -> Element {
+        el.with_optional_attr("print-object", self.print_object)
+            .with_optional_attr("print-spacing", self.print_spacing)
+            .with_optional_attr("print-dot", self.print_dot)
+            .with_optional_attr("print-lyric", self.print_lyric)
+    }
+
+    /// Whether the element is printed, which it is unless print-object is no
+    pub fn is_printed(&self) -> bool {
+        self.print_object != Some(YesNo::No)
+    }
+
+    /// Whether the element takes up space, by default only if it is printed
+    pub fn is_spaced(&self) -> bool {
+        match self.print_spacing {
+            Some(s) => s == YesNo::Yes,
+            None => self.is_printed(),
+        }
+    }
+}
+
+/// Control points of a bezier curve in tenths, e.g. of a slur or a tie
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Bezier {
+    pub x: Option<Tenths>,
+    pub y: Option<Tenths>,
+    pub x2: Option<Tenths>,
+    pub y2: Option<Tenths>,
+    /// Offset in divisions, for curves spanning notes
+    pub offset: Option<f64>,
+    pub offset2: Option<f64>,
+}
+
+impl Bezier {
+    pub(super) fn parse(node: &Node) -> Result<Self> {
+        Ok(Bezier {
+            x: parse_optional_attr(node, "bezier-x")?,
+            y: parse_optional_attr(node, "bezier-y")?,
+            x2: parse_optional_attr(node, "bezier-x2")?,
+            y2: parse_optional_attr(node, "bezier-y2")?,
+            offset: parse_optional_attr(node, "bezier-offset")?,
+            offset2: parse_optional_attr(node, "bezier-offset2")?,
+        })
+    }
+
+    pub(super) fn write_attrs(&self, el: Element) -> Element {
+        el.with_optional_attr("bezier-x", self.x)
+            .with_optional_attr("bezier-y", self.y)
+            .with_optional_attr("bezier-x2", self.x2)
+            .with_optional_attr("bezier-y2", self.y2)
+            .with_optional_attr("bezier-offset", self.offset)
+            .with_optional_attr("bezier-offset2", self.offset2)
+    }
+}
+
+/// Font attributes of a text-bearing element
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Font {
+    /// Comma-separated list of font names
+    pub family: Option<String>,
+    pub style: Option<FontStyle>,
+    /// Size in points or a CSS size like "large"
+    pub size: Option<String>,
+    pub weight: Option<FontWeight>,
+}
+
+impl Font {
+    pub(super) fn parse(node: &Node) -> Result<Self> {
+        Ok(Font {
+            family: parse_optional_attr(node, "font-family")?,
+            style: parse_optional_attr(node, "font-style")?,
+            size: parse_optional_attr(node, "font-size")?,
+            weight: parse_optional_attr(node, "font-weight")?,
+        })
+    }
+
+    pub(super) fn write_attrs(&self, el: Element) -> Element {
+        el.with_optional_attr("font-family", self.family.as_ref())
+            .with_optional_attr("font-style", self.style)
+            .with_optional_attr("font-size", self.size.as_ref())
+            .with_optional_attr("font-weight", self.weight)
+    }
+}
+
+/// Position, font and color of a printed element
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct PrintStyle {
+    pub position: Position,
+    pub font: Font,
+    pub color: Option<Color>,
+}
+
+impl PrintStyle {
+    pub(super) fn parse(node: &Node) -> Result<Self> {
+        Ok(PrintStyle {
+            position: Position::parse(node)?,
+            font: Font::parse(node)?,
+            color: parse_optional_attr(node, "color")?,
+        })
+    }
+
+    pub(super) fn write_attrs(&self, el: Element) -> Element {
+        let el = self.position.write_attrs(el);
+        self.font
+            .write_attrs(el)
+            .with_optional_attr("color", self.color)
+    }
+}
+
+/// Musical length as a fraction of a whole note, independent of the divisions of a part
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Duration {
+    num: u64,
+    den: u64,
+}
+
+fn gcd(a: u128, b: u128) -> u128 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+impl Duration {
+    pub const ZERO: Duration = Duration { num: 0, den: 1 };
+
+    /// Duration of num/den whole notes
+    pub fn new(num: u64, den: NonZeroU64) -> Self {
+        Duration::reduce(num as u128, den.get() as u128)
+    }
+
+    /// Reduce the fraction, den must not be zero. A denominator too large for u64
+    /// is approximated, a numerator too large saturates
+    fn reduce(num: u128, den: u128) -> Self {
+        let d = gcd(num, den);
+        let (mut num, mut den) = (num / d, den / d);
+        while den > u64::MAX as u128 {
+            num >>= 1;
+            den >>= 1;
+        }
+        Duration {
+            num: u64::try_from(num).unwrap_or(u64::MAX),
+            den: den as u64,
+        }
+    }
+
+    /// Duration of a length in divisions per quarter note
+    pub fn from_divisions(duration: u32, divisions: NonZeroU32) -> Self {
+        Duration::reduce(duration as u128, 4 * divisions.get() as u128)
+    }
+
+    pub fn numer(&self) -> u64 {
+        self.num
+    }
+
+    pub fn denom(&self) -> u64 {
+        self.den
+    }
+
+    /// Length in ticks at the given pulses per quarter note, rounded down and saturating
+    pub fn to_ticks(&self, ppq: u32) -> u64 {
+        u64::try_from(self.num as u128 * 4 * ppq as u128 / self.den as u128).unwrap_or(u64::MAX)
+    }
+
+    pub fn quarters(&self) -> f64 {
+        4. * self.num as f64 / self.den as f64
+    }
+}
+
+impl Add for Duration {
+    type Output = Duration;
+
+    fn add(self, rhs: Duration) -> Duration {
+        let (a, b) = (self.den as u128, rhs.den as u128);
+        let den = a / gcd(a, b) * b;
+        Duration::reduce(
+            self.num as u128 * (den / a) + rhs.num as u128 * (den / b),
+            den,
+        )
+    }
+}
+
+impl Sub for Duration {
+    type Output = Duration;
+
+    /// Difference of the durations, zero if rhs is longer
+    fn sub(self, rhs: Duration) -> Duration {
+        let (a, b) = (self.den as u128, rhs.den as u128);
+        let den = a / gcd(a, b) * b;
+        Duration::reduce(
+            (self.num as u128 * (den / a)).saturating_sub(rhs.num as u128 * (den / b)),
+            den,
+        )
+    }
+}
+
+impl Sum for Duration {
+    fn sum<I: Iterator<Item = Duration>>(iter: I) -> Duration {
+        iter.fold(Duration::ZERO, Add::add)
+    }
+}
+
+impl Ord for Duration {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.num as u128 * other.den as u128).cmp(&(other.num as u128 * self.den as u128))
+    }
+}
+
+impl PartialOrd for Duration {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for Duration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.num, self.den)
+    }
+}
+
+/// Attributes and child elements not modeled yet, kept as raw xml
+/// so that loading and saving a file does not lose them
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Unknown {
+    pub attrs: Vec<(String, String)>,
+    /// Child elements with the element they followed, if any, given by its name
+    /// and how many elements of that name came before it
+    pub children: Vec<(Option<(String, usize)>, Element)>,
+}
+
+impl Unknown {
+    /// Collect everything of the node except the given attributes and child elements
+    pub(super) fn parse(node: &Node, attrs: &[&str], children: &[&str]) -> Self {
+        let mut unknown = Unknown::parse_attrs(node, attrs);
+        let mut counts: HashMap<&str, usize> = HashMap::new();
+        let mut previous = None;
+        for c in node.children().filter(Node::is_element) {
+            let name = c.tag_name().name();
+            if !children.contains(&name) {
+                unknown.children.push((previous, Element::from(&c)));
+            }
+            let count = counts.entry(name).or_default();
+            previous = Some((name.to_owned(), *count));
+            *count += 1;
+        }
+
+        unknown
+    }
+
+    /// Collect the attributes of the node except the given ones
+    pub(super) fn parse_attrs(node: &Node, attrs: &[&str]) -> Self {
+        Unknown {
+            attrs: node
+                .attributes()
+                .map(|a| (attr_name(&a), a.value().to_owned()))
+                .filter(|(name, _)| !attrs.contains(&name.as_str()))
+                .collect(),
+            children: Vec::new(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.attrs.is_empty() && self.children.is_empty()
+    }
+
+    /// Add the kept attributes, and the kept children after the written
+    /// element they followed, or first if they had no preceding sibling
+    pub(super) fn write(&self, mut el: Element) -> Element {
+        el.attrs.extend(self.attrs.iter().cloned());
+
+        for (previous, c) in &self.children {
+            let i = match previous {
+                Some((name, n)) => el
+                    .children
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, e)| &e.name == name)
+                    .nth(*n)
+                    .map_or(el.children.len(), |(i, _)| i + 1),
+                None => 0,
+            };
+            el.children.insert(i, c.clone());
+        }
+
+        el
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn color_ok() {
+        let color: Color = "#80FF0000".parse().unwrap();
+        assert_eq!(
+            color,
+            Color {
+                alpha: Some(0x80),
+                red: 0xFF,
+                green: 0,
+                blue: 0
+            }
+        );
+        assert_eq!(color.to_string(), "#80FF0000");
+        assert_eq!("#00a0ff".parse::<Color>().unwrap().to_string(), "#00A0FF");
+        assert!("red".parse::<Color>().is_err());
+        assert!("#12345".parse::<Color>().is_err());
+    }
+
+    #[test]
+    fn duration_ok() {
+        let divisions = NonZeroU32::new(768).unwrap();
+        let dotted_quarter = Duration::from_divisions(1152, divisions);
+        assert_eq!(
+            dotted_quarter,
+            Duration::new(3, NonZeroU64::new(8).unwrap())
+        );
+        assert_eq!(dotted_quarter.quarters(), 1.5);
+        assert_eq!(dotted_quarter.to_ticks(480), 720);
+
+        let triplet = Duration::from_divisions(1, NonZeroU32::new(3).unwrap());
+        let sum: Duration = [triplet; 3].into_iter().sum();
+        assert_eq!(sum, Duration::new(1, NonZeroU64::new(4).unwrap()));
+        assert_eq!(sum.to_string(), "1/4");
+        assert!(triplet < sum);
+        assert_eq!(triplet - sum, Duration::ZERO);
+        assert_eq!(
+            dotted_quarter - sum,
+            Duration::new(1, NonZeroU64::new(8).unwrap())
+        );
+    }
+
+    #[test]
+    fn duration_overflow_ok() {
+        let long = Duration::new(u64::MAX, NonZeroU64::new(1).unwrap());
+        assert_eq!(long.to_ticks(480), u64::MAX);
+        assert_eq!(long + long, long);
+        assert_eq!(long - long, Duration::ZERO);
+
+        let a = Duration::new(1, NonZeroU64::new(u64::MAX).unwrap());
+        let b = Duration::new(1, NonZeroU64::new(u64::MAX - 1).unwrap());
+        let sum = a + b;
+        assert!(sum > b && sum.denom() > 0);
+        assert_eq!(
+            Duration::new(u64::MAX, NonZeroU64::new(3).unwrap()).to_ticks(u32::MAX),
+            u64::MAX
+        );
+    }
+
+    #[test]
+    fn simple_types_ok() {
+        assert_eq!("16".parse(), Ok(NumberLevel(16)));
+        assert_eq!("0".parse::<NumberLevel>(), Err(()));
+        assert_eq!("17".parse::<NumberLevel>(), Err(()));
+        assert_eq!(NumberLevel::default().to_string(), "1");
+
+        assert_eq!("-12.5".parse(), Ok(Tenths(-12.5)));
+        assert!("wide".parse::<Tenths>().is_err());
+        assert_eq!(Tenths(40.).to_string(), "40");
+
+        assert_eq!("G".parse(), Ok(Step::G));
+        assert_eq!(Step::G.to_jianpu(), 5);
+        assert!("H".parse::<Step>().is_err());
+    }
+}
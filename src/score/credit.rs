@@ -0,0 +1,181 @@
+use roxmltree::Node;
+
+use super::{
+    children_to_nodes, parse_children, parse_optional_attr, parse_optional_text, Font, FromNode,
+    Position, ToNode, Unknown,
+};
+use crate::{error::Result, writer::Element};
+
+xml_enum! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum LeftCenterRight {
+        Left => "left",
+        Center => "center",
+        Right => "right",
+    }
+}
+
+xml_enum! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum Valign {
+        Top => "top",
+        Middle => "middle",
+        Bottom => "bottom",
+        Baseline => "baseline",
+    }
+}
+
+/// Text shown on a page, positioned in tenths from the bottom left of the page
+#[derive(Debug, Clone, PartialEq)]
+pub struct CreditWords {
+    pub text: String,
+    pub position: Position,
+    pub justify: Option<LeftCenterRight>,
+    pub halign: Option<LeftCenterRight>,
+    pub valign: Option<Valign>,
+    pub font: Font,
+    pub unknown: Unknown,
+}
+
+impl FromNode for CreditWords {
+    fn tag() -> &'static str {
+        "credit-words"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        Ok(CreditWords {
+            text: node.text().unwrap_or_default().to_owned(),
+            position: Position::parse(node)?,
+            justify: parse_optional_attr(node, "justify")?,
+            halign: parse_optional_attr(node, "halign")?,
+            valign: parse_optional_attr(node, "valign")?,
+            font: Font::parse(node)?,
+            unknown: Unknown::parse_attrs(
+                node,
+                &[
+                    "default-x",
+                    "default-y",
+                    "relative-x",
+                    "relative-y",
+                    "justify",
+                    "halign",
+                    "valign",
+                    "font-family",
+                    "font-style",
+                    "font-size",
+                    "font-weight",
+                ],
+            ),
+        })
+    }
+}
+
+impl ToNode for CreditWords {
+    fn to_node(&self) -> Element {
+        let el = self
+            .position
+            .write_attrs(Element::new(Self::tag()))
+            .with_optional_attr("justify", self.justify)
+            .with_optional_attr("halign", self.halign)
+            .with_optional_attr("valign", self.valign);
+
+        let node = self.font.write_attrs(el).with_text(&self.text);
+        self.unknown.write(node)
+    }
+}
+
+/// Text shown on a page of the score, such as the title or composer
+#[derive(Debug, Clone, PartialEq)]
+pub struct Credit {
+    /// Page number the credit appears on, 1 if absent
+    pub page: Option<u32>,
+    /// Kinds of the credit, e.g. "title", "subtitle", "composer"
+    pub credit_type: Vec<String>,
+    pub credit_words: Vec<CreditWords>,
+    pub unknown: Unknown,
+}
+
+impl FromNode for Credit {
+    fn tag() -> &'static str {
+        "credit"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        Ok(Credit {
+            page: parse_optional_attr(node, "page")?,
+            credit_type: node
+                .children()
+                .filter(|c| c.tag_name().name() == "credit-type")
+                .filter_map(|c| parse_optional_text(&c))
+                .collect(),
+            credit_words: parse_children(node)?,
+            unknown: Unknown::parse(node, &["page"], &["credit-type", "credit-words"]),
+        })
+    }
+}
+
+impl ToNode for Credit {
+    fn to_node(&self) -> Element {
+        let node = Element::new(Self::tag())
+            .with_optional_attr("page", self.page)
+            .with_children(
+                self.credit_type
+                    .iter()
+                    .map(|t| Element::new("credit-type").with_text(t)),
+            )
+            .with_children(children_to_nodes(&self.credit_words));
+        self.unknown.write(node)
+    }
+}
+
+impl Credit {
+    /// Text of all credit words joined together
+    pub fn text(&self) -> String {
+        self.credit_words.iter().map(|w| w.text.as_str()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use roxmltree::Document;
+
+    use super::*;
+    use crate::score::Tenths;
+
+    #[test]
+    fn credit_ok() {
+        let xml = r#"
+            <credit page="1">
+                <credit-type>title</credit-type>
+                <credit-words default-x="612" default-y="1527" justify="center" valign="top" font-size="24" font-weight="bold">Greensleeves</credit-words>
+            </credit>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let credit = Credit::from_node(&node);
+        assert!(credit.is_ok());
+        let credit = credit.unwrap();
+        assert_eq!(credit.page, Some(1));
+        assert_eq!(credit.credit_type, vec!["title".to_owned()]);
+        assert_eq!(credit.text(), "Greensleeves");
+
+        let words = &credit.credit_words[0];
+        assert_eq!(words.position.default_x, Some(Tenths(612.)));
+        assert_eq!(words.justify, Some(LeftCenterRight::Center));
+        assert_eq!(words.valign, Some(Valign::Top));
+        assert_eq!(words.font.size.as_deref(), Some("24"));
+    }
+
+    #[test]
+    fn credit_multiple_words_ok() {
+        let xml = r#"
+            <credit>
+                <credit-words>Music by </credit-words>
+                <credit-words font-style="italic">Anonymous</credit-words>
+            </credit>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let credit = Credit::from_node(&node).unwrap();
+        assert!(credit.credit_type.is_empty());
+        assert_eq!(credit.text(), "Music by Anonymous");
+    }
+}
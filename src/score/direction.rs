@@ -0,0 +1,2553 @@
+use roxmltree::Node;
+
+use super::{
+    children_to_nodes, count_chd, parse_attr, parse_chd_text, parse_children, parse_option_chd,
+    parse_optional_attr, parse_optional_chd_text, parse_optional_text, parse_text, AboveBelow,
+    Beam, Color, Editorial, EnclosureShape, Font, FromNode, InstrumentChange, LineType, Listening,
+    MidiInstrument, NoteTypeValue, NumberLevel, Offset, Pitch, Position, PrintStyle, StartStop,
+    StartStopContinue, Step, Tenths, ToNode, Unknown, UpDownStopContinue, YesNo,
+};
+use crate::{
+    error::{Error::NodeNotFound, Result},
+    writer::Element,
+};
+
+/// Beat unit of a metronome mark, e.g. a dotted quarter
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BeatUnit {
+    pub value: NoteTypeValue,
+    pub dot: u8,
+    /// Beat units tied to this one, e.g. a half tied to an eighth
+    pub tied: Vec<BeatUnit>,
+}
+
+impl BeatUnit {
+    /// Length of the beat unit measured in quarter notes
+    pub fn quarters(&self) -> f64 {
+        let dotted = (0..self.dot).fold((1., 1.), |(len, dot), _| (len + dot / 2., dot / 2.));
+        self.value.quarters() * dotted.0 + self.tied.iter().map(BeatUnit::quarters).sum::<f64>()
+    }
+
+    /// Parse the beat-unit groups of consecutive children
+    fn parse_group(node: &Node) -> Result<Vec<Self>> {
+        let mut units: Vec<BeatUnit> = Vec::new();
+        for c in node.children() {
+            match (c.tag_name().name(), units.last_mut()) {
+                ("beat-unit", _) => units.push(BeatUnit {
+                    value: parse_text(&c, "beat-unit")?,
+                    dot: 0,
+                    tied: Vec::new(),
+                }),
+                ("beat-unit-dot", Some(u)) => u.dot += 1,
+                ("beat-unit-tied", Some(u)) => u.tied.extend(BeatUnit::parse_group(&c)?),
+                _ => (),
+            }
+        }
+        Ok(units)
+    }
+
+    fn to_nodes(&self) -> Vec<Element> {
+        let mut nodes = vec![Element::new("beat-unit").with_text(self.value)];
+        nodes.extend((0..self.dot).map(|_| Element::new("beat-unit-dot")));
+        nodes.extend(
+            self.tied
+                .iter()
+                .map(|t| Element::new("beat-unit-tied").with_children(t.to_nodes())),
+        );
+        nodes
+    }
+}
+
+/// Note drawn in a metronome mark relating notes to each other
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MetronomeNote {
+    pub metronome_type: NoteTypeValue,
+    pub dot: u8,
+    pub beams: Vec<Beam>,
+    pub tied: Option<StartStop>,
+    pub unknown: Unknown,
+}
+
+impl FromNode for MetronomeNote {
+    fn tag() -> &'static str {
+        "metronome-note"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        Ok(MetronomeNote {
+            metronome_type: parse_chd_text(node, "metronome-type")?,
+            dot: count_chd(node, "metronome-dot") as u8,
+            beams: node
+                .children()
+                .filter(|c| c.tag_name().name() == "metronome-beam")
+                .map(|c| Beam::from_node(&c))
+                .collect::<Result<_>>()?,
+            tied: match node
+                .children()
+                .find(|c| c.tag_name().name() == "metronome-tied")
+            {
+                Some(c) => Some(parse_attr(&c, "type")?),
+                None => None,
+            },
+            unknown: Unknown::parse(
+                node,
+                &[],
+                &[
+                    "metronome-type",
+                    "metronome-dot",
+                    "metronome-beam",
+                    "metronome-tied",
+                ],
+            ),
+        })
+    }
+}
+
+impl ToNode for MetronomeNote {
+    fn to_node(&self) -> Element {
+        let node = Element::new(Self::tag())
+            .with_chd_text("metronome-type", self.metronome_type)
+            .with_children((0..self.dot).map(|_| Element::new("metronome-dot")))
+            .with_children(self.beams.iter().map(|b| Element {
+                name: "metronome-beam".to_owned(),
+                ..b.to_node()
+            }))
+            .with_optional_child(
+                self.tied
+                    .map(|t| Element::new("metronome-tied").with_attr("type", t)),
+            );
+        self.unknown.write(node)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MetronomeKind {
+    /// Beat units per minute, e.g. "♩ = 120"
+    PerMinute {
+        beat_unit: BeatUnit,
+        /// Usually a number, but text such as "c. 108" is allowed
+        per_minute: String,
+    },
+    /// Metric modulation from one beat unit to another, e.g. "♩ = ♩."
+    BeatUnits(BeatUnit, BeatUnit),
+    /// Relation between notes drawn with beams and ties, e.g. swung eighths
+    Notes {
+        notes: Vec<MetronomeNote>,
+        /// Usually "equals"
+        relation: Option<String>,
+        related: Vec<MetronomeNote>,
+    },
+}
+
+impl MetronomeKind {
+    fn parse(node: &Node) -> Result<Self> {
+        let mut units = BeatUnit::parse_group(node)?.into_iter();
+        if let Some(beat_unit) = units.next() {
+            return Ok(match units.next() {
+                Some(other) => MetronomeKind::BeatUnits(beat_unit, other),
+                None => MetronomeKind::PerMinute {
+                    beat_unit,
+                    per_minute: parse_chd_text(node, "per-minute")?,
+                },
+            });
+        }
+
+        let (mut notes, mut related) = (Vec::new(), Vec::new());
+        let mut relation = None;
+        for c in node.children() {
+            match c.tag_name().name() {
+                "metronome-note" if relation.is_none() => notes.push(MetronomeNote::from_node(&c)?),
+                "metronome-note" => related.push(MetronomeNote::from_node(&c)?),
+                "metronome-relation" => relation = parse_optional_text(&c),
+                _ => (),
+            }
+        }
+        if notes.is_empty() {
+            return Err(NodeNotFound {
+                tag: "beat-unit",
+                parent_tag: Metronome::tag().to_owned(),
+            });
+        }
+
+        Ok(MetronomeKind::Notes {
+            notes,
+            relation,
+            related,
+        })
+    }
+
+    fn write(&self, node: Element) -> Element {
+        match self {
+            MetronomeKind::PerMinute {
+                beat_unit,
+                per_minute,
+            } => node
+                .with_children(beat_unit.to_nodes())
+                .with_chd_text("per-minute", per_minute),
+            MetronomeKind::BeatUnits(unit, other) => node
+                .with_children(unit.to_nodes())
+                .with_children(other.to_nodes()),
+            MetronomeKind::Notes {
+                notes,
+                relation,
+                related,
+            } => node
+                .with_children(children_to_nodes(notes))
+                .with_optional_chd_text("metronome-relation", relation.as_ref())
+                .with_children(children_to_nodes(related)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Metronome {
+    pub kind: MetronomeKind,
+    pub unknown: Unknown,
+}
+
+impl FromNode for Metronome {
+    fn tag() -> &'static str {
+        "metronome"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        Ok(Metronome {
+            kind: MetronomeKind::parse(node)?,
+            unknown: Unknown::parse(
+                node,
+                &[],
+                &[
+                    "beat-unit",
+                    "beat-unit-dot",
+                    "beat-unit-tied",
+                    "per-minute",
+                    "metronome-note",
+                    "metronome-relation",
+                ],
+            ),
+        })
+    }
+}
+
+impl ToNode for Metronome {
+    fn to_node(&self) -> Element {
+        self.unknown
+            .write(self.kind.write(Element::new(Self::tag())))
+    }
+}
+
+impl Metronome {
+    /// Tempo measured in quarter notes per minute, if per-minute is numeric
+    pub fn quarters_per_minute(&self) -> Option<f64> {
+        match &self.kind {
+            MetronomeKind::PerMinute {
+                beat_unit,
+                per_minute,
+            } => Some(per_minute.trim().parse::<f64>().ok()? * beat_unit.quarters()),
+            _ => None,
+        }
+    }
+
+    /// Factor a metric modulation applies to the quarter note tempo,
+    /// e.g. 1.5 for "♩ = ♩." where a dotted quarter takes the time of a quarter
+    pub fn modulation_ratio(&self) -> Option<f64> {
+        match &self.kind {
+            MetronomeKind::BeatUnits(unit, other) => Some(other.quarters() / unit.quarters()),
+            _ => None,
+        }
+    }
+}
+
+xml_enum! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum Mute {
+        On => "on",
+        Off => "off",
+        Straight => "straight",
+        Cup => "cup",
+        HarmonNoStem => "harmon-no-stem",
+        HarmonStem => "harmon-stem",
+        Bucket => "bucket",
+        Plunger => "plunger",
+        Hat => "hat",
+        Solotone => "solotone",
+        Practice => "practice",
+        StopMute => "stop-mute",
+        StopHand => "stop-hand",
+        Echo => "echo",
+        Palm => "palm",
+    }
+}
+
+xml_enum! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum SemiPitched {
+        High => "high",
+        MediumHigh => "medium-high",
+        Medium => "medium",
+        MediumLow => "medium-low",
+        Low => "low",
+        VeryLow => "very-low",
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PlayItem {
+    /// International Phonetic Alphabet pronunciation of a sung note
+    Ipa(String),
+    Mute(Mute),
+    SemiPitched(SemiPitched),
+    OtherPlay {
+        play_type: String,
+        text: Option<String>,
+    },
+}
+
+/// Playback technique not conveyed by the notation, e.g. a muted trumpet
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Play {
+    /// Id of the score instrument it applies to, all instruments if absent
+    pub id: Option<String>,
+    pub items: Vec<PlayItem>,
+    pub unknown: Unknown,
+}
+
+impl FromNode for Play {
+    fn tag() -> &'static str {
+        "play"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        let mut items = Vec::new();
+        for c in node.children() {
+            let item = match c.tag_name().name() {
+                "ipa" => PlayItem::Ipa(parse_text(&c, "ipa")?),
+                "mute" => PlayItem::Mute(parse_text(&c, "mute")?),
+                "semi-pitched" => PlayItem::SemiPitched(parse_text(&c, "semi-pitched")?),
+                "other-play" => PlayItem::OtherPlay {
+                    play_type: parse_attr(&c, "type")?,
+                    text: parse_optional_text(&c),
+                },
+                _ => continue,
+            };
+            items.push(item);
+        }
+
+        Ok(Play {
+            id: parse_optional_attr(node, "id")?,
+            items,
+            unknown: Unknown::parse(
+                node,
+                &["id"],
+                &["ipa", "mute", "semi-pitched", "other-play"],
+            ),
+        })
+    }
+}
+
+impl ToNode for Play {
+    fn to_node(&self) -> Element {
+        let node = Element::new(Self::tag())
+            .with_optional_attr("id", self.id.as_ref())
+            .with_children(self.items.iter().map(|i| {
+                match i {
+                    PlayItem::Ipa(ipa) => Element::new("ipa").with_text(ipa),
+                    PlayItem::Mute(m) => Element::new("mute").with_text(m),
+                    PlayItem::SemiPitched(p) => Element::new("semi-pitched").with_text(p),
+                    PlayItem::OtherPlay { play_type, text } => Element::new("other-play")
+                        .with_attr("type", play_type)
+                        .with_optional_text(text.as_ref()),
+                }
+            }));
+        self.unknown.write(node)
+    }
+}
+
+impl Play {
+    pub fn mute(&self) -> Option<Mute> {
+        self.items.iter().find_map(|i| match i {
+            PlayItem::Mute(m) => Some(*m),
+            _ => None,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SwingKind {
+    Straight,
+    Ratio {
+        /// Relative length of the first note of a swung pair
+        first: u32,
+        second: u32,
+        /// Swung note value, eighth notes if absent
+        swing_type: Option<NoteTypeValue>,
+        /// Description of the style, e.g. "hard shuffle"
+        swing_style: Option<String>,
+    },
+}
+
+impl SwingKind {
+    fn parse(node: &Node) -> Result<Self> {
+        if count_chd(node, "straight") > 0 {
+            return Ok(SwingKind::Straight);
+        }
+
+        Ok(SwingKind::Ratio {
+            first: parse_chd_text(node, "first")?,
+            second: parse_chd_text(node, "second")?,
+            swing_type: parse_optional_chd_text(node, "swing-type")?,
+            swing_style: parse_optional_chd_text(node, "swing-style")?,
+        })
+    }
+
+    fn write(&self, node: Element) -> Element {
+        match self {
+            SwingKind::Straight => node.with_child(Element::new("straight")),
+            SwingKind::Ratio {
+                first,
+                second,
+                swing_type,
+                swing_style,
+            } => node
+                .with_chd_text("first", first)
+                .with_chd_text("second", second)
+                .with_optional_chd_text("swing-type", *swing_type)
+                .with_optional_chd_text("swing-style", swing_style.as_ref()),
+        }
+    }
+}
+
+/// Swing feel of the playback, added in MusicXML 4.0
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Swing {
+    pub kind: SwingKind,
+    pub unknown: Unknown,
+}
+
+impl FromNode for Swing {
+    fn tag() -> &'static str {
+        "swing"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        Ok(Swing {
+            kind: SwingKind::parse(node)?,
+            unknown: Unknown::parse(
+                node,
+                &[],
+                &["straight", "first", "second", "swing-type", "swing-style"],
+            ),
+        })
+    }
+}
+
+impl ToNode for Swing {
+    fn to_node(&self) -> Element {
+        self.unknown
+            .write(self.kind.write(Element::new(Self::tag())))
+    }
+}
+
+impl Swing {
+    /// Position in divisions where a straight position is played with this swing,
+    /// stretching the first half of each pair of swung notes and shrinking the second
+    pub fn swung_position(&self, position: f64, divisions: u32) -> f64 {
+        let SwingKind::Ratio {
+            first,
+            second,
+            swing_type,
+            ..
+        } = &self.kind
+        else {
+            return position;
+        };
+
+        let unit = swing_type.unwrap_or(NoteTypeValue::Eighth).quarters() * divisions as f64;
+        let ratio = *first as f64 / (first + second) as f64;
+        let pair_start = (position / (2. * unit)).floor() * 2. * unit;
+        let offset = position - pair_start;
+
+        pair_start
+            + if offset < unit {
+                offset * 2. * ratio
+            } else {
+                2. * unit * ratio + (offset - unit) * 2. * (1. - ratio)
+            }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Sound {
+    /// Quarter notes per minute
+    pub tempo: Option<f64>,
+    /// Jump to the start of the score
+    pub dacapo: Option<YesNo>,
+    /// Target of a dal segno jump
+    pub segno: Option<String>,
+    /// Jump to the segno with the given target
+    pub dalsegno: Option<String>,
+    /// Target of a to coda jump
+    pub coda: Option<String>,
+    /// Jump to the coda with the given target
+    pub tocoda: Option<String>,
+    /// End of the piece after a jump
+    pub fine: Option<String>,
+    /// Percentage of the velocity of a forte, 90 in MIDI
+    pub dynamics: Option<f64>,
+    /// "yes", "no" or a percentage the pedal is pressed
+    pub damper_pedal: Option<String>,
+    pub soft_pedal: Option<String>,
+    pub sostenuto_pedal: Option<String>,
+    pub pizzicato: Option<YesNo>,
+    /// Repeat back to this point, for repeats without a forward barline
+    pub forward_repeat: Option<YesNo>,
+    pub instrument_change: Option<InstrumentChange>,
+    /// MIDI setup of the instrument from here on
+    pub midi_instrument: Option<MidiInstrument>,
+    pub play: Option<Play>,
+    pub swing: Option<Swing>,
+    /// Offset from the current position in divisions
+    pub offset: Option<Offset>,
+    pub unknown: Unknown,
+}
+
+impl FromNode for Sound {
+    fn tag() -> &'static str {
+        "sound"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        Ok(Sound {
+            tempo: parse_optional_attr(node, "tempo")?,
+            dacapo: parse_optional_attr(node, "dacapo")?,
+            segno: parse_optional_attr(node, "segno")?,
+            dalsegno: parse_optional_attr(node, "dalsegno")?,
+            coda: parse_optional_attr(node, "coda")?,
+            tocoda: parse_optional_attr(node, "tocoda")?,
+            fine: parse_optional_attr(node, "fine")?,
+            dynamics: parse_optional_attr(node, "dynamics")?,
+            damper_pedal: parse_optional_attr(node, "damper-pedal")?,
+            soft_pedal: parse_optional_attr(node, "soft-pedal")?,
+            sostenuto_pedal: parse_optional_attr(node, "sostenuto-pedal")?,
+            pizzicato: parse_optional_attr(node, "pizzicato")?,
+            forward_repeat: parse_optional_attr(node, "forward-repeat")?,
+            instrument_change: parse_option_chd(node)?,
+            midi_instrument: parse_option_chd(node)?,
+            play: parse_option_chd(node)?,
+            swing: parse_option_chd(node)?,
+            offset: parse_option_chd(node)?,
+            unknown: Unknown::parse(
+                node,
+                &[
+                    "tempo",
+                    "dacapo",
+                    "segno",
+                    "dalsegno",
+                    "coda",
+                    "tocoda",
+                    "fine",
+                    "dynamics",
+                    "damper-pedal",
+                    "soft-pedal",
+                    "sostenuto-pedal",
+                    "pizzicato",
+                    "forward-repeat",
+                ],
+                &[
+                    "instrument-change",
+                    "midi-instrument",
+                    "play",
+                    "swing",
+                    "offset",
+                ],
+            ),
+        })
+    }
+}
+
+impl ToNode for Sound {
+    fn to_node(&self) -> Element {
+        let node = Element::new(Self::tag())
+            .with_optional_attr("tempo", self.tempo)
+            .with_optional_attr("dacapo", self.dacapo)
+            .with_optional_attr("segno", self.segno.as_ref())
+            .with_optional_attr("dalsegno", self.dalsegno.as_ref())
+            .with_optional_attr("coda", self.coda.as_ref())
+            .with_optional_attr("tocoda", self.tocoda.as_ref())
+            .with_optional_attr("fine", self.fine.as_ref())
+            .with_optional_attr("dynamics", self.dynamics)
+            .with_optional_attr("damper-pedal", self.damper_pedal.as_ref())
+            .with_optional_attr("soft-pedal", self.soft_pedal.as_ref())
+            .with_optional_attr("sostenuto-pedal", self.sostenuto_pedal.as_ref())
+            .with_optional_attr("pizzicato", self.pizzicato)
+            .with_optional_attr("forward-repeat", self.forward_repeat)
+            .with_optional_child(self.instrument_change.as_ref().map(ToNode::to_node))
+            .with_optional_child(self.midi_instrument.as_ref().map(ToNode::to_node))
+            .with_optional_child(self.play.as_ref().map(ToNode::to_node))
+            .with_optional_child(self.swing.as_ref().map(ToNode::to_node))
+            .with_optional_child(self.offset.as_ref().map(ToNode::to_node));
+        self.unknown.write(node)
+    }
+}
+
+impl Sound {
+    /// MIDI velocity from the dynamics percentage
+    pub fn velocity(&self) -> Option<u8> {
+        self.dynamics
+            .map(|d| (d * 90. / 100.).round().clamp(0., 127.) as u8)
+    }
+
+    /// Jumps and targets of the playback, without the tempo
+    pub fn navigation(&self) -> Vec<Navigation> {
+        [
+            self.segno.clone().map(Navigation::Segno),
+            self.coda.clone().map(Navigation::Coda),
+            (self.dacapo == Some(YesNo::Yes)).then_some(Navigation::DaCapo),
+            self.dalsegno.clone().map(Navigation::DalSegno),
+            self.tocoda.clone().map(Navigation::ToCoda),
+            self.fine.as_ref().map(|_| Navigation::Fine),
+        ]
+        .into_iter()
+        .flatten()
+        .collect()
+    }
+}
+
+/// Jump instruction or jump target of the playback
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Navigation {
+    /// Target of dal segno jumps, with the target name given in the sound
+    Segno(String),
+    /// Target of to coda jumps, with the target name given in the sound
+    Coda(String),
+    DaCapo,
+    DalSegno(String),
+    ToCoda(String),
+    Fine,
+}
+
+impl Navigation {
+    /// Jump written as text, e.g. "D.S. al Coda", without a known target
+    pub fn from_words(text: &str) -> Option<Self> {
+        let text = text.trim().to_lowercase();
+        if text.starts_with("d.c.") || text.starts_with("da capo") {
+            Some(Navigation::DaCapo)
+        } else if text.starts_with("d.s.") || text.starts_with("dal segno") {
+            Some(Navigation::DalSegno(String::new()))
+        } else if text.starts_with("to coda") {
+            Some(Navigation::ToCoda(String::new()))
+        } else if text == "fine" {
+            Some(Navigation::Fine)
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Words {
+    pub text: String,
+    pub color: Option<Color>,
+    pub font: Font,
+    pub position: Position,
+    pub unknown: Unknown,
+}
+
+impl FromNode for Words {
+    fn tag() -> &'static str {
+        "words"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        Ok(Words {
+            text: node.text().unwrap_or_default().to_owned(),
+            color: parse_optional_attr(node, "color")?,
+            font: Font::parse(node)?,
+            position: Position::parse(node)?,
+            unknown: Unknown::parse_attrs(
+                node,
+                &[
+                    "color",
+                    "default-x",
+                    "default-y",
+                    "relative-x",
+                    "relative-y",
+                    "font-family",
+                    "font-style",
+                    "font-size",
+                    "font-weight",
+                ],
+            ),
+        })
+    }
+}
+
+impl ToNode for Words {
+    fn to_node(&self) -> Element {
+        let node = Element::new(Self::tag()).with_optional_attr("color", self.color);
+        let node = self.position.write_attrs(node);
+        let node = self.font.write_attrs(node).with_text(&self.text);
+        self.unknown.write(node)
+    }
+}
+
+/// Musical symbol written in a direction, named by its SMuFL glyph, e.g. "pictTuningKey"
+#[derive(Debug, Clone, PartialEq)]
+pub struct Symbol {
+    pub glyph: String,
+    pub print_style: PrintStyle,
+    pub unknown: Unknown,
+}
+
+impl FromNode for Symbol {
+    fn tag() -> &'static str {
+        "symbol"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        Ok(Symbol {
+            glyph: parse_text(node, Self::tag())?,
+            print_style: PrintStyle::parse(node)?,
+            unknown: Unknown::parse_attrs(
+                node,
+                &[
+                    "default-x",
+                    "default-y",
+                    "relative-x",
+                    "relative-y",
+                    "font-family",
+                    "font-style",
+                    "font-size",
+                    "font-weight",
+                    "color",
+                ],
+            ),
+        })
+    }
+}
+
+impl ToNode for Symbol {
+    fn to_node(&self) -> Element {
+        let node = self
+            .print_style
+            .write_attrs(Element::new(Self::tag()))
+            .with_text(&self.glyph);
+        self.unknown.write(node)
+    }
+}
+
+/// Words and symbols of a direction, mixed in document order
+#[derive(Debug, Clone, PartialEq)]
+pub enum WordsOrSymbol {
+    Words(Words),
+    Symbol(Symbol),
+}
+
+impl WordsOrSymbol {
+    fn parse_all(node: &Node) -> Result<Vec<Self>> {
+        node.children()
+            .filter_map(|c| match c.tag_name().name() {
+                "words" => Some(Words::from_node(&c).map(WordsOrSymbol::Words)),
+                "symbol" => Some(Symbol::from_node(&c).map(WordsOrSymbol::Symbol)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn to_node(&self) -> Element {
+        match self {
+            WordsOrSymbol::Words(w) => w.to_node(),
+            WordsOrSymbol::Symbol(s) => s.to_node(),
+        }
+    }
+}
+
+/// Sign without content, e.g. a segno, coda or damp mark
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Sign {
+    pub print_style: PrintStyle,
+    /// SMuFL glyph replacing the default one
+    pub smufl: Option<String>,
+    pub id: Option<String>,
+    pub unknown: Unknown,
+}
+
+impl Sign {
+    fn parse(node: &Node) -> Result<Self> {
+        Ok(Sign {
+            print_style: PrintStyle::parse(node)?,
+            smufl: parse_optional_attr(node, "smufl")?,
+            id: parse_optional_attr(node, "id")?,
+            unknown: Unknown::parse(
+                node,
+                &[
+                    "default-x",
+                    "default-y",
+                    "relative-x",
+                    "relative-y",
+                    "font-family",
+                    "font-style",
+                    "font-size",
+                    "font-weight",
+                    "color",
+                    "smufl",
+                    "id",
+                ],
+                &[],
+            ),
+        })
+    }
+
+    fn parse_all(node: &Node, tag: &str) -> Result<Vec<Self>> {
+        node.children()
+            .filter(|c| c.tag_name().name() == tag)
+            .map(|c| Sign::parse(&c))
+            .collect()
+    }
+
+    fn to_node(&self, tag: &str) -> Element {
+        let node = self
+            .print_style
+            .write_attrs(Element::new(tag))
+            .with_optional_attr("smufl", self.smufl.as_ref())
+            .with_optional_attr("id", self.id.as_ref());
+        self.unknown.write(node)
+    }
+}
+
+/// Rehearsal mark, usually a letter or number in a box
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rehearsal {
+    pub text: String,
+    pub enclosure: Option<EnclosureShape>,
+    pub unknown: Unknown,
+}
+
+impl FromNode for Rehearsal {
+    fn tag() -> &'static str {
+        "rehearsal"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        Ok(Rehearsal {
+            text: node.text().unwrap_or_default().to_owned(),
+            enclosure: parse_optional_attr(node, "enclosure")?,
+            unknown: Unknown::parse_attrs(node, &["enclosure"]),
+        })
+    }
+}
+
+impl ToNode for Rehearsal {
+    fn to_node(&self) -> Element {
+        let node = Element::new(Self::tag())
+            .with_optional_attr("enclosure", self.enclosure)
+            .with_text(&self.text);
+        self.unknown.write(node)
+    }
+}
+
+xml_enum! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum Dynamic {
+        Pppppp => "pppppp",
+        Ppppp => "ppppp",
+        Pppp => "pppp",
+        Ppp => "ppp",
+        Pp => "pp",
+        P => "p",
+        Mp => "mp",
+        Mf => "mf",
+        F => "f",
+        Ff => "ff",
+        Fff => "fff",
+        Ffff => "ffff",
+        Fffff => "fffff",
+        Ffffff => "ffffff",
+        Sf => "sf",
+        Sfp => "sfp",
+        Sfpp => "sfpp",
+        Fp => "fp",
+        Rf => "rf",
+        Rfz => "rfz",
+        Sfz => "sfz",
+        Sffz => "sffz",
+        Fz => "fz",
+        N => "n",
+        Pf => "pf",
+        Sfzp => "sfzp",
+    }
+}
+
+impl Dynamic {
+    /// Conventional MIDI velocity of the dynamic level, none for accents like sfz
+    pub fn velocity(&self) -> Option<u8> {
+        match self {
+            Dynamic::Pppppp => Some(1),
+            Dynamic::Ppppp => Some(5),
+            Dynamic::Pppp => Some(10),
+            Dynamic::Ppp => Some(16),
+            Dynamic::Pp => Some(33),
+            Dynamic::P => Some(49),
+            Dynamic::Mp => Some(64),
+            Dynamic::Mf => Some(80),
+            Dynamic::F => Some(96),
+            Dynamic::Ff => Some(112),
+            Dynamic::Fff => Some(126),
+            Dynamic::Ffff | Dynamic::Fffff | Dynamic::Ffffff => Some(127),
+            Dynamic::N => Some(0),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Dynamics {
+    pub placement: Option<AboveBelow>,
+    pub color: Option<Color>,
+    pub position: Position,
+    pub marks: Vec<Dynamic>,
+    pub other_dynamics: Option<String>,
+    pub unknown: Unknown,
+}
+
+impl FromNode for Dynamics {
+    fn tag() -> &'static str {
+        "dynamics"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        let known: Vec<&str> = node
+            .children()
+            .map(|c| c.tag_name().name())
+            .filter(|n| n.parse::<Dynamic>().is_ok())
+            .chain(["other-dynamics"])
+            .collect();
+
+        Ok(Dynamics {
+            placement: parse_optional_attr(node, "placement")?,
+            color: parse_optional_attr(node, "color")?,
+            position: Position::parse(node)?,
+            marks: node
+                .children()
+                .filter_map(|c| c.tag_name().name().parse().ok())
+                .collect(),
+            other_dynamics: parse_optional_chd_text(node, "other-dynamics")?,
+            unknown: Unknown::parse(
+                node,
+                &[
+                    "placement",
+                    "color",
+                    "default-x",
+                    "default-y",
+                    "relative-x",
+                    "relative-y",
+                ],
+                &known,
+            ),
+        })
+    }
+}
+
+impl ToNode for Dynamics {
+    fn to_node(&self) -> Element {
+        let node = Element::new(Self::tag())
+            .with_optional_attr("placement", self.placement)
+            .with_optional_attr("color", self.color);
+        let node = self
+            .position
+            .write_attrs(node)
+            .with_children(self.marks.iter().map(|m| Element::new(m.to_string())))
+            .with_optional_chd_text("other-dynamics", self.other_dynamics.as_ref());
+        self.unknown.write(node)
+    }
+}
+
+xml_enum! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum PedalType {
+        Start => "start",
+        Stop => "stop",
+        Sostenuto => "sostenuto",
+        Change => "change",
+        Continue => "continue",
+        Discontinue => "discontinue",
+        Resume => "resume",
+    }
+}
+
+impl PedalType {
+    /// Whether the pedal is held down after the mark, a change releases and presses again
+    pub fn is_down(&self) -> bool {
+        !matches!(self, PedalType::Stop | PedalType::Discontinue)
+    }
+}
+
+/// Piano pedal mark, the sustain pedal unless sostenuto
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pedal {
+    pub pedal_type: PedalType,
+    pub number: Option<NumberLevel>,
+    /// Displayed as a bracket line
+    pub line: Option<YesNo>,
+    /// Displayed as Ped. and * signs
+    pub sign: Option<YesNo>,
+    pub abbreviated: Option<YesNo>,
+    pub unknown: Unknown,
+}
+
+impl FromNode for Pedal {
+    fn tag() -> &'static str {
+        "pedal"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        Ok(Pedal {
+            pedal_type: parse_attr(node, "type")?,
+            number: parse_optional_attr(node, "number")?,
+            line: parse_optional_attr(node, "line")?,
+            sign: parse_optional_attr(node, "sign")?,
+            abbreviated: parse_optional_attr(node, "abbreviated")?,
+            unknown: Unknown::parse(
+                node,
+                &["type", "number", "line", "sign", "abbreviated"],
+                &[],
+            ),
+        })
+    }
+}
+
+impl ToNode for Pedal {
+    fn to_node(&self) -> Element {
+        let node = Element::new(Self::tag())
+            .with_attr("type", self.pedal_type)
+            .with_optional_attr("number", self.number)
+            .with_optional_attr("line", self.line)
+            .with_optional_attr("sign", self.sign)
+            .with_optional_attr("abbreviated", self.abbreviated);
+        self.unknown.write(node)
+    }
+}
+
+/// 8va and 8vb lines, the type is the direction the notes are displayed from their true pitch
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OctaveShift {
+    pub shift_type: UpDownStopContinue,
+    pub number: Option<NumberLevel>,
+    /// 8 for one octave, 15 for two octaves, 22 for three octaves, absent for one octave
+    pub size: Option<u8>,
+    pub unknown: Unknown,
+}
+
+impl FromNode for OctaveShift {
+    fn tag() -> &'static str {
+        "octave-shift"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        Ok(OctaveShift {
+            shift_type: parse_attr(node, "type")?,
+            number: parse_optional_attr(node, "number")?,
+            size: parse_optional_attr(node, "size")?,
+            unknown: Unknown::parse(node, &["type", "number", "size"], &[]),
+        })
+    }
+}
+
+impl ToNode for OctaveShift {
+    fn to_node(&self) -> Element {
+        let node = Element::new(Self::tag())
+            .with_attr("type", self.shift_type)
+            .with_optional_attr("number", self.number)
+            .with_optional_attr("size", self.size);
+        self.unknown.write(node)
+    }
+}
+
+impl OctaveShift {
+    /// Octaves from the written to the sounding pitch, positive for 8va
+    pub fn octaves(&self) -> i32 {
+        let octaves = (self.size.unwrap_or(8) as i32 + 1) / 7;
+        match self.shift_type {
+            UpDownStopContinue::Down => octaves,
+            UpDownStopContinue::Up => -octaves,
+            _ => 0,
+        }
+    }
+
+    /// Sounding pitch of a note written under this shift,
+    /// none if it leaves the octaves a pitch can be written in
+    pub fn apply(&self, pitch: &Pitch) -> Option<Pitch> {
+        Some(Pitch {
+            octave: u8::try_from(pitch.octave as i32 + self.octaves()).ok()?,
+            ..pitch.clone()
+        })
+    }
+}
+
+xml_enum! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum WedgeType {
+        Crescendo => "crescendo",
+        Diminuendo => "diminuendo",
+        Stop => "stop",
+        Continue => "continue",
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Wedge {
+    pub wedge_type: WedgeType,
+    /// Distinguishes overlapping wedges, pairing a start with its stop
+    pub number: Option<NumberLevel>,
+    /// Opening of the hairpin in tenths
+    pub spread: Option<Tenths>,
+    pub color: Option<Color>,
+    pub position: Position,
+    pub unknown: Unknown,
+}
+
+impl FromNode for Wedge {
+    fn tag() -> &'static str {
+        "wedge"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        Ok(Wedge {
+            wedge_type: parse_attr(node, "type")?,
+            number: parse_optional_attr(node, "number")?,
+            spread: parse_optional_attr(node, "spread")?,
+            color: parse_optional_attr(node, "color")?,
+            position: Position::parse(node)?,
+            unknown: Unknown::parse(
+                node,
+                &[
+                    "type",
+                    "number",
+                    "spread",
+                    "color",
+                    "default-x",
+                    "default-y",
+                    "relative-x",
+                    "relative-y",
+                ],
+                &[],
+            ),
+        })
+    }
+}
+
+impl ToNode for Wedge {
+    fn to_node(&self) -> Element {
+        let node = Element::new(Self::tag())
+            .with_attr("type", self.wedge_type)
+            .with_optional_attr("number", self.number)
+            .with_optional_attr("spread", self.spread)
+            .with_optional_attr("color", self.color);
+        let node = self.position.write_attrs(node);
+        self.unknown.write(node)
+    }
+}
+
+/// Dashed line continuing a text direction, e.g. "cresc. - - -"
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Dashes {
+    pub dashes_type: StartStopContinue,
+    /// Distinguishes overlapping dashes, pairing a start with its stop
+    pub number: Option<NumberLevel>,
+    pub unknown: Unknown,
+}
+
+impl FromNode for Dashes {
+    fn tag() -> &'static str {
+        "dashes"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        Ok(Dashes {
+            dashes_type: parse_attr(node, "type")?,
+            number: parse_optional_attr(node, "number")?,
+            unknown: Unknown::parse(node, &["type", "number"], &[]),
+        })
+    }
+}
+
+impl ToNode for Dashes {
+    fn to_node(&self) -> Element {
+        let node = Element::new(Self::tag())
+            .with_attr("type", self.dashes_type)
+            .with_optional_attr("number", self.number);
+        self.unknown.write(node)
+    }
+}
+
+xml_enum! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum LineEnd {
+        Up => "up",
+        Down => "down",
+        Both => "both",
+        Arrow => "arrow",
+        None => "none",
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Bracket {
+    pub bracket_type: StartStopContinue,
+    /// Distinguishes overlapping brackets, pairing a start with its stop
+    pub number: Option<NumberLevel>,
+    /// Hook at the start or stop of the bracket
+    pub line_end: LineEnd,
+    /// Length of the hook in tenths
+    pub end_length: Option<Tenths>,
+    pub line_type: Option<LineType>,
+    pub unknown: Unknown,
+}
+
+impl FromNode for Bracket {
+    fn tag() -> &'static str {
+        "bracket"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        Ok(Bracket {
+            bracket_type: parse_attr(node, "type")?,
+            number: parse_optional_attr(node, "number")?,
+            line_end: parse_attr(node, "line-end")?,
+            end_length: parse_optional_attr(node, "end-length")?,
+            line_type: parse_optional_attr(node, "line-type")?,
+            unknown: Unknown::parse(
+                node,
+                &["type", "number", "line-end", "end-length", "line-type"],
+                &[],
+            ),
+        })
+    }
+}
+
+impl ToNode for Bracket {
+    fn to_node(&self) -> Element {
+        let node = Element::new(Self::tag())
+            .with_attr("type", self.bracket_type)
+            .with_optional_attr("number", self.number)
+            .with_attr("line-end", self.line_end)
+            .with_optional_attr("end-length", self.end_length)
+            .with_optional_attr("line-type", self.line_type);
+        self.unknown.write(node)
+    }
+}
+
+/// Harp pedal setting of one string
+#[derive(Debug, Clone, PartialEq)]
+pub struct PedalTuning {
+    pub step: Step,
+    pub alter: f64,
+    pub unknown: Unknown,
+}
+
+impl FromNode for PedalTuning {
+    fn tag() -> &'static str {
+        "pedal-tuning"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        Ok(PedalTuning {
+            step: parse_chd_text(node, "pedal-step")?,
+            alter: parse_chd_text(node, "pedal-alter")?,
+            unknown: Unknown::parse(node, &[], &["pedal-step", "pedal-alter"]),
+        })
+    }
+}
+
+impl ToNode for PedalTuning {
+    fn to_node(&self) -> Element {
+        let node = Element::new(Self::tag())
+            .with_chd_text("pedal-step", self.step)
+            .with_chd_text("pedal-alter", self.alter);
+        self.unknown.write(node)
+    }
+}
+
+/// Accordion registration by the reed ranks in use
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccordionRegistration {
+    pub high: bool,
+    /// Number of dots in the middle section, from 1 to 3
+    pub middle: Option<u8>,
+    pub low: bool,
+    pub unknown: Unknown,
+}
+
+impl FromNode for AccordionRegistration {
+    fn tag() -> &'static str {
+        "accordion-registration"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        Ok(AccordionRegistration {
+            high: count_chd(node, "accordion-high") > 0,
+            middle: parse_optional_chd_text(node, "accordion-middle")?,
+            low: count_chd(node, "accordion-low") > 0,
+            unknown: Unknown::parse(
+                node,
+                &[],
+                &["accordion-high", "accordion-middle", "accordion-low"],
+            ),
+        })
+    }
+}
+
+impl ToNode for AccordionRegistration {
+    fn to_node(&self) -> Element {
+        let node = Element::new(Self::tag())
+            .with_optional_child(self.high.then(|| Element::new("accordion-high")))
+            .with_optional_chd_text("accordion-middle", self.middle)
+            .with_optional_child(self.low.then(|| Element::new("accordion-low")));
+        self.unknown.write(node)
+    }
+}
+
+xml_enum! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum OnOff {
+        On => "on",
+        Off => "off",
+    }
+}
+
+xml_enum! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum PrincipalVoiceSymbol {
+        Hauptstimme => "Hauptstimme",
+        Nebenstimme => "Nebenstimme",
+        Plain => "plain",
+        None => "none",
+    }
+}
+
+/// Bracketed section of the principal or secondary voice
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrincipalVoice {
+    pub principal_voice_type: StartStop,
+    pub symbol: PrincipalVoiceSymbol,
+    pub text: Option<String>,
+    pub unknown: Unknown,
+}
+
+impl FromNode for PrincipalVoice {
+    fn tag() -> &'static str {
+        "principal-voice"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        Ok(PrincipalVoice {
+            principal_voice_type: parse_attr(node, "type")?,
+            symbol: parse_attr(node, "symbol")?,
+            text: parse_optional_text(node),
+            unknown: Unknown::parse_attrs(node, &["type", "symbol"]),
+        })
+    }
+}
+
+impl ToNode for PrincipalVoice {
+    fn to_node(&self) -> Element {
+        let node = Element::new(Self::tag())
+            .with_attr("type", self.principal_voice_type)
+            .with_attr("symbol", self.symbol)
+            .with_optional_text(self.text.as_ref());
+        self.unknown.write(node)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PercussionKind {
+    /// Glass instrument, e.g. "wind chimes"
+    Glass(String),
+    /// Metal instrument, e.g. "cowbell" or "triangle"
+    Metal(String),
+    /// Wooden instrument, e.g. "claves" or "temple block"
+    Wood(String),
+    /// Pitched instrument, e.g. "xylophone"
+    Pitched(String),
+    /// Skinned instrument, e.g. "snare drum"
+    Membrane(String),
+    /// Sound effect, e.g. "siren" or "whistle"
+    Effect(String),
+    Timpani,
+    Beater {
+        value: String,
+        /// Direction of the beater tip, e.g. "up"
+        tip: Option<String>,
+    },
+    Stick {
+        stick_type: String,
+        stick_material: String,
+        tip: Option<String>,
+    },
+    /// Where to strike the instrument, e.g. "center" or "rim"
+    StickLocation(String),
+    OtherPercussion(Option<String>),
+    /// Pictogram not modeled yet, kept as raw xml
+    Other(Element),
+}
+
+impl PercussionKind {
+    fn parse(content: &Node) -> Result<Self> {
+        Ok(match content.tag_name().name() {
+            "glass" => PercussionKind::Glass(parse_text(content, "glass")?),
+            "metal" => PercussionKind::Metal(parse_text(content, "metal")?),
+            "wood" => PercussionKind::Wood(parse_text(content, "wood")?),
+            "pitched" => PercussionKind::Pitched(parse_text(content, "pitched")?),
+            "membrane" => PercussionKind::Membrane(parse_text(content, "membrane")?),
+            "effect" => PercussionKind::Effect(parse_text(content, "effect")?),
+            "timpani" => PercussionKind::Timpani,
+            "beater" => PercussionKind::Beater {
+                value: parse_text(content, "beater")?,
+                tip: parse_optional_attr(content, "tip")?,
+            },
+            "stick" => PercussionKind::Stick {
+                stick_type: parse_chd_text(content, "stick-type")?,
+                stick_material: parse_chd_text(content, "stick-material")?,
+                tip: parse_optional_attr(content, "tip")?,
+            },
+            "stick-location" => {
+                PercussionKind::StickLocation(parse_text(content, "stick-location")?)
+            }
+            "other-percussion" => PercussionKind::OtherPercussion(parse_optional_text(content)),
+            _ => PercussionKind::Other(Element::from(content)),
+        })
+    }
+
+    fn to_node(&self) -> Element {
+        match self {
+            PercussionKind::Glass(g) => Element::new("glass").with_text(g),
+            PercussionKind::Metal(m) => Element::new("metal").with_text(m),
+            PercussionKind::Wood(w) => Element::new("wood").with_text(w),
+            PercussionKind::Pitched(p) => Element::new("pitched").with_text(p),
+            PercussionKind::Membrane(m) => Element::new("membrane").with_text(m),
+            PercussionKind::Effect(e) => Element::new("effect").with_text(e),
+            PercussionKind::Timpani => Element::new("timpani"),
+            PercussionKind::Beater { value, tip } => Element::new("beater")
+                .with_optional_attr("tip", tip.as_ref())
+                .with_text(value),
+            PercussionKind::Stick {
+                stick_type,
+                stick_material,
+                tip,
+            } => Element::new("stick")
+                .with_optional_attr("tip", tip.as_ref())
+                .with_chd_text("stick-type", stick_type)
+                .with_chd_text("stick-material", stick_material),
+            PercussionKind::StickLocation(l) => Element::new("stick-location").with_text(l),
+            PercussionKind::OtherPercussion(t) => {
+                Element::new("other-percussion").with_optional_text(t.as_ref())
+            }
+            PercussionKind::Other(e) => e.clone(),
+        }
+    }
+}
+
+/// Percussion pictogram, e.g. an instrument or beater to pick up
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Percussion {
+    pub kind: PercussionKind,
+    pub unknown: Unknown,
+}
+
+impl FromNode for Percussion {
+    fn tag() -> &'static str {
+        "percussion"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        let content = node.children().find(Node::is_element).ok_or(NodeNotFound {
+            tag: "percussion content",
+            parent_tag: Self::tag().to_owned(),
+        })?;
+
+        Ok(Percussion {
+            kind: PercussionKind::parse(&content)?,
+            unknown: Unknown::parse(node, &[], &[content.tag_name().name()]),
+        })
+    }
+}
+
+impl ToNode for Percussion {
+    fn to_node(&self) -> Element {
+        let node = Element::new(Self::tag()).with_child(self.kind.to_node());
+        self.unknown.write(node)
+    }
+}
+
+/// Tuning of a string that differs from the usual tuning
+#[derive(Debug, Clone, PartialEq)]
+pub struct Accord {
+    /// String number, 1 is the highest pitched string
+    pub string: u8,
+    pub tuning_step: Step,
+    pub tuning_alter: Option<f64>,
+    pub tuning_octave: u8,
+    pub unknown: Unknown,
+}
+
+impl FromNode for Accord {
+    fn tag() -> &'static str {
+        "accord"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        Ok(Accord {
+            string: parse_attr(node, "string")?,
+            tuning_step: parse_chd_text(node, "tuning-step")?,
+            tuning_alter: parse_optional_chd_text(node, "tuning-alter")?,
+            tuning_octave: parse_chd_text(node, "tuning-octave")?,
+            unknown: Unknown::parse(
+                node,
+                &["string"],
+                &["tuning-step", "tuning-alter", "tuning-octave"],
+            ),
+        })
+    }
+}
+
+impl ToNode for Accord {
+    fn to_node(&self) -> Element {
+        let node = Element::new(Self::tag())
+            .with_attr("string", self.string)
+            .with_chd_text("tuning-step", self.tuning_step)
+            .with_optional_chd_text("tuning-alter", self.tuning_alter)
+            .with_chd_text("tuning-octave", self.tuning_octave);
+        self.unknown.write(node)
+    }
+}
+
+impl Accord {
+    /// Pitch of the open string
+    pub fn pitch(&self) -> Pitch {
+        Pitch {
+            step: self.tuning_step,
+            alter: self.tuning_alter.unwrap_or(0.),
+            octave: self.tuning_octave,
+            unknown: Unknown::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DirectionType {
+    Rehearsal(Vec<Rehearsal>),
+    Segno(Vec<Sign>),
+    Coda(Vec<Sign>),
+    Pedal(Pedal),
+    OctaveShift(OctaveShift),
+    Words(Vec<WordsOrSymbol>),
+    Wedge(Wedge),
+    Dynamics(Vec<Dynamics>),
+    Metronome(Metronome),
+    Dashes(Dashes),
+    Bracket(Bracket),
+    HarpPedals(Vec<PedalTuning>),
+    AccordionRegistration(AccordionRegistration),
+    StringMute(OnOff),
+    /// Dampen the sound of the instrument
+    Damp(Sign),
+    /// Dampen all strings of the instrument
+    DampAll(Sign),
+    /// Look at the conductor, e.g. for a page turn or tempo change
+    Eyeglasses(Sign),
+    PrincipalVoice(PrincipalVoice),
+    Percussion(Vec<Percussion>),
+    /// Alternate tuning of the strings
+    Scordatura(Vec<Accord>),
+    /// Direction without a dedicated element, described by its text
+    OtherDirection(Option<String>),
+    /// Content not modeled yet, kept as raw xml
+    Other(Vec<Element>),
+}
+
+impl FromNode for DirectionType {
+    fn tag() -> &'static str {
+        "direction-type"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        let content = node.children().find(Node::is_element).ok_or(NodeNotFound {
+            tag: "direction-type content",
+            parent_tag: Self::tag().to_owned(),
+        })?;
+
+        Ok(match content.tag_name().name() {
+            "rehearsal" => DirectionType::Rehearsal(parse_children(node)?),
+            "segno" => DirectionType::Segno(Sign::parse_all(node, "segno")?),
+            "coda" => DirectionType::Coda(Sign::parse_all(node, "coda")?),
+            "pedal" => DirectionType::Pedal(Pedal::from_node(&content)?),
+            "octave-shift" => DirectionType::OctaveShift(OctaveShift::from_node(&content)?),
+            "words" | "symbol" => DirectionType::Words(WordsOrSymbol::parse_all(node)?),
+            "wedge" => DirectionType::Wedge(Wedge::from_node(&content)?),
+            "dynamics" => DirectionType::Dynamics(parse_children(node)?),
+            "metronome" => DirectionType::Metronome(Metronome::from_node(&content)?),
+            "dashes" => DirectionType::Dashes(Dashes::from_node(&content)?),
+            "bracket" => DirectionType::Bracket(Bracket::from_node(&content)?),
+            "harp-pedals" => DirectionType::HarpPedals(parse_children(&content)?),
+            "accordion-registration" => {
+                DirectionType::AccordionRegistration(AccordionRegistration::from_node(&content)?)
+            }
+            "string-mute" => DirectionType::StringMute(parse_attr(&content, "type")?),
+            "damp" => DirectionType::Damp(Sign::parse(&content)?),
+            "damp-all" => DirectionType::DampAll(Sign::parse(&content)?),
+            "eyeglasses" => DirectionType::Eyeglasses(Sign::parse(&content)?),
+            "principal-voice" => {
+                DirectionType::PrincipalVoice(PrincipalVoice::from_node(&content)?)
+            }
+            "other-direction" => DirectionType::OtherDirection(parse_optional_text(&content)),
+            "percussion" => DirectionType::Percussion(parse_children(node)?),
+            "scordatura" => DirectionType::Scordatura(parse_children(&content)?),
+            _ => DirectionType::Other(
+                node.children()
+                    .filter(Node::is_element)
+                    .map(|c| Element::from(&c))
+                    .collect(),
+            ),
+        })
+    }
+}
+
+impl ToNode for DirectionType {
+    fn to_node(&self) -> Element {
+        let node = Element::new(Self::tag());
+        match self {
+            DirectionType::Rehearsal(r) => node.with_children(children_to_nodes(r)),
+            DirectionType::Segno(s) => node.with_children(s.iter().map(|s| s.to_node("segno"))),
+            DirectionType::Coda(c) => node.with_children(c.iter().map(|c| c.to_node("coda"))),
+            DirectionType::Pedal(p) => node.with_child(p.to_node()),
+            DirectionType::OctaveShift(o) => node.with_child(o.to_node()),
+            DirectionType::Words(words) => {
+                node.with_children(words.iter().map(WordsOrSymbol::to_node))
+            }
+            DirectionType::Wedge(w) => node.with_child(w.to_node()),
+            DirectionType::Dynamics(d) => node.with_children(children_to_nodes(d)),
+            DirectionType::Metronome(m) => node.with_child(m.to_node()),
+            DirectionType::Dashes(d) => node.with_child(d.to_node()),
+            DirectionType::Bracket(b) => node.with_child(b.to_node()),
+            DirectionType::HarpPedals(p) => {
+                node.with_child(Element::new("harp-pedals").with_children(children_to_nodes(p)))
+            }
+            DirectionType::AccordionRegistration(a) => node.with_child(a.to_node()),
+            DirectionType::StringMute(m) => {
+                node.with_child(Element::new("string-mute").with_attr("type", m))
+            }
+            DirectionType::Damp(d) => node.with_child(d.to_node("damp")),
+            DirectionType::DampAll(d) => node.with_child(d.to_node("damp-all")),
+            DirectionType::Eyeglasses(e) => node.with_child(e.to_node("eyeglasses")),
+            DirectionType::PrincipalVoice(p) => node.with_child(p.to_node()),
+            DirectionType::Percussion(p) => node.with_children(children_to_nodes(p)),
+            DirectionType::Scordatura(a) => {
+                node.with_child(Element::new("scordatura").with_children(children_to_nodes(a)))
+            }
+            DirectionType::OtherDirection(t) => {
+                node.with_child(Element::new("other-direction").with_optional_text(t.as_ref()))
+            }
+            DirectionType::Other(elements) => node.with_children(elements.iter().cloned()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Direction {
+    pub placement: Option<AboveBelow>,
+    /// Tempo-like text aligned with the time signature rather than a note
+    pub directive: Option<YesNo>,
+    pub direction_types: Vec<DirectionType>,
+    pub offset: Option<Offset>,
+    pub editorial: Editorial,
+    pub staff: Option<u8>,
+    pub sound: Option<Sound>,
+    pub listening: Option<Listening>,
+    pub unknown: Unknown,
+}
+
+impl FromNode for Direction {
+    fn tag() -> &'static str {
+        "direction"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        let direction_types: Vec<DirectionType> = parse_children(node)?;
+        if direction_types.is_empty() {
+            return Err(NodeNotFound {
+                tag: DirectionType::tag(),
+                parent_tag: Self::tag().to_owned(),
+            });
+        }
+
+        Ok(Direction {
+            placement: parse_optional_attr(node, "placement")?,
+            directive: parse_optional_attr(node, "directive")?,
+            direction_types,
+            offset: parse_option_chd(node)?,
+            editorial: Editorial::parse(node)?,
+            staff: parse_optional_chd_text(node, "staff")?,
+            sound: parse_option_chd(node)?,
+            listening: parse_option_chd(node)?,
+            unknown: Unknown::parse(
+                node,
+                &["placement", "directive"],
+                &[
+                    "direction-type",
+                    "offset",
+                    "footnote",
+                    "level",
+                    "staff",
+                    "sound",
+                    "listening",
+                ],
+            ),
+        })
+    }
+}
+
+impl ToNode for Direction {
+    fn to_node(&self) -> Element {
+        let node = Element::new(Self::tag())
+            .with_optional_attr("placement", self.placement)
+            .with_optional_attr("directive", self.directive)
+            .with_children(children_to_nodes(&self.direction_types))
+            .with_optional_child(self.offset.as_ref().map(ToNode::to_node))
+            .with_children(self.editorial.to_nodes())
+            .with_optional_chd_text("staff", self.staff)
+            .with_optional_child(self.sound.as_ref().map(ToNode::to_node))
+            .with_optional_child(self.listening.as_ref().map(ToNode::to_node));
+        self.unknown.write(node)
+    }
+}
+
+impl Direction {
+    /// Divisions from the current position at which the direction sounds,
+    /// an offset of the direction only counts if it affects the sound
+    pub fn playback_offset(&self) -> f64 {
+        let offset = self
+            .offset
+            .as_ref()
+            .filter(|o| o.sound == Some(YesNo::Yes))
+            .map_or(0., |o| o.value);
+        let sound_offset = self
+            .sound
+            .as_ref()
+            .and_then(|s| s.offset.as_ref())
+            .map_or(0., |o| o.value);
+
+        offset + sound_offset
+    }
+
+    pub fn octave_shift(&self) -> Option<&OctaveShift> {
+        self.direction_types.iter().find_map(|t| match t {
+            DirectionType::OctaveShift(o) => Some(o),
+            _ => None,
+        })
+    }
+
+    pub fn pedal(&self) -> Option<&Pedal> {
+        self.direction_types.iter().find_map(|t| match t {
+            DirectionType::Pedal(p) => Some(p),
+            _ => None,
+        })
+    }
+
+    /// Jumps and targets of this direction, preferring the playback semantics of sound
+    /// over the segno and coda signs and the jump instructions written as words
+    pub fn navigation(&self) -> Vec<Navigation> {
+        let sound = self
+            .sound
+            .as_ref()
+            .map(Sound::navigation)
+            .unwrap_or_default();
+        if !sound.is_empty() {
+            return sound;
+        }
+
+        self.direction_types
+            .iter()
+            .flat_map(|t| match t {
+                DirectionType::Segno(_) => vec![Navigation::Segno(String::new())],
+                DirectionType::Coda(_) => vec![Navigation::Coda(String::new())],
+                DirectionType::Words(words) => words
+                    .iter()
+                    .filter_map(|w| match w {
+                        WordsOrSymbol::Words(w) => Navigation::from_words(&w.text),
+                        WordsOrSymbol::Symbol(_) => None,
+                    })
+                    .collect(),
+                _ => vec![],
+            })
+            .collect()
+    }
+
+    pub fn rehearsals(&self) -> impl Iterator<Item = &Rehearsal> {
+        self.direction_types.iter().flat_map(|t| match t {
+            DirectionType::Rehearsal(r) => r.as_slice(),
+            _ => &[],
+        })
+    }
+
+    pub fn metronome(&self) -> Option<&Metronome> {
+        self.direction_types.iter().find_map(|t| match t {
+            DirectionType::Metronome(m) => Some(m),
+            _ => None,
+        })
+    }
+
+    /// Retuned strings of a scordatura direction
+    pub fn scordatura(&self) -> Option<&[Accord]> {
+        self.direction_types.iter().find_map(|t| match t {
+            DirectionType::Scordatura(a) => Some(a.as_slice()),
+            _ => None,
+        })
+    }
+
+    pub fn dashes(&self) -> Option<&Dashes> {
+        self.direction_types.iter().find_map(|t| match t {
+            DirectionType::Dashes(d) => Some(d),
+            _ => None,
+        })
+    }
+
+    pub fn bracket(&self) -> Option<&Bracket> {
+        self.direction_types.iter().find_map(|t| match t {
+            DirectionType::Bracket(b) => Some(b),
+            _ => None,
+        })
+    }
+
+    pub fn principal_voice(&self) -> Option<&PrincipalVoice> {
+        self.direction_types.iter().find_map(|t| match t {
+            DirectionType::PrincipalVoice(p) => Some(p),
+            _ => None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use roxmltree::Document;
+
+    use super::*;
+    use crate::score::{BeamValue, FontWeight};
+
+    #[test]
+    fn direction_words_ok() {
+        let xml = r#"
+            <direction placement="below">
+                <direction-type>
+                    <words default-y="-40" relative-x="5.5">dolce</words>
+                    <words font-weight="bold">e legato</words>
+                </direction-type>
+                <direction-type>
+                    <damp />
+                </direction-type>
+                <offset>-2</offset>
+                <staff>2</staff>
+            </direction>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let direction = Direction::from_node(&node);
+        assert!(direction.is_ok());
+        let direction = direction.unwrap();
+        assert_eq!(direction.placement, Some(AboveBelow::Below));
+        assert_eq!(
+            direction.offset,
+            Some(Offset {
+                value: -2.,
+                sound: None,
+                unknown: Unknown::default()
+            })
+        );
+        assert_eq!(direction.playback_offset(), 0.);
+        assert_eq!(direction.staff, Some(2));
+        assert_eq!(
+            direction.direction_types,
+            vec![
+                DirectionType::Words(vec![
+                    WordsOrSymbol::Words(Words {
+                        text: "dolce".to_owned(),
+                        color: None,
+                        font: Font::default(),
+                        position: Position {
+                            default_y: Some(Tenths(-40.)),
+                            relative_x: Some(Tenths(5.5)),
+                            ..Default::default()
+                        },
+                        unknown: Unknown::default()
+                    }),
+                    WordsOrSymbol::Words(Words {
+                        text: "e legato".to_owned(),
+                        color: None,
+                        font: Font {
+                            weight: Some(FontWeight::Bold),
+                            ..Default::default()
+                        },
+                        position: Position::default(),
+                        unknown: Unknown::default()
+                    })
+                ]),
+                DirectionType::Damp(Sign::default())
+            ]
+        );
+        assert_eq!(direction.metronome(), None);
+        assert_eq!(direction.to_node(), Element::from(&node));
+    }
+
+    #[test]
+    fn direction_symbols_ok() {
+        let xml = r##"
+            <direction>
+                <direction-type>
+                    <words>to</words>
+                    <symbol default-y="20" font-size="16">coda</symbol>
+                </direction-type>
+                <direction-type>
+                    <segno default-x="-10" color="#FF0000" smufl="segnoSerpent1" />
+                </direction-type>
+                <direction-type>
+                    <damp relative-y="5" />
+                </direction-type>
+            </direction>"##;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let direction = Direction::from_node(&node).unwrap();
+        match &direction.direction_types[0] {
+            DirectionType::Words(items) => assert_eq!(
+                items[1],
+                WordsOrSymbol::Symbol(Symbol {
+                    glyph: "coda".to_owned(),
+                    print_style: PrintStyle {
+                        position: Position {
+                            default_y: Some(Tenths(20.)),
+                            ..Default::default()
+                        },
+                        font: Font {
+                            size: Some("16".parse().unwrap()),
+                            ..Default::default()
+                        },
+                        color: None
+                    },
+                    unknown: Unknown::default()
+                })
+            ),
+            _ => panic!("words expected"),
+        }
+        match &direction.direction_types[1] {
+            DirectionType::Segno(signs) => {
+                assert_eq!(signs[0].smufl.as_deref(), Some("segnoSerpent1"));
+                assert_eq!(signs[0].print_style.position.default_x, Some(Tenths(-10.)));
+            }
+            _ => panic!("segno expected"),
+        }
+        assert_eq!(direction.to_node(), Element::from(&node));
+    }
+
+    #[test]
+    fn metronome_modulation_ok() {
+        let xml = r#"
+            <metronome>
+                <beat-unit>half</beat-unit>
+                <beat-unit-tied>
+                    <beat-unit>eighth</beat-unit>
+                </beat-unit-tied>
+                <beat-unit>quarter</beat-unit>
+                <beat-unit-dot />
+            </metronome>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let metronome = Metronome::from_node(&node).unwrap();
+        match &metronome.kind {
+            MetronomeKind::BeatUnits(unit, other) => {
+                assert_eq!(unit.quarters(), 2.5);
+                assert_eq!(other.quarters(), 1.5);
+            }
+            _ => panic!("expected a metric modulation"),
+        }
+        assert_eq!(metronome.modulation_ratio(), Some(0.6));
+        assert_eq!(metronome.quarters_per_minute(), None);
+        assert_eq!(metronome.to_node(), Element::from(&node));
+    }
+
+    #[test]
+    fn metronome_notes_ok() {
+        let xml = r#"
+            <metronome>
+                <metronome-note>
+                    <metronome-type>eighth</metronome-type>
+                    <metronome-beam number="1">begin</metronome-beam>
+                </metronome-note>
+                <metronome-note>
+                    <metronome-type>eighth</metronome-type>
+                    <metronome-beam number="1">end</metronome-beam>
+                </metronome-note>
+                <metronome-relation>equals</metronome-relation>
+                <metronome-note>
+                    <metronome-type>quarter</metronome-type>
+                    <metronome-tied type="start" />
+                </metronome-note>
+            </metronome>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let metronome = Metronome::from_node(&node).unwrap();
+        match &metronome.kind {
+            MetronomeKind::Notes {
+                notes,
+                relation,
+                related,
+            } => {
+                assert_eq!(notes.len(), 2);
+                assert_eq!(notes[1].beams[0].value, BeamValue::End);
+                assert_eq!(relation.as_deref(), Some("equals"));
+                assert_eq!(related[0].tied, Some(StartStop::Start));
+            }
+            _ => panic!("expected related metronome notes"),
+        }
+        assert_eq!(metronome.to_node(), Element::from(&node));
+    }
+
+    #[test]
+    fn direction_dynamics_ok() {
+        let xml = r#"
+            <direction placement="below">
+                <direction-type>
+                    <dynamics>
+                        <mf />
+                    </dynamics>
+                </direction-type>
+                <sound dynamics="88.89" />
+            </direction>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let direction = Direction::from_node(&node).unwrap();
+        assert_eq!(
+            direction.direction_types,
+            vec![DirectionType::Dynamics(vec![Dynamics {
+                placement: None,
+                color: None,
+                marks: vec![Dynamic::Mf],
+                other_dynamics: None,
+                position: Position::default(),
+                unknown: Unknown::default()
+            }])]
+        );
+        assert_eq!(Dynamic::Mf.velocity(), Some(80));
+        assert_eq!(Dynamic::Sfz.velocity(), None);
+    }
+
+    #[test]
+    fn direction_wedge_ok() {
+        let xml = r#"
+            <direction placement="below">
+                <direction-type>
+                    <wedge type="diminuendo" number="2" spread="15" />
+                </direction-type>
+            </direction>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let direction = Direction::from_node(&node).unwrap();
+        assert_eq!(
+            direction.direction_types,
+            vec![DirectionType::Wedge(Wedge {
+                wedge_type: WedgeType::Diminuendo,
+                number: Some(NumberLevel(2)),
+                spread: Some(Tenths(15.)),
+                color: None,
+                position: Position::default(),
+                unknown: Unknown::default()
+            })]
+        );
+    }
+
+    #[test]
+    fn direction_rehearsal_ok() {
+        let xml = r#"
+            <direction placement="above">
+                <direction-type>
+                    <rehearsal enclosure="square" font-weight="bold">B</rehearsal>
+                </direction-type>
+            </direction>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let direction = Direction::from_node(&node).unwrap();
+        assert_eq!(
+            direction.rehearsals().collect::<Vec<_>>(),
+            vec![&Rehearsal {
+                text: "B".to_owned(),
+                enclosure: Some(EnclosureShape::Square),
+                unknown: Unknown {
+                    attrs: vec![("font-weight".to_owned(), "bold".to_owned())],
+                    children: vec![],
+                }
+            }]
+        );
+    }
+
+    #[test]
+    fn direction_navigation_ok() {
+        let xml = r#"
+            <direction placement="above">
+                <direction-type>
+                    <words>D.S. al Coda</words>
+                </direction-type>
+                <sound dalsegno="segno1" />
+            </direction>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let direction = Direction::from_node(&node).unwrap();
+        assert_eq!(
+            direction.navigation(),
+            vec![Navigation::DalSegno("segno1".to_owned())]
+        );
+
+        let xml = r#"
+            <direction>
+                <direction-type>
+                    <segno />
+                </direction-type>
+            </direction>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let direction = Direction::from_node(&node).unwrap();
+        assert_eq!(
+            direction.direction_types,
+            vec![DirectionType::Segno(vec![Sign::default()])]
+        );
+        assert_eq!(
+            direction.navigation(),
+            vec![Navigation::Segno(String::new())]
+        );
+
+        let xml = r#"
+            <direction>
+                <direction-type>
+                    <words>Fine</words>
+                </direction-type>
+            </direction>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let direction = Direction::from_node(&node).unwrap();
+        assert_eq!(direction.navigation(), vec![Navigation::Fine]);
+    }
+
+    #[test]
+    fn sound_navigation_ok() {
+        let xml = r#"
+            <sound tempo="90" dynamics="80" damper-pedal="yes" coda="coda" tocoda="coda">
+                <offset>2</offset>
+            </sound>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let sound = Sound::from_node(&node).unwrap();
+        assert_eq!(sound.tempo, Some(90.));
+        assert_eq!(sound.velocity(), Some(72));
+        assert_eq!(sound.damper_pedal.as_deref(), Some("yes"));
+        assert_eq!(sound.offset.as_ref().map(|o| o.value), Some(2.));
+        assert_eq!(
+            sound.navigation(),
+            vec![
+                Navigation::Coda("coda".to_owned()),
+                Navigation::ToCoda("coda".to_owned())
+            ]
+        );
+    }
+
+    #[test]
+    fn direction_pedal_ok() {
+        let xml = r#"
+            <direction placement="below">
+                <direction-type>
+                    <pedal type="change" line="yes" sign="no" />
+                </direction-type>
+                <staff>2</staff>
+            </direction>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let direction = Direction::from_node(&node).unwrap();
+        let pedal = direction.pedal().unwrap();
+        assert_eq!(
+            pedal,
+            &Pedal {
+                pedal_type: PedalType::Change,
+                number: None,
+                line: Some(YesNo::Yes),
+                sign: Some(YesNo::No),
+                abbreviated: None,
+                unknown: Unknown::default()
+            }
+        );
+        assert!(pedal.pedal_type.is_down());
+        assert!(!PedalType::Stop.is_down());
+    }
+
+    #[test]
+    fn direction_octave_shift_ok() {
+        let xml = r#"
+            <direction placement="above">
+                <direction-type>
+                    <octave-shift type="down" size="15" number="1" />
+                </direction-type>
+            </direction>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let direction = Direction::from_node(&node).unwrap();
+        let shift = direction.octave_shift().unwrap();
+        assert_eq!(shift.shift_type, UpDownStopContinue::Down);
+        assert_eq!(shift.octaves(), 2);
+
+        let pitch = Pitch {
+            step: Step::C,
+            alter: 0.,
+            octave: 5,
+            unknown: Unknown::default(),
+        };
+        assert_eq!(shift.apply(&pitch).unwrap().octave, 7);
+
+        let vb = OctaveShift {
+            shift_type: UpDownStopContinue::Up,
+            number: None,
+            size: None,
+            unknown: Unknown::default(),
+        };
+        assert_eq!(vb.apply(&pitch).unwrap().octave, 4);
+        assert_eq!(
+            vb.to_node(),
+            Element::new("octave-shift").with_attr("type", "up")
+        );
+
+        let low = Pitch { octave: 0, ..pitch };
+        assert_eq!(vb.apply(&low), None);
+
+        let huge = OctaveShift {
+            size: Some(255),
+            ..vb
+        };
+        assert_eq!(huge.octaves(), -36);
+        assert_eq!(huge.apply(&low), None);
+    }
+
+    #[test]
+    fn direction_without_type_err() {
+        let xml = r#"
+            <direction>
+                <staff>1</staff>
+            </direction>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let direction = Direction::from_node(&node);
+        assert!(matches!(direction, Err(NodeNotFound { tag, .. }) if tag == "direction-type"));
+    }
+
+    #[test]
+    fn direction_dashes_bracket_ok() {
+        let xml = r#"
+            <direction>
+                <direction-type>
+                    <words>cresc.</words>
+                </direction-type>
+                <direction-type>
+                    <dashes type="start" number="1" />
+                </direction-type>
+            </direction>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let direction = Direction::from_node(&node).unwrap();
+        assert_eq!(
+            direction.dashes(),
+            Some(&Dashes {
+                dashes_type: StartStopContinue::Start,
+                number: Some(NumberLevel(1)),
+                unknown: Unknown::default()
+            })
+        );
+
+        let xml = r#"
+            <direction>
+                <direction-type>
+                    <bracket type="stop" line-end="down" end-length="15" line-type="dashed" />
+                </direction-type>
+            </direction>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let direction = Direction::from_node(&node).unwrap();
+        assert_eq!(
+            direction.bracket(),
+            Some(&Bracket {
+                bracket_type: StartStopContinue::Stop,
+                number: None,
+                line_end: LineEnd::Down,
+                end_length: Some(Tenths(15.)),
+                line_type: Some(LineType::Dashed),
+                unknown: Unknown::default()
+            })
+        );
+        assert_eq!(
+            direction.direction_types[0].to_node().children[0],
+            Element::new("bracket")
+                .with_attr("type", "stop")
+                .with_attr("line-end", "down")
+                .with_attr("end-length", 15)
+                .with_attr("line-type", "dashed")
+        );
+    }
+
+    #[test]
+    fn direction_specialized_types_ok() {
+        let xml = r#"
+            <direction>
+                <direction-type>
+                    <harp-pedals>
+                        <pedal-tuning>
+                            <pedal-step>D</pedal-step>
+                            <pedal-alter>-1</pedal-alter>
+                        </pedal-tuning>
+                    </harp-pedals>
+                </direction-type>
+                <direction-type>
+                    <accordion-registration>
+                        <accordion-high />
+                        <accordion-middle>2</accordion-middle>
+                    </accordion-registration>
+                </direction-type>
+                <direction-type>
+                    <string-mute type="on" />
+                </direction-type>
+                <direction-type>
+                    <principal-voice type="start" symbol="Hauptstimme" />
+                </direction-type>
+                <direction-type>
+                    <other-direction>col legno</other-direction>
+                </direction-type>
+                <direction-type>
+                    <staff-divide type="down" />
+                </direction-type>
+            </direction>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let direction = Direction::from_node(&node).unwrap();
+        assert_eq!(
+            direction.direction_types,
+            vec![
+                DirectionType::HarpPedals(vec![PedalTuning {
+                    step: Step::D,
+                    alter: -1.,
+                    unknown: Unknown::default()
+                }]),
+                DirectionType::AccordionRegistration(AccordionRegistration {
+                    high: true,
+                    middle: Some(2),
+                    low: false,
+                    unknown: Unknown::default()
+                }),
+                DirectionType::StringMute(OnOff::On),
+                DirectionType::PrincipalVoice(PrincipalVoice {
+                    principal_voice_type: StartStop::Start,
+                    symbol: PrincipalVoiceSymbol::Hauptstimme,
+                    text: None,
+                    unknown: Unknown::default()
+                }),
+                DirectionType::OtherDirection(Some("col legno".to_owned())),
+                DirectionType::Other(vec![Element::new("staff-divide").with_attr("type", "down")])
+            ]
+        );
+    }
+
+    #[test]
+    fn direction_percussion_ok() {
+        let xml = r#"
+            <direction>
+                <direction-type>
+                    <percussion>
+                        <metal>triangle</metal>
+                    </percussion>
+                    <percussion>
+                        <stick tip="down">
+                            <stick-type>snare stick</stick-type>
+                            <stick-material>hard</stick-material>
+                        </stick>
+                    </percussion>
+                </direction-type>
+            </direction>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let direction = Direction::from_node(&node).unwrap();
+        assert_eq!(
+            direction.direction_types,
+            vec![DirectionType::Percussion(vec![
+                Percussion {
+                    kind: PercussionKind::Metal("triangle".to_owned()),
+                    unknown: Unknown::default()
+                },
+                Percussion {
+                    kind: PercussionKind::Stick {
+                        stick_type: "snare stick".to_owned(),
+                        stick_material: "hard".to_owned(),
+                        tip: Some("down".to_owned())
+                    },
+                    unknown: Unknown::default()
+                }
+            ])]
+        );
+        assert_eq!(
+            direction.direction_types[0].to_node().children[1].children[0],
+            Element::new("stick")
+                .with_attr("tip", "down")
+                .with_chd_text("stick-type", "snare stick")
+                .with_chd_text("stick-material", "hard")
+        );
+    }
+
+    #[test]
+    fn direction_metronome_percussion_unknown_ok() {
+        let xml = r#"
+            <direction>
+                <direction-type>
+                    <metronome parentheses="yes" default-y="40">
+                        <beat-unit>quarter</beat-unit>
+                        <per-minute>96</per-minute>
+                    </metronome>
+                </direction-type>
+                <direction-type>
+                    <percussion enclosure="circle">
+                        <timpani />
+                    </percussion>
+                    <percussion>
+                        <other-percussion>guiro</other-percussion>
+                    </percussion>
+                    <percussion>
+                        <future-pictogram>x</future-pictogram>
+                    </percussion>
+                </direction-type>
+            </direction>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let direction = Direction::from_node(&node).unwrap();
+        let metronome = direction.metronome().unwrap();
+        assert_eq!(
+            metronome.unknown.attrs,
+            vec![
+                ("parentheses".to_owned(), "yes".to_owned()),
+                ("default-y".to_owned(), "40".to_owned())
+            ]
+        );
+        assert_eq!(metronome.quarters_per_minute(), Some(96.));
+        match &direction.direction_types[1] {
+            DirectionType::Percussion(p) => {
+                assert_eq!(p[0].kind, PercussionKind::Timpani);
+                assert_eq!(
+                    p[1].kind,
+                    PercussionKind::OtherPercussion(Some("guiro".to_owned()))
+                );
+                assert_eq!(
+                    p[2].kind,
+                    PercussionKind::Other(Element::new("future-pictogram").with_text("x"))
+                );
+            }
+            _ => panic!("percussion expected"),
+        }
+        assert_eq!(direction.to_node(), Element::from(&node));
+    }
+
+    #[test]
+    fn direction_scordatura_ok() {
+        let xml = r#"
+            <direction>
+                <direction-type>
+                    <scordatura>
+                        <accord string="3">
+                            <tuning-step>C</tuning-step>
+                            <tuning-alter>1</tuning-alter>
+                            <tuning-octave>3</tuning-octave>
+                        </accord>
+                        <accord string="6">
+                            <tuning-step>D</tuning-step>
+                            <tuning-octave>2</tuning-octave>
+                        </accord>
+                    </scordatura>
+                </direction-type>
+            </direction>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let direction = Direction::from_node(&node).unwrap();
+        let accords = direction.scordatura().unwrap();
+        assert_eq!(accords.len(), 2);
+        assert_eq!(
+            accords[0].pitch(),
+            Pitch {
+                step: Step::C,
+                alter: 1.,
+                octave: 3,
+                unknown: Unknown::default()
+            }
+        );
+        assert_eq!(accords[1].string, 6);
+        assert_eq!(accords[1].pitch().step, Step::D);
+        assert_eq!(accords[1].pitch().to_jianpu(), 2);
+    }
+
+    #[test]
+    fn sound_play_ok() {
+        let xml = r#"
+            <sound>
+                <play id="P1-I1">
+                    <mute>harmon-no-stem</mute>
+                    <other-play type="flutter">fl.</other-play>
+                </play>
+            </sound>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let sound = Sound::from_node(&node).unwrap();
+        let play = sound.play.as_ref().unwrap();
+        assert_eq!(play.id.as_deref(), Some("P1-I1"));
+        assert_eq!(play.mute(), Some(Mute::HarmonNoStem));
+        assert_eq!(
+            play.items[1],
+            PlayItem::OtherPlay {
+                play_type: "flutter".to_owned(),
+                text: Some("fl.".to_owned())
+            }
+        );
+        assert_eq!(sound.to_node(), Element::from(&node));
+    }
+
+    #[test]
+    fn sound_swing_ok() {
+        let xml = r#"
+            <sound tempo="160">
+                <swing>
+                    <first>2</first>
+                    <second>1</second>
+                    <swing-type>eighth</swing-type>
+                </swing>
+            </sound>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let sound = Sound::from_node(&node).unwrap();
+        let swing = sound.swing.as_ref().unwrap();
+        assert_eq!(
+            swing.kind,
+            SwingKind::Ratio {
+                first: 2,
+                second: 1,
+                swing_type: Some(NoteTypeValue::Eighth),
+                swing_style: None
+            }
+        );
+
+        // offbeat eighths are delayed to the last triplet eighth
+        let positions: Vec<_> = [0., 3., 6., 9.]
+            .into_iter()
+            .map(|p| swing.swung_position(p, 6))
+            .collect();
+        assert_eq!(positions, vec![0., 4., 6., 10.]);
+        let straight = Swing {
+            kind: SwingKind::Straight,
+            unknown: Unknown::default(),
+        };
+        assert_eq!(straight.swung_position(3., 6), 3.);
+        assert_eq!(sound.to_node(), Element::from(&node));
+    }
+}
@@ -0,0 +1,661 @@
+use roxmltree::Node;
+
+use super::{
+    children_to_nodes, parse_attr, parse_chd, parse_chd_text, parse_children, parse_option_chd,
+    parse_optional_attr, parse_optional_chd_text, parse_text, AboveBelow, FromNode, Offset,
+    StartStop, Step, ToNode, Unknown, YesNo,
+};
+use crate::{error::Result, writer::Element};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Root {
+    pub root_step: Step,
+    pub root_alter: Option<f64>,
+    pub unknown: Unknown,
+}
+
+impl FromNode for Root {
+    fn tag() -> &'static str {
+        "root"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        Ok(Root {
+            root_step: parse_chd_text(node, "root-step")?,
+            root_alter: parse_optional_chd_text(node, "root-alter")?,
+            unknown: Unknown::parse(node, &[], &["root-step", "root-alter"]),
+        })
+    }
+}
+
+impl ToNode for Root {
+    fn to_node(&self) -> Element {
+        let node = Element::new(Self::tag())
+            .with_chd_text("root-step", self.root_step)
+            .with_optional_chd_text("root-alter", self.root_alter);
+        self.unknown.write(node)
+    }
+}
+
+xml_enum! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum NumeralMode {
+        Major => "major",
+        Minor => "minor",
+        NaturalMinor => "natural minor",
+        MelodicMinor => "melodic minor",
+        HarmonicMinor => "harmonic minor",
+    }
+}
+
+/// Key a roman numeral is relative to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NumeralKey {
+    pub numeral_fifths: i8,
+    pub numeral_mode: NumeralMode,
+    pub unknown: Unknown,
+}
+
+impl FromNode for NumeralKey {
+    fn tag() -> &'static str {
+        "numeral-key"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        Ok(NumeralKey {
+            numeral_fifths: parse_chd_text(node, "numeral-fifths")?,
+            numeral_mode: parse_chd_text(node, "numeral-mode")?,
+            unknown: Unknown::parse(node, &[], &["numeral-fifths", "numeral-mode"]),
+        })
+    }
+}
+
+impl ToNode for NumeralKey {
+    fn to_node(&self) -> Element {
+        let node = Element::new(Self::tag())
+            .with_chd_text("numeral-fifths", self.numeral_fifths)
+            .with_chd_text("numeral-mode", self.numeral_mode);
+        self.unknown.write(node)
+    }
+}
+
+/// Roman numeral of a harmony, added in MusicXML 4.0
+#[derive(Debug, Clone, PartialEq)]
+pub struct Numeral {
+    /// Scale degree from 1 to 7
+    pub numeral_root: u8,
+    /// Displayed numeral, e.g. "ii"
+    pub numeral_root_text: Option<String>,
+    pub numeral_alter: Option<f64>,
+    pub numeral_key: Option<NumeralKey>,
+    pub unknown: Unknown,
+}
+
+impl FromNode for Numeral {
+    fn tag() -> &'static str {
+        "numeral"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        let root = node
+            .children()
+            .find(|c| c.tag_name().name() == "numeral-root");
+
+        Ok(Numeral {
+            numeral_root: parse_chd_text(node, "numeral-root")?,
+            numeral_root_text: root
+                .map(|r| parse_optional_attr(&r, "text"))
+                .transpose()?
+                .flatten(),
+            numeral_alter: parse_optional_chd_text(node, "numeral-alter")?,
+            numeral_key: parse_option_chd(node)?,
+            unknown: Unknown::parse(node, &[], &["numeral-root", "numeral-alter", "numeral-key"]),
+        })
+    }
+}
+
+impl ToNode for Numeral {
+    fn to_node(&self) -> Element {
+        let node = Element::new(Self::tag())
+            .with_child(
+                Element::new("numeral-root")
+                    .with_optional_attr("text", self.numeral_root_text.as_ref())
+                    .with_text(self.numeral_root),
+            )
+            .with_optional_chd_text("numeral-alter", self.numeral_alter)
+            .with_optional_child(self.numeral_key.as_ref().map(ToNode::to_node));
+        self.unknown.write(node)
+    }
+}
+
+const ROMAN_NUMERALS: [&str; 7] = ["I", "II", "III", "IV", "V", "VI", "VII"];
+
+impl Numeral {
+    /// Roman numeral text, preferring the displayed text
+    pub fn roman(&self) -> String {
+        if let Some(text) = &self.numeral_root_text {
+            return text.clone();
+        }
+
+        let numeral = ROMAN_NUMERALS
+            .get((self.numeral_root as usize).wrapping_sub(1))
+            .copied()
+            .unwrap_or_default();
+        format!("{}{numeral}", accidental(self.numeral_alter))
+    }
+}
+
+xml_enum! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum KindValue {
+        Major => "major",
+        Minor => "minor",
+        Augmented => "augmented",
+        Diminished => "diminished",
+        Dominant => "dominant",
+        MajorSeventh => "major-seventh",
+        MinorSeventh => "minor-seventh",
+        DiminishedSeventh => "diminished-seventh",
+        AugmentedSeventh => "augmented-seventh",
+        HalfDiminished => "half-diminished",
+        MajorMinor => "major-minor",
+        MajorSixth => "major-sixth",
+        MinorSixth => "minor-sixth",
+        DominantNinth => "dominant-ninth",
+        MajorNinth => "major-ninth",
+        MinorNinth => "minor-ninth",
+        Dominant11th => "dominant-11th",
+        Major11th => "major-11th",
+        Minor11th => "minor-11th",
+        Dominant13th => "dominant-13th",
+        Major13th => "major-13th",
+        Minor13th => "minor-13th",
+        SuspendedSecond => "suspended-second",
+        SuspendedFourth => "suspended-fourth",
+        Neapolitan => "Neapolitan",
+        Italian => "Italian",
+        French => "French",
+        German => "German",
+        Pedal => "pedal",
+        Power => "power",
+        Tristan => "Tristan",
+        Other => "other",
+        None => "none",
+    }
+}
+
+impl KindValue {
+    /// Common chord symbol suffix, e.g. "m7" for a minor seventh chord
+    pub fn suffix(&self) -> &'static str {
+        match self {
+            KindValue::Major => "",
+            KindValue::Minor => "m",
+            KindValue::Augmented => "+",
+            KindValue::Diminished => "dim",
+            KindValue::Dominant => "7",
+            KindValue::MajorSeventh => "maj7",
+            KindValue::MinorSeventh => "m7",
+            KindValue::DiminishedSeventh => "dim7",
+            KindValue::AugmentedSeventh => "+7",
+            KindValue::HalfDiminished => "m7b5",
+            KindValue::MajorMinor => "m(maj7)",
+            KindValue::MajorSixth => "6",
+            KindValue::MinorSixth => "m6",
+            KindValue::DominantNinth => "9",
+            KindValue::MajorNinth => "maj9",
+            KindValue::MinorNinth => "m9",
+            KindValue::Dominant11th => "11",
+            KindValue::Major11th => "maj11",
+            KindValue::Minor11th => "m11",
+            KindValue::Dominant13th => "13",
+            KindValue::Major13th => "maj13",
+            KindValue::Minor13th => "m13",
+            KindValue::SuspendedSecond => "sus2",
+            KindValue::SuspendedFourth => "sus4",
+            KindValue::Power => "5",
+            _ => "",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Kind {
+    pub value: KindValue,
+    /// Text displayed instead of the default rendering of the kind
+    pub text: Option<String>,
+    pub use_symbols: Option<YesNo>,
+    pub unknown: Unknown,
+}
+
+impl FromNode for Kind {
+    fn tag() -> &'static str {
+        "kind"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        Ok(Kind {
+            value: parse_text(node, Self::tag())?,
+            text: parse_optional_attr(node, "text")?,
+            use_symbols: parse_optional_attr(node, "use-symbols")?,
+            unknown: Unknown::parse_attrs(node, &["text", "use-symbols"]),
+        })
+    }
+}
+
+impl ToNode for Kind {
+    fn to_node(&self) -> Element {
+        let node = Element::new(Self::tag())
+            .with_optional_attr("text", self.text.as_ref())
+            .with_optional_attr("use-symbols", self.use_symbols)
+            .with_text(self.value);
+        self.unknown.write(node)
+    }
+}
+
+/// Bass note of a slash chord
+#[derive(Debug, Clone, PartialEq)]
+pub struct Bass {
+    pub bass_separator: Option<String>,
+    pub bass_step: Step,
+    pub bass_alter: Option<f64>,
+    pub unknown: Unknown,
+}
+
+impl FromNode for Bass {
+    fn tag() -> &'static str {
+        "bass"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        Ok(Bass {
+            bass_separator: parse_optional_chd_text(node, "bass-separator")?,
+            bass_step: parse_chd_text(node, "bass-step")?,
+            bass_alter: parse_optional_chd_text(node, "bass-alter")?,
+            unknown: Unknown::parse(node, &[], &["bass-separator", "bass-step", "bass-alter"]),
+        })
+    }
+}
+
+impl ToNode for Bass {
+    fn to_node(&self) -> Element {
+        let node = Element::new(Self::tag())
+            .with_optional_chd_text("bass-separator", self.bass_separator.as_ref())
+            .with_chd_text("bass-step", self.bass_step)
+            .with_optional_chd_text("bass-alter", self.bass_alter);
+        self.unknown.write(node)
+    }
+}
+
+xml_enum! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum DegreeTypeValue {
+        Add => "add",
+        Alter => "alter",
+        Subtract => "subtract",
+    }
+}
+
+/// Added, altered or subtracted chord degree, e.g. the b9 of C7(b9)
+#[derive(Debug, Clone, PartialEq)]
+pub struct Degree {
+    pub degree_value: u8,
+    pub degree_alter: f64,
+    pub degree_type: DegreeTypeValue,
+    pub unknown: Unknown,
+}
+
+impl FromNode for Degree {
+    fn tag() -> &'static str {
+        "degree"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        Ok(Degree {
+            degree_value: parse_chd_text(node, "degree-value")?,
+            degree_alter: parse_chd_text(node, "degree-alter")?,
+            degree_type: parse_chd_text(node, "degree-type")?,
+            unknown: Unknown::parse(node, &[], &["degree-value", "degree-alter", "degree-type"]),
+        })
+    }
+}
+
+impl ToNode for Degree {
+    fn to_node(&self) -> Element {
+        let node = Element::new(Self::tag())
+            .with_chd_text("degree-value", self.degree_value)
+            .with_chd_text("degree-alter", self.degree_alter)
+            .with_chd_text("degree-type", self.degree_type);
+        self.unknown.write(node)
+    }
+}
+
+/// Dot of a chord diagram, strings numbered from the highest pitched
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FrameNote {
+    pub string: u8,
+    /// 0 for an open string
+    pub fret: u8,
+    pub fingering: Option<String>,
+    pub barre: Option<StartStop>,
+    pub unknown: Unknown,
+}
+
+impl FromNode for FrameNote {
+    fn tag() -> &'static str {
+        "frame-note"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        let barre = node.children().find(|c| c.tag_name().name() == "barre");
+
+        Ok(FrameNote {
+            string: parse_chd_text(node, "string")?,
+            fret: parse_chd_text(node, "fret")?,
+            fingering: parse_optional_chd_text(node, "fingering")?,
+            barre: barre.map(|b| parse_attr(&b, "type")).transpose()?,
+            unknown: Unknown::parse(node, &[], &["string", "fret", "fingering", "barre"]),
+        })
+    }
+}
+
+impl ToNode for FrameNote {
+    fn to_node(&self) -> Element {
+        let node = Element::new(Self::tag())
+            .with_chd_text("string", self.string)
+            .with_chd_text("fret", self.fret)
+            .with_optional_chd_text("fingering", self.fingering.as_ref())
+            .with_optional_child(
+                self.barre
+                    .map(|b| Element::new("barre").with_attr("type", b)),
+            );
+        self.unknown.write(node)
+    }
+}
+
+/// Chord diagram of a fretted instrument
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame {
+    pub frame_strings: u8,
+    pub frame_frets: u8,
+    /// Fret shown at the top of the diagram, 1 if absent
+    pub first_fret: Option<u8>,
+    pub frame_notes: Vec<FrameNote>,
+    pub unknown: Unknown,
+}
+
+impl FromNode for Frame {
+    fn tag() -> &'static str {
+        "frame"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        Ok(Frame {
+            frame_strings: parse_chd_text(node, "frame-strings")?,
+            frame_frets: parse_chd_text(node, "frame-frets")?,
+            first_fret: parse_optional_chd_text(node, "first-fret")?,
+            frame_notes: parse_children(node)?,
+            unknown: Unknown::parse(
+                node,
+                &[],
+                &["frame-strings", "frame-frets", "first-fret", "frame-note"],
+            ),
+        })
+    }
+}
+
+impl ToNode for Frame {
+    fn to_node(&self) -> Element {
+        let node = Element::new(Self::tag())
+            .with_chd_text("frame-strings", self.frame_strings)
+            .with_chd_text("frame-frets", self.frame_frets)
+            .with_optional_chd_text("first-fret", self.first_fret)
+            .with_children(children_to_nodes(&self.frame_notes));
+        self.unknown.write(node)
+    }
+}
+
+impl Frame {
+    /// Fret played on the given string, None if the string is not played
+    pub fn fret(&self, string: u8) -> Option<u8> {
+        self.frame_notes
+            .iter()
+            .find(|n| n.string == string)
+            .map(|n| n.fret)
+    }
+}
+
+/// Chord symbol
+#[derive(Debug, Clone, PartialEq)]
+pub struct Harmony {
+    pub placement: Option<AboveBelow>,
+    pub root: Option<Root>,
+    /// Roman numeral analysis used instead of a root
+    pub numeral: Option<Numeral>,
+    pub kind: Kind,
+    /// 0 for root position, 1 for first inversion and so on
+    pub inversion: Option<u8>,
+    pub bass: Option<Bass>,
+    pub degree: Vec<Degree>,
+    pub frame: Option<Frame>,
+    /// Offset from the current position in divisions
+    pub offset: Option<Offset>,
+    pub staff: Option<u8>,
+    pub unknown: Unknown,
+}
+
+impl FromNode for Harmony {
+    fn tag() -> &'static str {
+        "harmony"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        Ok(Harmony {
+            placement: parse_optional_attr(node, "placement")?,
+            root: parse_option_chd(node)?,
+            numeral: parse_option_chd(node)?,
+            kind: parse_chd(node)?,
+            inversion: parse_optional_chd_text(node, "inversion")?,
+            bass: parse_option_chd(node)?,
+            degree: parse_children(node)?,
+            frame: parse_option_chd(node)?,
+            offset: parse_option_chd(node)?,
+            staff: parse_optional_chd_text(node, "staff")?,
+            unknown: Unknown::parse(
+                node,
+                &["placement"],
+                &[
+                    "root",
+                    "numeral",
+                    "kind",
+                    "inversion",
+                    "bass",
+                    "degree",
+                    "frame",
+                    "offset",
+                    "staff",
+                ],
+            ),
+        })
+    }
+}
+
+impl ToNode for Harmony {
+    fn to_node(&self) -> Element {
+        let node = Element::new(Self::tag())
+            .with_optional_attr("placement", self.placement)
+            .with_optional_child(self.root.as_ref().map(ToNode::to_node))
+            .with_optional_child(self.numeral.as_ref().map(ToNode::to_node))
+            .with_child(self.kind.to_node())
+            .with_optional_chd_text("inversion", self.inversion)
+            .with_optional_child(self.bass.as_ref().map(ToNode::to_node))
+            .with_children(children_to_nodes(&self.degree))
+            .with_optional_child(self.frame.as_ref().map(ToNode::to_node))
+            .with_optional_child(self.offset.as_ref().map(ToNode::to_node))
+            .with_optional_chd_text("staff", self.staff);
+        self.unknown.write(node)
+    }
+}
+
+fn accidental(alter: Option<f64>) -> &'static str {
+    match alter {
+        Some(a) if a >= 1. => "#",
+        Some(a) if a <= -1. => "b",
+        _ => "",
+    }
+}
+
+impl Harmony {
+    /// Chord symbol text like "F#m7/C#", preferring the text given on the kind
+    pub fn symbol(&self) -> String {
+        let mut symbol = String::new();
+        if let Some(root) = &self.root {
+            symbol.push_str(&root.root_step.to_string());
+            symbol.push_str(accidental(root.root_alter));
+        }
+        match &self.kind.text {
+            Some(text) => symbol.push_str(text),
+            None => symbol.push_str(self.kind.value.suffix()),
+        }
+        if let Some(bass) = &self.bass {
+            symbol.push('/');
+            symbol.push_str(&bass.bass_step.to_string());
+            symbol.push_str(accidental(bass.bass_alter));
+        }
+
+        symbol
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use roxmltree::Document;
+
+    use super::*;
+
+    #[test]
+    fn harmony_ok() {
+        let xml = r#"
+            <harmony placement="above">
+                <root>
+                    <root-step>F</root-step>
+                    <root-alter>1</root-alter>
+                </root>
+                <kind>minor-seventh</kind>
+                <bass>
+                    <bass-step>C</bass-step>
+                    <bass-alter>1</bass-alter>
+                </bass>
+            </harmony>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let harmony = Harmony::from_node(&node);
+        assert!(harmony.is_ok());
+        let harmony = harmony.unwrap();
+        assert_eq!(harmony.placement, Some(AboveBelow::Above));
+        assert_eq!(harmony.kind.value, KindValue::MinorSeventh);
+        assert_eq!(harmony.symbol(), "F#m7/C#");
+    }
+
+    #[test]
+    fn harmony_degree_ok() {
+        let xml = r#"
+            <harmony>
+                <root>
+                    <root-step>B</root-step>
+                    <root-alter>-1</root-alter>
+                </root>
+                <kind text="7">dominant</kind>
+                <inversion>1</inversion>
+                <degree>
+                    <degree-value>9</degree-value>
+                    <degree-alter>-1</degree-alter>
+                    <degree-type>add</degree-type>
+                </degree>
+                <staff>1</staff>
+            </harmony>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let harmony = Harmony::from_node(&node).unwrap();
+        assert_eq!(harmony.inversion, Some(1));
+        assert_eq!(
+            harmony.degree,
+            vec![Degree {
+                degree_value: 9,
+                degree_alter: -1.,
+                degree_type: DegreeTypeValue::Add,
+                unknown: Unknown::default()
+            }]
+        );
+        assert_eq!(harmony.staff, Some(1));
+        assert_eq!(harmony.symbol(), "Bb7");
+    }
+
+    #[test]
+    fn harmony_numeral_ok() {
+        let xml = r#"
+            <harmony>
+                <numeral>
+                    <numeral-root>7</numeral-root>
+                    <numeral-alter>-1</numeral-alter>
+                    <numeral-key>
+                        <numeral-fifths>-3</numeral-fifths>
+                        <numeral-mode>harmonic minor</numeral-mode>
+                    </numeral-key>
+                </numeral>
+                <kind>major</kind>
+            </harmony>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let harmony = Harmony::from_node(&node).unwrap();
+        assert_eq!(harmony.root, None);
+        let numeral = harmony.numeral.as_ref().unwrap();
+        assert_eq!(
+            numeral.numeral_key,
+            Some(NumeralKey {
+                numeral_fifths: -3,
+                numeral_mode: NumeralMode::HarmonicMinor,
+                unknown: Unknown::default()
+            })
+        );
+        assert_eq!(numeral.roman(), "bVII");
+        assert_eq!(harmony.to_node(), Element::from(&node));
+
+        let xml = r#"<numeral><numeral-root text="ii">2</numeral-root></numeral>"#;
+        let doc = Document::parse(xml).unwrap();
+        let numeral = Numeral::from_node(&doc.root_element()).unwrap();
+        assert_eq!(numeral.roman(), "ii");
+    }
+
+    #[test]
+    fn harmony_frame_ok() {
+        let xml = r#"
+            <harmony>
+                <root><root-step>F</root-step></root>
+                <kind>major</kind>
+                <frame>
+                    <frame-strings>6</frame-strings>
+                    <frame-frets>4</frame-frets>
+                    <frame-note>
+                        <string>6</string>
+                        <fret>1</fret>
+                        <barre type="start" />
+                    </frame-note>
+                    <frame-note>
+                        <string>5</string>
+                        <fret>3</fret>
+                        <fingering>3</fingering>
+                    </frame-note>
+                    <frame-note>
+                        <string>1</string>
+                        <fret>1</fret>
+                        <barre type="stop" />
+                    </frame-note>
+                </frame>
+            </harmony>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let harmony = Harmony::from_node(&node).unwrap();
+        let frame = harmony.frame.unwrap();
+        assert_eq!(frame.frame_strings, 6);
+        assert_eq!(frame.first_fret, None);
+        assert_eq!(frame.fret(5), Some(3));
+        assert_eq!(frame.fret(4), None);
+        assert_eq!(frame.frame_notes[0].barre, Some(StartStop::Start));
+        assert_eq!(frame.frame_notes[1].fingering.as_deref(), Some("3"));
+    }
+}
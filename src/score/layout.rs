@@ -0,0 +1,854 @@
+use roxmltree::Node;
+
+use super::{
+    children_to_nodes, count_chd, parse_attr, parse_chd_text, parse_children, parse_option_chd,
+    parse_optional_attr, parse_optional_chd_text, parse_text, Font, FromNode, Position, Tenths,
+    ToNode, Unknown, YesNo,
+};
+use crate::{
+    error::{Error::NodeNotFound, Result},
+    writer::Element,
+};
+
+xml_enum! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum MarginType {
+        Odd => "odd",
+        Even => "even",
+        Both => "both",
+    }
+}
+
+/// Page margins in tenths
+#[derive(Debug, Clone, PartialEq)]
+pub struct PageMargins {
+    /// Applies to both odd and even pages if absent
+    pub margin_type: Option<MarginType>,
+    pub left_margin: Tenths,
+    pub right_margin: Tenths,
+    pub top_margin: Tenths,
+    pub bottom_margin: Tenths,
+    pub unknown: Unknown,
+}
+
+impl FromNode for PageMargins {
+    fn tag() -> &'static str {
+        "page-margins"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        Ok(PageMargins {
+            margin_type: parse_optional_attr(node, "type")?,
+            left_margin: parse_chd_text(node, "left-margin")?,
+            right_margin: parse_chd_text(node, "right-margin")?,
+            top_margin: parse_chd_text(node, "top-margin")?,
+            bottom_margin: parse_chd_text(node, "bottom-margin")?,
+            unknown: Unknown::parse(
+                node,
+                &["type"],
+                &["left-margin", "right-margin", "top-margin", "bottom-margin"],
+            ),
+        })
+    }
+}
+
+impl ToNode for PageMargins {
+    fn to_node(&self) -> Element {
+        let node = Element::new(Self::tag())
+            .with_optional_attr("type", self.margin_type)
+            .with_chd_text("left-margin", self.left_margin)
+            .with_chd_text("right-margin", self.right_margin)
+            .with_chd_text("top-margin", self.top_margin)
+            .with_chd_text("bottom-margin", self.bottom_margin);
+        self.unknown.write(node)
+    }
+}
+
+/// Page size and margins in tenths
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PageLayout {
+    pub page_height: Option<Tenths>,
+    pub page_width: Option<Tenths>,
+    pub page_margins: Vec<PageMargins>,
+    pub unknown: Unknown,
+}
+
+impl FromNode for PageLayout {
+    fn tag() -> &'static str {
+        "page-layout"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        Ok(PageLayout {
+            page_height: parse_optional_chd_text(node, "page-height")?,
+            page_width: parse_optional_chd_text(node, "page-width")?,
+            page_margins: parse_children(node)?,
+            unknown: Unknown::parse(node, &[], &["page-height", "page-width", "page-margins"]),
+        })
+    }
+}
+
+impl ToNode for PageLayout {
+    fn to_node(&self) -> Element {
+        let node = Element::new(Self::tag())
+            .with_optional_chd_text("page-height", self.page_height)
+            .with_optional_chd_text("page-width", self.page_width)
+            .with_children(children_to_nodes(&self.page_margins));
+        self.unknown.write(node)
+    }
+}
+
+/// System margins in tenths, relative to the page margins
+#[derive(Debug, Clone, PartialEq)]
+pub struct SystemMargins {
+    pub left_margin: Tenths,
+    pub right_margin: Tenths,
+    pub unknown: Unknown,
+}
+
+impl FromNode for SystemMargins {
+    fn tag() -> &'static str {
+        "system-margins"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        Ok(SystemMargins {
+            left_margin: parse_chd_text(node, "left-margin")?,
+            right_margin: parse_chd_text(node, "right-margin")?,
+            unknown: Unknown::parse(node, &[], &["left-margin", "right-margin"]),
+        })
+    }
+}
+
+impl ToNode for SystemMargins {
+    fn to_node(&self) -> Element {
+        let node = Element::new(Self::tag())
+            .with_chd_text("left-margin", self.left_margin)
+            .with_chd_text("right-margin", self.right_margin);
+        self.unknown.write(node)
+    }
+}
+
+/// Divider drawn at the left or right of the page between systems
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Divider {
+    pub print_object: Option<YesNo>,
+    pub position: Position,
+    pub unknown: Unknown,
+}
+
+impl Divider {
+    fn parse(node: &Node) -> Result<Self> {
+        Ok(Divider {
+            print_object: parse_optional_attr(node, "print-object")?,
+            position: Position::parse(node)?,
+            unknown: Unknown::parse(
+                node,
+                &[
+                    "print-object",
+                    "default-x",
+                    "default-y",
+                    "relative-x",
+                    "relative-y",
+                ],
+                &[],
+            ),
+        })
+    }
+
+    fn to_node(&self, tag: &str) -> Element {
+        let node = Element::new(tag).with_optional_attr("print-object", self.print_object);
+        self.unknown.write(self.position.write_attrs(node))
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SystemDividers {
+    pub left_divider: Divider,
+    pub right_divider: Divider,
+    pub unknown: Unknown,
+}
+
+impl FromNode for SystemDividers {
+    fn tag() -> &'static str {
+        "system-dividers"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        let divider = |tag: &'static str| {
+            let c = node
+                .children()
+                .find(|c| c.tag_name().name() == tag)
+                .ok_or(NodeNotFound {
+                    tag,
+                    parent_tag: Self::tag().to_owned(),
+                })?;
+            Divider::parse(&c)
+        };
+
+        Ok(SystemDividers {
+            left_divider: divider("left-divider")?,
+            right_divider: divider("right-divider")?,
+            unknown: Unknown::parse(node, &[], &["left-divider", "right-divider"]),
+        })
+    }
+}
+
+impl ToNode for SystemDividers {
+    fn to_node(&self) -> Element {
+        let node = Element::new(Self::tag())
+            .with_child(self.left_divider.to_node("left-divider"))
+            .with_child(self.right_divider.to_node("right-divider"));
+        self.unknown.write(node)
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SystemLayout {
+    pub system_margins: Option<SystemMargins>,
+    /// Distance from the bottom of the previous system in tenths
+    pub system_distance: Option<Tenths>,
+    /// Distance of the first system from the top margin in tenths
+    pub top_system_distance: Option<Tenths>,
+    pub system_dividers: Option<SystemDividers>,
+    pub unknown: Unknown,
+}
+
+impl FromNode for SystemLayout {
+    fn tag() -> &'static str {
+        "system-layout"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        Ok(SystemLayout {
+            system_margins: parse_option_chd(node)?,
+            system_distance: parse_optional_chd_text(node, "system-distance")?,
+            top_system_distance: parse_optional_chd_text(node, "top-system-distance")?,
+            system_dividers: parse_option_chd(node)?,
+            unknown: Unknown::parse(
+                node,
+                &[],
+                &[
+                    "system-margins",
+                    "system-distance",
+                    "top-system-distance",
+                    "system-dividers",
+                ],
+            ),
+        })
+    }
+}
+
+impl ToNode for SystemLayout {
+    fn to_node(&self) -> Element {
+        let node = Element::new(Self::tag())
+            .with_optional_child(self.system_margins.as_ref().map(ToNode::to_node))
+            .with_optional_chd_text("system-distance", self.system_distance)
+            .with_optional_chd_text("top-system-distance", self.top_system_distance)
+            .with_optional_child(self.system_dividers.as_ref().map(ToNode::to_node));
+        self.unknown.write(node)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct StaffLayout {
+    pub number: u8,
+    /// Distance from the bottom of the previous staff in tenths
+    pub staff_distance: Option<Tenths>,
+    pub unknown: Unknown,
+}
+
+impl FromNode for StaffLayout {
+    fn tag() -> &'static str {
+        "staff-layout"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        Ok(StaffLayout {
+            number: parse_optional_attr(node, "number")?.unwrap_or(1),
+            staff_distance: parse_optional_chd_text(node, "staff-distance")?,
+            unknown: Unknown::parse(node, &["number"], &["staff-distance"]),
+        })
+    }
+}
+
+impl ToNode for StaffLayout {
+    fn to_node(&self) -> Element {
+        let node = Element::new(Self::tag())
+            .with_attr("number", self.number)
+            .with_optional_chd_text("staff-distance", self.staff_distance);
+        self.unknown.write(node)
+    }
+}
+
+xml_enum! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum MeasureNumberingValue {
+        None => "none",
+        Measure => "measure",
+        System => "system",
+    }
+}
+
+/// Display measure numbers on every measure, each system or not at all
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MeasureNumbering {
+    pub value: MeasureNumberingValue,
+    pub unknown: Unknown,
+}
+
+impl FromNode for MeasureNumbering {
+    fn tag() -> &'static str {
+        "measure-numbering"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        Ok(MeasureNumbering {
+            value: parse_text(node, Self::tag())?,
+            unknown: Unknown::parse_attrs(node, &[]),
+        })
+    }
+}
+
+impl ToNode for MeasureNumbering {
+    fn to_node(&self) -> Element {
+        let node = Element::new(Self::tag()).with_text(self.value);
+        self.unknown.write(node)
+    }
+}
+
+/// Layout changes and breaks starting from the measure
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Print {
+    pub new_system: Option<YesNo>,
+    pub new_page: Option<YesNo>,
+    /// Number of blank pages inserted before the measure
+    pub blank_page: Option<u32>,
+    pub page_number: Option<String>,
+    pub page_layout: Option<PageLayout>,
+    pub system_layout: Option<SystemLayout>,
+    pub staff_layout: Vec<StaffLayout>,
+    /// Horizontal distance from the previous measure in tenths
+    pub measure_distance: Option<Tenths>,
+    pub measure_numbering: Option<MeasureNumbering>,
+    pub unknown: Unknown,
+}
+
+impl FromNode for Print {
+    fn tag() -> &'static str {
+        "print"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        let measure_layout = node
+            .children()
+            .find(|c| c.tag_name().name() == "measure-layout");
+
+        Ok(Print {
+            new_system: parse_optional_attr(node, "new-system")?,
+            new_page: parse_optional_attr(node, "new-page")?,
+            blank_page: parse_optional_attr(node, "blank-page")?,
+            page_number: parse_optional_attr(node, "page-number")?,
+            page_layout: parse_option_chd(node)?,
+            system_layout: parse_option_chd(node)?,
+            staff_layout: parse_children(node)?,
+            measure_distance: match measure_layout {
+                Some(l) => parse_optional_chd_text(&l, "measure-distance")?,
+                None => None,
+            },
+            measure_numbering: parse_option_chd(node)?,
+            unknown: Unknown::parse(
+                node,
+                &["new-system", "new-page", "blank-page", "page-number"],
+                &[
+                    "page-layout",
+                    "system-layout",
+                    "staff-layout",
+                    "measure-layout",
+                    "measure-numbering",
+                ],
+            ),
+        })
+    }
+}
+
+impl ToNode for Print {
+    fn to_node(&self) -> Element {
+        let node = Element::new(Self::tag())
+            .with_optional_attr("new-system", self.new_system)
+            .with_optional_attr("new-page", self.new_page)
+            .with_optional_attr("blank-page", self.blank_page)
+            .with_optional_attr("page-number", self.page_number.as_ref())
+            .with_optional_child(self.page_layout.as_ref().map(ToNode::to_node))
+            .with_optional_child(self.system_layout.as_ref().map(ToNode::to_node))
+            .with_children(children_to_nodes(&self.staff_layout))
+            .with_optional_child(
+                self.measure_distance
+                    .map(|d| Element::new("measure-layout").with_chd_text("measure-distance", d)),
+            )
+            .with_optional_child(self.measure_numbering.as_ref().map(ToNode::to_node));
+        self.unknown.write(node)
+    }
+}
+
+impl Print {
+    /// Whether the measure starts a new system, including one on a new page
+    pub fn is_system_break(&self) -> bool {
+        self.new_system == Some(YesNo::Yes) || self.is_page_break()
+    }
+
+    pub fn is_page_break(&self) -> bool {
+        self.new_page == Some(YesNo::Yes)
+    }
+}
+
+/// Ratio of millimeters to tenths, tenths being a tenth of the staff interline space
+#[derive(Debug, Clone, PartialEq)]
+pub struct Scaling {
+    pub millimeters: f64,
+    pub tenths: f64,
+    pub unknown: Unknown,
+}
+
+impl FromNode for Scaling {
+    fn tag() -> &'static str {
+        "scaling"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        Ok(Scaling {
+            millimeters: parse_chd_text(node, "millimeters")?,
+            tenths: parse_chd_text(node, "tenths")?,
+            unknown: Unknown::parse(node, &[], &["millimeters", "tenths"]),
+        })
+    }
+}
+
+impl ToNode for Scaling {
+    fn to_node(&self) -> Element {
+        let node = Element::new(Self::tag())
+            .with_chd_text("millimeters", self.millimeters)
+            .with_chd_text("tenths", self.tenths);
+        self.unknown.write(node)
+    }
+}
+
+impl Scaling {
+    pub fn tenths_to_mm(&self, tenths: Tenths) -> f64 {
+        tenths.0 * self.millimeters / self.tenths
+    }
+}
+
+/// Width in tenths of a line type, e.g. "stem" or "light barline"
+#[derive(Debug, Clone, PartialEq)]
+pub struct LineWidth {
+    pub line_width_type: String,
+    pub value: Tenths,
+    pub unknown: Unknown,
+}
+
+impl FromNode for LineWidth {
+    fn tag() -> &'static str {
+        "line-width"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        Ok(LineWidth {
+            line_width_type: parse_attr(node, "type")?,
+            value: parse_text(node, Self::tag())?,
+            unknown: Unknown::parse_attrs(node, &["type"]),
+        })
+    }
+}
+
+impl ToNode for LineWidth {
+    fn to_node(&self) -> Element {
+        let node = Element::new(Self::tag())
+            .with_attr("type", &self.line_width_type)
+            .with_text(self.value);
+        self.unknown.write(node)
+    }
+}
+
+xml_enum! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum NoteSizeType {
+        Cue => "cue",
+        Grace => "grace",
+        GraceCue => "grace-cue",
+        Large => "large",
+    }
+}
+
+/// Size of a note type as a percentage of the regular note size
+#[derive(Debug, Clone, PartialEq)]
+pub struct NoteSize {
+    pub note_size_type: NoteSizeType,
+    pub value: f64,
+    pub unknown: Unknown,
+}
+
+impl FromNode for NoteSize {
+    fn tag() -> &'static str {
+        "note-size"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        Ok(NoteSize {
+            note_size_type: parse_attr(node, "type")?,
+            value: parse_text(node, Self::tag())?,
+            unknown: Unknown::parse_attrs(node, &["type"]),
+        })
+    }
+}
+
+impl ToNode for NoteSize {
+    fn to_node(&self) -> Element {
+        let node = Element::new(Self::tag())
+            .with_attr("type", self.note_size_type)
+            .with_text(self.value);
+        self.unknown.write(node)
+    }
+}
+
+/// Distance in tenths of a layout type, e.g. "hyphen" or "beam"
+#[derive(Debug, Clone, PartialEq)]
+pub struct Distance {
+    pub distance_type: String,
+    pub value: Tenths,
+    pub unknown: Unknown,
+}
+
+impl FromNode for Distance {
+    fn tag() -> &'static str {
+        "distance"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        Ok(Distance {
+            distance_type: parse_attr(node, "type")?,
+            value: parse_text(node, Self::tag())?,
+            unknown: Unknown::parse_attrs(node, &["type"]),
+        })
+    }
+}
+
+impl ToNode for Distance {
+    fn to_node(&self) -> Element {
+        let node = Element::new(Self::tag())
+            .with_attr("type", &self.distance_type)
+            .with_text(self.value);
+        self.unknown.write(node)
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Appearance {
+    pub line_widths: Vec<LineWidth>,
+    pub note_sizes: Vec<NoteSize>,
+    pub distances: Vec<Distance>,
+    pub unknown: Unknown,
+}
+
+impl FromNode for Appearance {
+    fn tag() -> &'static str {
+        "appearance"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        Ok(Appearance {
+            line_widths: parse_children(node)?,
+            note_sizes: parse_children(node)?,
+            distances: parse_children(node)?,
+            unknown: Unknown::parse(node, &[], &["line-width", "note-size", "distance"]),
+        })
+    }
+}
+
+impl ToNode for Appearance {
+    fn to_node(&self) -> Element {
+        let node = Element::new(Self::tag())
+            .with_children(children_to_nodes(&self.line_widths))
+            .with_children(children_to_nodes(&self.note_sizes))
+            .with_children(children_to_nodes(&self.distances));
+        self.unknown.write(node)
+    }
+}
+
+impl Appearance {
+    pub fn line_width(&self, line_width_type: &str) -> Option<Tenths> {
+        self.line_widths
+            .iter()
+            .find(|w| w.line_width_type == line_width_type)
+            .map(|w| w.value)
+    }
+}
+
+/// Default font of the lyrics with the given number and name
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LyricFont {
+    pub number: Option<String>,
+    pub name: Option<String>,
+    pub font: Font,
+    pub unknown: Unknown,
+}
+
+impl FromNode for LyricFont {
+    fn tag() -> &'static str {
+        "lyric-font"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        Ok(LyricFont {
+            number: parse_optional_attr(node, "number")?,
+            name: parse_optional_attr(node, "name")?,
+            font: Font::parse(node)?,
+            unknown: Unknown::parse(
+                node,
+                &[
+                    "number",
+                    "name",
+                    "font-family",
+                    "font-style",
+                    "font-size",
+                    "font-weight",
+                ],
+                &[],
+            ),
+        })
+    }
+}
+
+impl ToNode for LyricFont {
+    fn to_node(&self) -> Element {
+        let el = Element::new(Self::tag())
+            .with_optional_attr("number", self.number.as_ref())
+            .with_optional_attr("name", self.name.as_ref());
+        let node = self.font.write_attrs(el);
+        self.unknown.write(node)
+    }
+}
+
+/// Score-wide layout and appearance, overridden by print elements in measures
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Defaults {
+    pub scaling: Option<Scaling>,
+    /// Transposing instruments are written at concert pitch
+    pub concert_score: bool,
+    pub page_layout: Option<PageLayout>,
+    pub system_layout: Option<SystemLayout>,
+    pub staff_layout: Vec<StaffLayout>,
+    pub appearance: Option<Appearance>,
+    pub music_font: Option<Font>,
+    pub word_font: Option<Font>,
+    pub lyric_font: Vec<LyricFont>,
+    pub unknown: Unknown,
+}
+
+impl FromNode for Defaults {
+    fn tag() -> &'static str {
+        "defaults"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        let parse_font = |name: &str| {
+            node.children()
+                .find(|c| c.tag_name().name() == name)
+                .map(|c| Font::parse(&c))
+                .transpose()
+        };
+
+        Ok(Defaults {
+            scaling: parse_option_chd(node)?,
+            concert_score: count_chd(node, "concert-score") > 0,
+            page_layout: parse_option_chd(node)?,
+            system_layout: parse_option_chd(node)?,
+            staff_layout: parse_children(node)?,
+            appearance: parse_option_chd(node)?,
+            music_font: parse_font("music-font")?,
+            word_font: parse_font("word-font")?,
+            lyric_font: parse_children(node)?,
+            unknown: Unknown::parse(
+                node,
+                &[],
+                &[
+                    "scaling",
+                    "concert-score",
+                    "page-layout",
+                    "system-layout",
+                    "staff-layout",
+                    "appearance",
+                    "music-font",
+                    "word-font",
+                    "lyric-font",
+                ],
+            ),
+        })
+    }
+}
+
+impl ToNode for Defaults {
+    fn to_node(&self) -> Element {
+        let font_node = |name: &str, font: &Font| font.write_attrs(Element::new(name));
+        let node = Element::new(Self::tag())
+            .with_optional_child(self.scaling.as_ref().map(ToNode::to_node))
+            .with_optional_child(self.concert_score.then(|| Element::new("concert-score")))
+            .with_optional_child(self.page_layout.as_ref().map(ToNode::to_node))
+            .with_optional_child(self.system_layout.as_ref().map(ToNode::to_node))
+            .with_children(children_to_nodes(&self.staff_layout))
+            .with_optional_child(self.appearance.as_ref().map(ToNode::to_node))
+            .with_optional_child(self.music_font.as_ref().map(|f| font_node("music-font", f)))
+            .with_optional_child(self.word_font.as_ref().map(|f| font_node("word-font", f)))
+            .with_children(children_to_nodes(&self.lyric_font));
+        self.unknown.write(node)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use roxmltree::Document;
+
+    use super::*;
+    use crate::score::{FontStyle, Tenths};
+
+    #[test]
+    fn print_ok() {
+        let xml = r#"
+            <print new-page="yes" page-number="2">
+                <system-layout>
+                    <system-margins>
+                        <left-margin>70</left-margin>
+                        <right-margin>0</right-margin>
+                    </system-margins>
+                    <top-system-distance>211.25</top-system-distance>
+                </system-layout>
+                <staff-layout number="2">
+                    <staff-distance>65</staff-distance>
+                </staff-layout>
+                <measure-layout>
+                    <measure-distance>20</measure-distance>
+                </measure-layout>
+                <measure-numbering>system</measure-numbering>
+            </print>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let print = Print::from_node(&node);
+        assert!(print.is_ok());
+        let print = print.unwrap();
+        assert!(print.is_page_break());
+        assert!(print.is_system_break());
+        assert_eq!(
+            print,
+            Print {
+                new_page: Some(YesNo::Yes),
+                page_number: Some("2".to_owned()),
+                system_layout: Some(SystemLayout {
+                    system_margins: Some(SystemMargins {
+                        left_margin: Tenths(70.),
+                        right_margin: Tenths(0.),
+                        unknown: Unknown::default()
+                    }),
+                    system_distance: None,
+                    top_system_distance: Some(Tenths(211.25)),
+                    system_dividers: None,
+                    unknown: Unknown::default()
+                }),
+                staff_layout: vec![StaffLayout {
+                    number: 2,
+                    staff_distance: Some(Tenths(65.)),
+                    unknown: Unknown::default()
+                }],
+                measure_distance: Some(Tenths(20.)),
+                measure_numbering: Some(MeasureNumbering {
+                    value: MeasureNumberingValue::System,
+                    unknown: Unknown::default()
+                }),
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn print_page_layout_ok() {
+        let xml = r#"
+            <print>
+                <page-layout>
+                    <page-height>1683.78</page-height>
+                    <page-width>1190.55</page-width>
+                    <page-margins type="both">
+                        <left-margin>56.6929</left-margin>
+                        <right-margin>56.6929</right-margin>
+                        <top-margin>56.6929</top-margin>
+                        <bottom-margin>113.386</bottom-margin>
+                    </page-margins>
+                </page-layout>
+            </print>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let print = Print::from_node(&node).unwrap();
+        assert!(!print.is_system_break());
+        let page_layout = print.page_layout.unwrap();
+        assert_eq!(page_layout.page_width, Some(Tenths(1190.55)));
+        assert_eq!(
+            page_layout.page_margins[0].margin_type,
+            Some(MarginType::Both)
+        );
+        assert_eq!(page_layout.page_margins[0].bottom_margin, Tenths(113.386));
+    }
+
+    #[test]
+    fn defaults_ok() {
+        let xml = r#"
+            <defaults>
+                <scaling>
+                    <millimeters>7.05556</millimeters>
+                    <tenths>40</tenths>
+                </scaling>
+                <concert-score />
+                <page-layout>
+                    <page-height>1584</page-height>
+                    <page-width>1224</page-width>
+                </page-layout>
+                <system-layout>
+                    <system-distance>121</system-distance>
+                    <system-dividers>
+                        <left-divider print-object="yes" relative-y="-10" />
+                        <right-divider print-object="no" />
+                    </system-dividers>
+                </system-layout>
+                <appearance>
+                    <line-width type="stem">0.7487</line-width>
+                    <line-width type="light barline">0.7487</line-width>
+                    <note-size type="cue">70</note-size>
+                    <distance type="hyphen">60</distance>
+                </appearance>
+                <music-font font-family="Maestro,engraved" font-size="18.4" />
+                <word-font font-family="Times New Roman" font-size="10.2" />
+                <lyric-font number="1" font-family="Times New Roman" font-style="italic" />
+            </defaults>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let defaults = Defaults::from_node(&node);
+        assert!(defaults.is_ok());
+        let defaults = defaults.unwrap();
+        assert_eq!(defaults.scaling.unwrap().tenths_to_mm(Tenths(40.)), 7.05556);
+        assert!(defaults.concert_score);
+        assert_eq!(
+            defaults.page_layout.unwrap().page_height,
+            Some(Tenths(1584.))
+        );
+        let system_layout = defaults.system_layout.unwrap();
+        assert_eq!(system_layout.system_distance, Some(Tenths(121.)));
+        let dividers = system_layout.system_dividers.unwrap();
+        assert_eq!(dividers.left_divider.print_object, Some(YesNo::Yes));
+        assert_eq!(
+            dividers.left_divider.position.relative_y,
+            Some(Tenths(-10.))
+        );
+        assert_eq!(dividers.right_divider.print_object, Some(YesNo::No));
+
+        let appearance = defaults.appearance.unwrap();
+        assert_eq!(appearance.line_width("stem"), Some(Tenths(0.7487)));
+        assert_eq!(appearance.note_sizes[0].note_size_type, NoteSizeType::Cue);
+        assert_eq!(appearance.distances[0].value, Tenths(60.));
+
+        assert_eq!(
+            defaults.music_font,
+            Some(Font {
+                family: Some("Maestro,engraved".to_owned()),
+                size: Some("18.4".to_owned()),
+                ..Default::default()
+            })
+        );
+        assert_eq!(defaults.lyric_font[0].number.as_deref(), Some("1"));
+        assert_eq!(defaults.lyric_font[0].font.style, Some(FontStyle::Italic));
+    }
+}
@@ -0,0 +1,338 @@
+use roxmltree::Node;
+
+use super::{
+    parse_attr, parse_option_chd, parse_optional_attr, parse_optional_text, FromNode, Offset,
+    ToNode, Unknown, YesNo,
+};
+use crate::{error::Result, writer::Element};
+
+/// Whether a note is evaluated by an assessment application
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Assess {
+    pub assess_type: YesNo,
+    /// Id of the player the assessment applies to, all players if absent
+    pub player: Option<String>,
+    /// Times through a repeat the assessment applies to, e.g. "1, 3"
+    pub time_only: Option<String>,
+    pub unknown: Unknown,
+}
+
+impl FromNode for Assess {
+    fn tag() -> &'static str {
+        "assess"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        Ok(Assess {
+            assess_type: parse_attr(node, "type")?,
+            player: parse_optional_attr(node, "player")?,
+            time_only: parse_optional_attr(node, "time-only")?,
+            unknown: Unknown::parse(node, &["type", "player", "time-only"], &[]),
+        })
+    }
+}
+
+impl ToNode for Assess {
+    fn to_node(&self) -> Element {
+        let node = Element::new(Self::tag())
+            .with_attr("type", self.assess_type)
+            .with_optional_attr("player", self.player.as_ref())
+            .with_optional_attr("time-only", self.time_only.as_ref());
+        self.unknown.write(node)
+    }
+}
+
+/// Wait for the performer before continuing the accompaniment
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Wait {
+    pub player: Option<String>,
+    pub time_only: Option<String>,
+    pub unknown: Unknown,
+}
+
+impl FromNode for Wait {
+    fn tag() -> &'static str {
+        "wait"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        Ok(Wait {
+            player: parse_optional_attr(node, "player")?,
+            time_only: parse_optional_attr(node, "time-only")?,
+            unknown: Unknown::parse(node, &["player", "time-only"], &[]),
+        })
+    }
+}
+
+impl ToNode for Wait {
+    fn to_node(&self) -> Element {
+        let node = Element::new(Self::tag())
+            .with_optional_attr("player", self.player.as_ref())
+            .with_optional_attr("time-only", self.time_only.as_ref());
+        self.unknown.write(node)
+    }
+}
+
+/// Listening instruction not covered by the other elements
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OtherListening {
+    pub other_type: String,
+    pub player: Option<String>,
+    pub time_only: Option<String>,
+    pub text: Option<String>,
+    pub unknown: Unknown,
+}
+
+impl OtherListening {
+    fn parse(node: &Node) -> Result<Self> {
+        Ok(OtherListening {
+            other_type: parse_attr(node, "type")?,
+            player: parse_optional_attr(node, "player")?,
+            time_only: parse_optional_attr(node, "time-only")?,
+            text: parse_optional_text(node),
+            unknown: Unknown::parse_attrs(node, &["type", "player", "time-only"]),
+        })
+    }
+
+    fn to_node(&self, tag: &str) -> Element {
+        let node = Element::new(tag)
+            .with_attr("type", &self.other_type)
+            .with_optional_attr("player", self.player.as_ref())
+            .with_optional_attr("time-only", self.time_only.as_ref())
+            .with_optional_text(self.text.as_ref());
+        self.unknown.write(node)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ListenItem {
+    Assess(Assess),
+    Wait(Wait),
+    OtherListen(OtherListening),
+}
+
+/// Listening instructions of a note for interactive performance applications
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Listen {
+    pub items: Vec<ListenItem>,
+    pub unknown: Unknown,
+}
+
+impl FromNode for Listen {
+    fn tag() -> &'static str {
+        "listen"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        let mut items = Vec::new();
+        for c in node.children() {
+            match c.tag_name().name() {
+                "assess" => items.push(ListenItem::Assess(Assess::from_node(&c)?)),
+                "wait" => items.push(ListenItem::Wait(Wait::from_node(&c)?)),
+                "other-listen" => items.push(ListenItem::OtherListen(OtherListening::parse(&c)?)),
+                _ => (),
+            }
+        }
+
+        Ok(Listen {
+            items,
+            unknown: Unknown::parse(node, &[], &["assess", "wait", "other-listen"]),
+        })
+    }
+}
+
+impl Listen {
+    /// Whether the note is assessed for the player with the given id,
+    /// an assessment without a player applies to all players
+    pub fn is_assessed(&self, player: &str) -> bool {
+        self.items
+            .iter()
+            .rev()
+            .find_map(|i| match i {
+                ListenItem::Assess(a) if a.player.as_deref().is_none_or(|p| p == player) => {
+                    Some(a.assess_type == YesNo::Yes)
+                }
+                _ => None,
+            })
+            .unwrap_or(true)
+    }
+}
+
+impl ToNode for Listen {
+    fn to_node(&self) -> Element {
+        let node = Element::new(Self::tag()).with_children(self.items.iter().map(|i| match i {
+            ListenItem::Assess(a) => a.to_node(),
+            ListenItem::Wait(w) => w.to_node(),
+            ListenItem::OtherListen(o) => o.to_node("other-listen"),
+        }));
+        self.unknown.write(node)
+    }
+}
+
+xml_enum! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum SyncType {
+        None => "none",
+        Tempo => "tempo",
+        MostlyTempo => "mostly-tempo",
+        MostlyEvent => "mostly-event",
+        Event => "event",
+        AlwaysEvent => "always-event",
+    }
+}
+
+/// How an accompaniment follows the performer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sync {
+    pub sync_type: SyncType,
+    /// Latency in milliseconds the listening application should expect
+    pub latency: Option<u32>,
+    pub player: Option<String>,
+    pub time_only: Option<String>,
+    pub unknown: Unknown,
+}
+
+impl FromNode for Sync {
+    fn tag() -> &'static str {
+        "sync"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        Ok(Sync {
+            sync_type: parse_attr(node, "type")?,
+            latency: parse_optional_attr(node, "latency")?,
+            player: parse_optional_attr(node, "player")?,
+            time_only: parse_optional_attr(node, "time-only")?,
+            unknown: Unknown::parse(node, &["type", "latency", "player", "time-only"], &[]),
+        })
+    }
+}
+
+impl ToNode for Sync {
+    fn to_node(&self) -> Element {
+        let node = Element::new(Self::tag())
+            .with_attr("type", self.sync_type)
+            .with_optional_attr("latency", self.latency)
+            .with_optional_attr("player", self.player.as_ref())
+            .with_optional_attr("time-only", self.time_only.as_ref());
+        self.unknown.write(node)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ListeningItem {
+    Sync(Sync),
+    OtherListening(OtherListening),
+}
+
+/// Listening instructions changing from the current position, added in MusicXML 4.0
+#[derive(Debug, Clone, PartialEq)]
+pub struct Listening {
+    pub items: Vec<ListeningItem>,
+    pub offset: Option<Offset>,
+    pub unknown: Unknown,
+}
+
+impl FromNode for Listening {
+    fn tag() -> &'static str {
+        "listening"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        let mut items = Vec::new();
+        for c in node.children() {
+            match c.tag_name().name() {
+                "sync" => items.push(ListeningItem::Sync(Sync::from_node(&c)?)),
+                "other-listening" => {
+                    items.push(ListeningItem::OtherListening(OtherListening::parse(&c)?))
+                }
+                _ => (),
+            }
+        }
+
+        Ok(Listening {
+            items,
+            offset: parse_option_chd(node)?,
+            unknown: Unknown::parse(node, &[], &["sync", "other-listening", "offset"]),
+        })
+    }
+}
+
+impl ToNode for Listening {
+    fn to_node(&self) -> Element {
+        let node = Element::new(Self::tag())
+            .with_children(self.items.iter().map(|i| match i {
+                ListeningItem::Sync(s) => s.to_node(),
+                ListeningItem::OtherListening(o) => o.to_node("other-listening"),
+            }))
+            .with_optional_child(self.offset.as_ref().map(ToNode::to_node));
+        self.unknown.write(node)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use roxmltree::Document;
+
+    use super::*;
+
+    #[test]
+    fn listen_ok() {
+        let xml = r#"
+            <listen>
+                <assess type="no" player="P1-I1" />
+                <wait time-only="2" />
+                <other-listen type="cue">flute</other-listen>
+            </listen>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let listen = Listen::from_node(&node).unwrap();
+        assert_eq!(
+            listen.items,
+            vec![
+                ListenItem::Assess(Assess {
+                    assess_type: YesNo::No,
+                    player: Some("P1-I1".to_owned()),
+                    time_only: None,
+                    unknown: Unknown::default()
+                }),
+                ListenItem::Wait(Wait {
+                    player: None,
+                    time_only: Some("2".to_owned()),
+                    unknown: Unknown::default()
+                }),
+                ListenItem::OtherListen(OtherListening {
+                    other_type: "cue".to_owned(),
+                    player: None,
+                    time_only: None,
+                    text: Some("flute".to_owned()),
+                    unknown: Unknown::default()
+                })
+            ]
+        );
+        assert_eq!(listen.to_node(), Element::from(&node));
+        assert!(!listen.is_assessed("P1-I1"));
+        assert!(listen.is_assessed("P1-I2"));
+    }
+
+    #[test]
+    fn listening_ok() {
+        let xml = r#"
+            <listening>
+                <sync type="mostly-tempo" latency="50" />
+                <offset>1</offset>
+            </listening>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let listening = Listening::from_node(&node).unwrap();
+        assert_eq!(
+            listening.items,
+            vec![ListeningItem::Sync(Sync {
+                sync_type: SyncType::MostlyTempo,
+                latency: Some(50),
+                player: None,
+                time_only: None,
+                unknown: Unknown::default()
+            })]
+        );
+        assert_eq!(listening.offset.as_ref().map(|o| o.value), Some(1.));
+        assert_eq!(listening.to_node(), Element::from(&node));
+    }
+}
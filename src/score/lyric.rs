@@ -0,0 +1,196 @@
+use roxmltree::Node;
+
+use super::{
+    parse_option_chd, parse_optional_attr, parse_text, Font, FromNode, StartStopContinue, ToNode,
+    Unknown,
+};
+use crate::{error::Result, writer::Element};
+
+xml_enum! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum Syllabic {
+        Single => "single",
+        Begin => "begin",
+        End => "end",
+        Middle => "middle",
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Syllable {
+    /// Elision text joining this syllable to the previous one, usually an undertie
+    pub elision: Option<String>,
+    pub syllabic: Option<Syllabic>,
+    pub text: String,
+    pub font: Font,
+}
+
+/// Word extension line following the last syllable
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Extend {
+    pub extend_type: Option<StartStopContinue>,
+    pub unknown: Unknown,
+}
+
+impl FromNode for Extend {
+    fn tag() -> &'static str {
+        "extend"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        Ok(Extend {
+            extend_type: parse_optional_attr(node, "type")?,
+            unknown: Unknown::parse(node, &["type"], &[]),
+        })
+    }
+}
+
+impl ToNode for Extend {
+    fn to_node(&self) -> Element {
+        let node = Element::new(Self::tag()).with_optional_attr("type", self.extend_type);
+        self.unknown.write(node)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Lyric {
+    /// Verse number, distinguishing several lyrics on one note
+    pub number: Option<String>,
+    pub name: Option<String>,
+    /// Syllables sung on this note, more than one if joined by elisions
+    pub syllables: Vec<Syllable>,
+    pub extend: Option<Extend>,
+    pub unknown: Unknown,
+}
+
+impl FromNode for Lyric {
+    fn tag() -> &'static str {
+        "lyric"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        let mut syllables = Vec::new();
+        let (mut elision, mut syllabic) = (None, None);
+        for c in node.children() {
+            match c.tag_name().name() {
+                "elision" => elision = Some(c.text().unwrap_or_default().to_owned()),
+                "syllabic" => syllabic = Some(parse_text(&c, "syllabic")?),
+                "text" => syllables.push(Syllable {
+                    elision: elision.take(),
+                    syllabic: syllabic.take(),
+                    text: c.text().unwrap_or_default().to_owned(),
+                    font: Font::parse(&c)?,
+                }),
+                _ => {}
+            }
+        }
+
+        Ok(Lyric {
+            number: parse_optional_attr(node, "number")?,
+            name: parse_optional_attr(node, "name")?,
+            syllables,
+            extend: parse_option_chd(node)?,
+            unknown: Unknown::parse(
+                node,
+                &["number", "name"],
+                &["elision", "syllabic", "text", "extend"],
+            ),
+        })
+    }
+}
+
+impl ToNode for Lyric {
+    fn to_node(&self) -> Element {
+        let syllables = self.syllables.iter().flat_map(|s| {
+            let elision = s
+                .elision
+                .as_ref()
+                .map(|e| Element::new("elision").with_text(e));
+            let syllabic = s.syllabic.map(|s| Element::new("syllabic").with_text(s));
+            let text = s.font.write_attrs(Element::new("text")).with_text(&s.text);
+
+            elision.into_iter().chain(syllabic).chain(Some(text))
+        });
+
+        let node = Element::new(Self::tag())
+            .with_optional_attr("number", self.number.as_ref())
+            .with_optional_attr("name", self.name.as_ref())
+            .with_children(syllables)
+            .with_optional_child(self.extend.as_ref().map(ToNode::to_node));
+        self.unknown.write(node)
+    }
+}
+
+impl Lyric {
+    /// Sung text of the syllables, joining elided syllables with a space
+    pub fn text(&self) -> String {
+        self.syllables
+            .iter()
+            .map(|s| s.text.as_str())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use roxmltree::Document;
+
+    use super::*;
+    use crate::score::FontStyle;
+
+    #[test]
+    fn lyric_ok() {
+        let xml = r#"
+            <lyric number="1">
+                <syllabic>begin</syllabic>
+                <text>Green</text>
+            </lyric>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let lyric = Lyric::from_node(&node);
+        assert!(lyric.is_ok());
+        assert_eq!(
+            lyric.unwrap(),
+            Lyric {
+                number: Some("1".to_owned()),
+                name: None,
+                syllables: vec![Syllable {
+                    elision: None,
+                    syllabic: Some(Syllabic::Begin),
+                    text: "Green".to_owned(),
+                    font: Font::default()
+                }],
+                extend: None,
+                unknown: Unknown::default()
+            }
+        );
+    }
+
+    #[test]
+    fn lyric_elision_extend_ok() {
+        let xml = r#"
+            <lyric number="2" name="verse">
+                <syllabic>single</syllabic>
+                <text>me</text>
+                <elision>‿</elision>
+                <syllabic>single</syllabic>
+                <text font-style="italic">and</text>
+                <extend type="start" />
+            </lyric>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let lyric = Lyric::from_node(&node).unwrap();
+        assert_eq!(lyric.syllables.len(), 2);
+        assert_eq!(lyric.syllables[1].elision.as_deref(), Some("‿"));
+        assert_eq!(lyric.text(), "me and");
+        assert_eq!(lyric.syllables[1].font.style, Some(FontStyle::Italic));
+        assert_eq!(
+            lyric.extend,
+            Some(Extend {
+                extend_type: Some(StartStopContinue::Start),
+                unknown: Unknown::default()
+            })
+        );
+    }
+}
@@ -0,0 +1,321 @@
+use roxmltree::Node;
+
+use super::{
+    children_to_nodes, parse_attr, parse_children, parse_option_chd, parse_optional_attr,
+    parse_optional_chd_text, parse_optional_text, FromNode, ToNode, Unknown, YesNo,
+};
+use crate::{error::Result, writer::Element};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Work {
+    pub work_number: Option<String>,
+    pub work_title: Option<String>,
+    pub unknown: Unknown,
+}
+
+impl FromNode for Work {
+    fn tag() -> &'static str {
+        "work"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        Ok(Work {
+            work_number: parse_optional_chd_text(node, "work-number")?,
+            work_title: parse_optional_chd_text(node, "work-title")?,
+            unknown: Unknown::parse(node, &[], &["work-number", "work-title"]),
+        })
+    }
+}
+
+impl ToNode for Work {
+    fn to_node(&self) -> Element {
+        let node = Element::new(Self::tag())
+            .with_optional_chd_text("work-number", self.work_number.as_ref())
+            .with_optional_chd_text("work-title", self.work_title.as_ref());
+        self.unknown.write(node)
+    }
+}
+
+/// Creator or rights text with an optional type, e.g. "composer" or "lyricist"
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TypedText {
+    pub text_type: Option<String>,
+    pub text: String,
+    pub unknown: Unknown,
+}
+
+impl TypedText {
+    fn parse_all(node: &Node, name: &str) -> Result<Vec<Self>> {
+        node.children()
+            .filter(|c| c.tag_name().name() == name)
+            .map(|c| {
+                Ok(TypedText {
+                    text_type: parse_optional_attr(&c, "type")?,
+                    text: parse_optional_text(&c).unwrap_or_default(),
+                    unknown: Unknown::parse_attrs(&c, &["type"]),
+                })
+            })
+            .collect()
+    }
+
+    fn to_node(&self, name: &str) -> Element {
+        let node = Element::new(name)
+            .with_optional_attr("type", self.text_type.as_ref())
+            .with_text(&self.text);
+        self.unknown.write(node)
+    }
+}
+
+/// Whether the encoding supports an element, attribute or value
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Supports {
+    pub supports_type: YesNo,
+    pub element: String,
+    pub attribute: Option<String>,
+    pub value: Option<String>,
+    pub unknown: Unknown,
+}
+
+impl FromNode for Supports {
+    fn tag() -> &'static str {
+        "supports"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        Ok(Supports {
+            supports_type: parse_attr(node, "type")?,
+            element: parse_attr(node, "element")?,
+            attribute: parse_optional_attr(node, "attribute")?,
+            value: parse_optional_attr(node, "value")?,
+            unknown: Unknown::parse(node, &["type", "element", "attribute", "value"], &[]),
+        })
+    }
+}
+
+impl ToNode for Supports {
+    fn to_node(&self) -> Element {
+        let node = Element::new(Self::tag())
+            .with_attr("type", self.supports_type)
+            .with_attr("element", &self.element)
+            .with_optional_attr("attribute", self.attribute.as_ref())
+            .with_optional_attr("value", self.value.as_ref());
+        self.unknown.write(node)
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Encoding {
+    pub encoding_date: Option<String>,
+    pub encoder: Vec<TypedText>,
+    pub software: Vec<String>,
+    pub encoding_description: Option<String>,
+    pub supports: Vec<Supports>,
+    pub unknown: Unknown,
+}
+
+impl FromNode for Encoding {
+    fn tag() -> &'static str {
+        "encoding"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        Ok(Encoding {
+            encoding_date: parse_optional_chd_text(node, "encoding-date")?,
+            encoder: TypedText::parse_all(node, "encoder")?,
+            software: node
+                .children()
+                .filter(|c| c.tag_name().name() == "software")
+                .filter_map(|c| parse_optional_text(&c))
+                .collect(),
+            encoding_description: parse_optional_chd_text(node, "encoding-description")?,
+            supports: parse_children(node)?,
+            unknown: Unknown::parse(
+                node,
+                &[],
+                &[
+                    "encoding-date",
+                    "encoder",
+                    "software",
+                    "encoding-description",
+                    "supports",
+                ],
+            ),
+        })
+    }
+}
+
+impl ToNode for Encoding {
+    fn to_node(&self) -> Element {
+        let node = Element::new(Self::tag())
+            .with_optional_chd_text("encoding-date", self.encoding_date.as_ref())
+            .with_children(self.encoder.iter().map(|e| e.to_node("encoder")))
+            .with_children(
+                self.software
+                    .iter()
+                    .map(|s| Element::new("software").with_text(s)),
+            )
+            .with_optional_chd_text("encoding-description", self.encoding_description.as_ref())
+            .with_children(children_to_nodes(&self.supports));
+        self.unknown.write(node)
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Identification {
+    pub creator: Vec<TypedText>,
+    pub rights: Vec<TypedText>,
+    pub encoding: Option<Encoding>,
+    /// Source the digital score is based on
+    pub source: Option<String>,
+    pub unknown: Unknown,
+}
+
+impl FromNode for Identification {
+    fn tag() -> &'static str {
+        "identification"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        Ok(Identification {
+            creator: TypedText::parse_all(node, "creator")?,
+            rights: TypedText::parse_all(node, "rights")?,
+            encoding: parse_option_chd(node)?,
+            source: parse_optional_chd_text(node, "source")?,
+            unknown: Unknown::parse(node, &[], &["creator", "rights", "encoding", "source"]),
+        })
+    }
+}
+
+impl ToNode for Identification {
+    fn to_node(&self) -> Element {
+        let node = Element::new(Self::tag())
+            .with_children(self.creator.iter().map(|c| c.to_node("creator")))
+            .with_children(self.rights.iter().map(|r| r.to_node("rights")))
+            .with_optional_child(self.encoding.as_ref().map(ToNode::to_node))
+            .with_optional_chd_text("source", self.source.as_ref());
+        self.unknown.write(node)
+    }
+}
+
+impl Identification {
+    /// Names of the creators of the given type, e.g. "composer"
+    pub fn creators<'a>(&'a self, creator_type: &'a str) -> impl Iterator<Item = &'a str> {
+        self.creator
+            .iter()
+            .filter(move |c| c.text_type.as_deref() == Some(creator_type))
+            .map(|c| c.text.as_str())
+    }
+}
+
+/// Bibliographic header of a score
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Metadata {
+    pub work: Option<Work>,
+    pub movement_number: Option<String>,
+    pub movement_title: Option<String>,
+    pub identification: Option<Identification>,
+}
+
+impl Metadata {
+    pub(super) fn parse(node: &Node) -> Result<Self> {
+        Ok(Metadata {
+            work: parse_option_chd(node)?,
+            movement_number: parse_optional_chd_text(node, "movement-number")?,
+            movement_title: parse_optional_chd_text(node, "movement-title")?,
+            identification: parse_option_chd(node)?,
+        })
+    }
+
+    pub(super) fn to_nodes(&self) -> impl Iterator<Item = Element> {
+        [
+            self.work.as_ref().map(ToNode::to_node),
+            self.movement_number
+                .as_ref()
+                .map(|n| Element::new("movement-number").with_text(n)),
+            self.movement_title
+                .as_ref()
+                .map(|t| Element::new("movement-title").with_text(t)),
+            self.identification.as_ref().map(ToNode::to_node),
+        ]
+        .into_iter()
+        .flatten()
+    }
+
+    /// Title of the work, falling back to the movement title
+    pub fn title(&self) -> Option<&str> {
+        self.work
+            .as_ref()
+            .and_then(|w| w.work_title.as_deref())
+            .or(self.movement_title.as_deref())
+    }
+
+    pub fn composer(&self) -> Option<&str> {
+        self.identification.as_ref()?.creators("composer").next()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use roxmltree::Document;
+
+    use super::*;
+
+    #[test]
+    fn metadata_ok() {
+        let xml = r#"
+            <score-partwise>
+                <work>
+                    <work-number>BWV 846</work-number>
+                    <work-title>Das Wohltemperierte Klavier</work-title>
+                </work>
+                <movement-number>1</movement-number>
+                <movement-title>Prelude in C major</movement-title>
+                <identification>
+                    <creator type="composer">Johann Sebastian Bach</creator>
+                    <rights>Public Domain</rights>
+                    <encoding>
+                        <software>MuseScore 4.1.1</software>
+                        <encoding-date>2023-09-01</encoding-date>
+                        <supports element="accidental" type="yes" />
+                    </encoding>
+                </identification>
+            </score-partwise>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let metadata = Metadata::parse(&node);
+        assert!(metadata.is_ok());
+        let metadata = metadata.unwrap();
+        assert_eq!(metadata.title(), Some("Das Wohltemperierte Klavier"));
+        assert_eq!(metadata.composer(), Some("Johann Sebastian Bach"));
+        assert_eq!(metadata.movement_number.as_deref(), Some("1"));
+
+        let identification = metadata.identification.as_ref().unwrap();
+        assert_eq!(identification.rights[0].text_type, None);
+        let encoding = identification.encoding.as_ref().unwrap();
+        assert_eq!(encoding.software, vec!["MuseScore 4.1.1".to_owned()]);
+        assert_eq!(encoding.encoding_date.as_deref(), Some("2023-09-01"));
+        assert_eq!(encoding.supports[0].supports_type, YesNo::Yes);
+
+        let names: Vec<_> = metadata.to_nodes().map(|e| e.name).collect();
+        assert_eq!(
+            names,
+            vec![
+                "work",
+                "movement-number",
+                "movement-title",
+                "identification"
+            ]
+        );
+    }
+
+    #[test]
+    fn metadata_movement_title_ok() {
+        let xml = r#"
+            <score-partwise>
+                <movement-title>Greensleeves</movement-title>
+            </score-partwise>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let metadata = Metadata::parse(&node).unwrap();
+        assert_eq!(metadata.title(), Some("Greensleeves"));
+        assert_eq!(metadata.composer(), None);
+    }
+}
@@ -0,0 +1,849 @@
+use std::{any::type_name, str::FromStr};
+
+use roxmltree::{Document, Node};
+
+use crate::error::Error::{
+    AttrNotFound, AttrValueParseFailed, DuplicatedNodesFound, NodeNotFound, NodeTextEmpty,
+    NodeTextParseFailed, UnsupportedVersion,
+};
+use crate::error::Result;
+use crate::writer::{Element, WriteOptions};
+
+const XML_DECLARATION: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="no"?>"#;
+
+/// Declare an enum of MusicXML string values with `FromStr` and `Display` impls
+macro_rules! xml_enum {
+    (
+        $(#[$meta:meta])*
+        pub enum $name:ident {
+            $($(#[$v_meta:meta])* $variant:ident => $v:literal,)+
+        }
+    ) => {
+        $(#[$meta])*
+        pub enum $name {
+            $($(#[$v_meta])* $variant,)+
+        }
+
+        impl ::std::str::FromStr for $name {
+            type Err = ();
+
+            fn from_str(s: &str) -> core::result::Result<Self, Self::Err> {
+                match s {
+                    $($v => Ok($name::$variant),)+
+                    _ => Err(()),
+                }
+            }
+        }
+
+        impl ::std::fmt::Display for $name {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                f.write_str(match self {
+                    $($name::$variant => $v,)+
+                })
+            }
+        }
+    };
+}
+
+mod attributes;
+mod barline;
+mod common;
+mod credit;
+mod direction;
+mod harmony;
+mod layout;
+mod listening;
+mod lyric;
+mod metadata;
+mod notations;
+mod note;
+mod part;
+mod part_list;
+mod technical;
+mod timewise;
+
+pub use attributes::*;
+pub use barline::*;
+pub use common::*;
+pub use credit::*;
+pub use direction::*;
+pub use harmony::*;
+pub use layout::*;
+pub use listening::*;
+pub use lyric::*;
+pub use metadata::*;
+pub use notations::*;
+pub use note::*;
+pub use part::*;
+pub use part_list::*;
+pub use technical::*;
+pub use timewise::*;
+
+trait FromNode: Sized {
+    fn tag() -> &'static str;
+    fn from_node(node: &Node) -> Result<Self>;
+}
+
+trait ToNode {
+    fn to_node(&self) -> Element;
+}
+
+fn children_to_nodes<T: ToNode>(children: &[T]) -> impl Iterator<Item = Element> + '_ {
+    children.iter().map(T::to_node)
+}
+
+fn parse_children<T: FromNode>(node: &Node) -> Result<Vec<T>> {
+    node.children()
+        .filter(|c| c.tag_name().name() == T::tag())
+        .map(|c| T::from_node(&c))
+        .collect::<Result<Vec<T>>>()
+}
+
+fn parse_chd<T: FromNode>(node: &Node) -> Result<T> {
+    parse_option_chd(node)?.ok_or(NodeNotFound {
+        tag: T::tag(),
+        parent_tag: node.tag_name().name().to_owned(),
+    })
+}
+
+fn parse_option_chd<T: FromNode>(node: &Node) -> Result<Option<T>> {
+    node.children()
+        .find(|c| c.tag_name().name() == T::tag())
+        .as_ref()
+        .map(T::from_node)
+        .transpose()
+}
+
+pub fn parse_optional_attr<T: FromStr>(node: &Node, attr: &str) -> Result<Option<T>> {
+    match node.attribute(attr) {
+        Some(v) => Some(T::from_str(v))
+            .transpose()
+            .map_err(|_| AttrValueParseFailed {
+                attr: attr.to_owned(),
+                tag: node.tag_name().name().to_owned(),
+                v: v.to_owned(),
+                ty: type_name::<T>(),
+            }),
+        None => Ok(None),
+    }
+}
+
+pub fn parse_attr<T: FromStr>(node: &Node, attr: &'static str) -> Result<T> {
+    parse_optional_attr(node, attr)
+        .transpose()
+        .ok_or(AttrNotFound {
+            attr,
+            tag: node.tag_name().name().to_owned(),
+        })?
+}
+
+pub fn parse_optional_chd_text<T: FromStr>(node: &Node, name: &'static str) -> Result<Option<T>> {
+    match node
+        .children()
+        .filter(|c| c.tag_name().name() == name)
+        .count()
+    {
+        1 => {}
+        0 => return Ok(None),
+        _ => {
+            return Err(DuplicatedNodesFound {
+                tag: name,
+                parent_tag: node.tag_name().name().to_owned(),
+            })
+        }
+    };
+
+    let text = node
+        .children()
+        .find(|c| c.tag_name().name() == name)
+        .unwrap()
+        .text()
+        .ok_or(NodeTextEmpty { tag: name })?
+        .to_owned();
+
+    Some(text.parse().map_err(|_| NodeTextParseFailed {
+        tag: name,
+        text,
+        ty: type_name::<T>(),
+    }))
+    .transpose()
+}
+
+fn parse_text<T: FromStr>(node: &Node, tag: &'static str) -> Result<T> {
+    let text = node.text().ok_or(NodeTextEmpty { tag })?;
+
+    text.parse().map_err(|_| NodeTextParseFailed {
+        tag,
+        text: text.to_owned(),
+        ty: type_name::<T>(),
+    })
+}
+
+fn parse_optional_text(node: &Node) -> Option<String> {
+    node.text()
+        .map(str::trim)
+        .filter(|t| !t.is_empty())
+        .map(str::to_owned)
+}
+
+fn count_chd(node: &Node, name: &str) -> usize {
+    node.children()
+        .filter(|c| c.tag_name().name() == name)
+        .count()
+}
+
+pub fn parse_chd_text<T: FromStr>(node: &Node, name: &'static str) -> Result<T> {
+    match parse_optional_chd_text(node, name).transpose() {
+        None => Err(NodeNotFound {
+            tag: name,
+            parent_tag: node.tag_name().name().to_owned(),
+        }),
+        Some(r) => r,
+    }
+}
+
+xml_enum! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+    pub enum Version {
+        V1_0 => "1.0",
+        V1_1 => "1.1",
+        V2_0 => "2.0",
+        V3_0 => "3.0",
+        V3_1 => "3.1",
+        V4_0 => "4.0",
+    }
+}
+
+/// Elements of the model introduced after MusicXML 1.0, with their version,
+/// the children of a listed element are removed with it and not listed
+const VERSIONED_ELEMENTS: &[(&str, Version)] = &[
+    ("credit", Version::V1_1),
+    ("credit-type", Version::V3_0),
+    ("interchangeable", Version::V3_0),
+    ("percussion", Version::V3_0),
+    ("play", Version::V3_0),
+    ("beat-unit-tied", Version::V3_1),
+    ("haydn", Version::V3_1),
+    ("soft-accent", Version::V3_1),
+    ("symbol", Version::V3_1),
+    ("concert-score", Version::V4_0),
+    ("for-part", Version::V4_0),
+    ("instrument-change", Version::V4_0),
+    ("listen", Version::V4_0),
+    ("listening", Version::V4_0),
+    ("numeral", Version::V4_0),
+    ("player", Version::V4_0),
+    ("swing", Version::V4_0),
+];
+
+impl Version {
+    pub const LATEST: Version = Version::V4_0;
+
+    /// Whether elements introduced in the given version are part of this version
+    pub fn supports(&self, since: Version) -> bool {
+        *self >= since
+    }
+
+    /// Whether an element is part of this version, elements not known to be
+    /// introduced later are assumed to be
+    pub fn supports_element(&self, tag: &str) -> bool {
+        VERSIONED_ELEMENTS
+            .iter()
+            .find(|(t, _)| *t == tag)
+            .is_none_or(|(_, since)| self.supports(*since))
+    }
+
+    /// Drop the elements written from the model that this version does not have,
+    /// so the document stays valid against its doctype
+    fn retain_supported(&self, mut node: Element) -> Element {
+        node.retain_descendants(&|e| self.supports_element(&e.name));
+        node
+    }
+
+    /// Version declared by the root element of a document
+    fn parse(node: &Node) -> Result<Self> {
+        match node.attribute("version") {
+            Some(v) => v.parse().map_err(|_| UnsupportedVersion {
+                version: v.to_owned(),
+            }),
+            None => Ok(Version::V1_0),
+        }
+    }
+
+    fn partwise_doctype(&self) -> String {
+        format!(
+            r#"<!DOCTYPE score-partwise PUBLIC "-//Recordare//DTD MusicXML {} Partwise//EN" "http://www.musicxml.org/dtds/partwise.dtd">"#,
+            self
+        )
+    }
+
+    fn timewise_doctype(&self) -> String {
+        format!(
+            r#"<!DOCTYPE score-timewise PUBLIC "-//Recordare//DTD MusicXML {} Timewise//EN" "http://www.musicxml.org/dtds/timewise.dtd">"#,
+            self
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Score {
+    /// 1.0 if the document does not declare its version
+    pub version: Version,
+    pub metadata: Metadata,
+    pub defaults: Option<Defaults>,
+    pub credits: Vec<Credit>,
+    pub part_list: PartList,
+    pub parts: Vec<Part>,
+    pub unknown: Unknown,
+}
+
+impl FromNode for Score {
+    fn tag() -> &'static str {
+        "score-partwise"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        Ok(Score {
+            version: Version::parse(node)?,
+            metadata: Metadata::parse(node)?,
+            defaults: parse_option_chd(node)?,
+            credits: parse_children(node)?,
+            part_list: parse_chd(node)?,
+            parts: parse_children(node)?,
+            unknown: Unknown::parse(
+                node,
+                &["version"],
+                &[
+                    "work",
+                    "movement-number",
+                    "movement-title",
+                    "identification",
+                    "defaults",
+                    "credit",
+                    "part-list",
+                    "part",
+                ],
+            ),
+        })
+    }
+}
+
+impl ToNode for Score {
+    fn to_node(&self) -> Element {
+        let node = Element::new(Self::tag())
+            .with_attr("version", self.version)
+            .with_children(self.metadata.to_nodes())
+            .with_optional_child(self.defaults.as_ref().map(ToNode::to_node))
+            .with_children(children_to_nodes(&self.credits))
+            .with_child(self.part_list.to_node())
+            .with_children(children_to_nodes(&self.parts));
+        self.version.retain_supported(self.unknown.write(node))
+    }
+}
+
+impl Score {
+    pub fn from_xml(xml: &str) -> Result<Self> {
+        let doc = Document::parse(xml)?;
+
+        Score::from_node(&doc.root_element())
+    }
+
+    pub fn to_xml(&self) -> String {
+        self.to_xml_with(&WriteOptions::default())
+    }
+
+    pub fn to_xml_with(&self, options: &WriteOptions) -> String {
+        let newline = options.newline.as_str();
+
+        let mut xml = format!("{}{}", XML_DECLARATION, newline);
+        if options.doctype {
+            xml.push_str(&self.version.partwise_doctype());
+            xml.push_str(newline);
+        }
+        self.to_node().write_with(&mut xml, 0, options);
+
+        xml
+    }
+
+    /// Whether transposing instruments are written at concert pitch
+    pub fn is_concert_score(&self) -> bool {
+        self.defaults.as_ref().is_some_and(|d| d.concert_score)
+    }
+
+    /// Text of the first credit of the given type, e.g. "title" or "composer"
+    pub fn credit_text(&self, credit_type: &str) -> Option<String> {
+        self.credits
+            .iter()
+            .find(|c| c.credit_type.iter().any(|t| t == credit_type))
+            .map(Credit::text)
+    }
+
+    /// Find the score-part in the part-list that identifies the given part
+    pub fn score_part(&self, part: &Part) -> Option<&ScorePart> {
+        self.part_list.score_parts().find(|p| p.id == part.id)
+    }
+
+    /// Standalone score of the part with the given id for printing it on its own,
+    /// keeping the credits of the first page with the part name credit naming it,
+    /// and leaving the system and page breaks of the full score to the layout
+    pub fn extract_part(&self, id: &str) -> Option<Score> {
+        let part = self.parts.iter().find(|p| p.id == id)?;
+        let score_part = self.score_part(part)?;
+
+        let credits = self
+            .credits
+            .iter()
+            .filter(|c| c.page.is_none_or(|p| p == 1))
+            .cloned()
+            .map(|mut c| {
+                if c.credit_type.iter().any(|t| t == "part name") {
+                    c.credit_words.truncate(1);
+                    if let Some(w) = c.credit_words.first_mut() {
+                        w.text = score_part.part_name.clone();
+                    }
+                }
+                c
+            })
+            .collect();
+
+        let mut part = part.clone();
+        for c in part.measures.iter_mut().flat_map(|m| &mut m.contents) {
+            if let MeasureContent::Print(p) = c {
+                p.new_system = None;
+                p.new_page = None;
+            }
+        }
+
+        Some(Score {
+            version: self.version,
+            metadata: self.metadata.clone(),
+            defaults: self.defaults.clone(),
+            credits,
+            part_list: PartList {
+                items: vec![PartListItem::ScorePart(score_part.clone())],
+                unknown: Unknown::default(),
+            },
+            parts: vec![part],
+            unknown: self.unknown.clone(),
+        })
+    }
+
+    /// MIDI setup playing a note of the given part, falling back to
+    /// the only instrument of the part if the note references none
+    pub fn midi_instrument(&self, part: &Part, note: &Note) -> Option<&MidiInstrument> {
+        let score_part = self.score_part(part)?;
+
+        match note.instrument.first() {
+            Some(id) => score_part.midi_instrument(id),
+            None => match score_part.midi_instrument.as_slice() {
+                [m] => Some(m),
+                _ => None,
+            },
+        }
+    }
+
+    /// MIDI setup playing a note in the measure at the given index, following
+    /// the instrument changes and MIDI setups of sounds up to that measure,
+    /// none if the part has no such measure
+    pub fn midi_instrument_at<'a>(
+        &'a self,
+        part: &'a Part,
+        measure: usize,
+        note: &'a Note,
+    ) -> Option<&'a MidiInstrument> {
+        let sounds: Vec<&Sound> = part
+            .measures
+            .get(..=measure)?
+            .iter()
+            .flat_map(Measure::sounds)
+            .collect();
+
+        let id = note.instrument.first().or_else(|| {
+            sounds
+                .iter()
+                .rev()
+                .find_map(|s| s.instrument_change.as_ref().map(|c| &c.id))
+        });
+        let Some(id) = id else {
+            return self.midi_instrument(part, note);
+        };
+
+        sounds
+            .iter()
+            .rev()
+            .filter_map(|s| s.midi_instrument.as_ref())
+            .find(|m| &m.id == id)
+            .or_else(|| self.score_part(part)?.midi_instrument(id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::writer::Newline;
+
+    #[test]
+    fn parse_attr_ok() {
+        let xml = r#"<slur type="start" />"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let slur_ty = parse_attr::<String>(&node, "type");
+        assert!(slur_ty.is_ok());
+        assert_eq!(slur_ty.unwrap(), "start");
+    }
+
+    #[test]
+    fn parse_absent_attr_err() {
+        let xml = r#"<slur />"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let slur_ty = parse_attr::<String>(&node, "type");
+        assert!(slur_ty.is_err());
+        assert!(
+            matches!(slur_ty, Err(AttrNotFound { attr, tag }) if attr == "type" && tag == "slur" )
+        );
+    }
+
+    #[test]
+    fn score_xml_round_trip_ok() {
+        let xml = r#"
+            <score-partwise version="3.1">
+                <part-list>
+                    <score-part id="P1">
+                        <part-name>Piano</part-name>
+                    </score-part>
+                </part-list>
+                <part id="P1">
+                    <measure number="1">
+                        <attributes>
+                            <divisions>2</divisions>
+                            <staves>1</staves>
+                            <clef>
+                                <sign>G</sign>
+                                <line>2</line>
+                            </clef>
+                        </attributes>
+                        <note>
+                            <pitch>
+                                <step>B</step>
+                                <alter>1</alter>
+                                <octave>3</octave>
+                            </pitch>
+                            <duration>2</duration>
+                        </note>
+                        <note>
+                            <rest />
+                            <duration>2</duration>
+                        </note>
+                    </measure>
+                </part>
+            </score-partwise>"#;
+        let score = Score::from_xml(xml).unwrap();
+
+        let written = score.to_xml();
+        assert!(written.starts_with(XML_DECLARATION));
+        assert!(written.contains("DTD MusicXML 3.1 Partwise"));
+        assert!(written.contains(r#"<score-partwise version="3.1">"#));
+        assert!(written.contains(r#"<score-part id="P1">"#));
+        assert!(written.contains("<step>B</step>"));
+
+        let reparsed = Score::from_xml(&written);
+        assert!(reparsed.is_ok());
+        assert_eq!(reparsed.unwrap(), score);
+    }
+
+    #[test]
+    fn write_options_ok() {
+        let score =
+            Score::from_xml(r#"<score-partwise version="4.0"><part-list /></score-partwise>"#)
+                .unwrap();
+        let options = WriteOptions {
+            doctype: false,
+            newline: Newline::CrLf,
+            ..Default::default()
+        };
+
+        let written = score.to_xml_with(&options);
+        assert_eq!(
+            written,
+            format!(
+                "{}\r\n<score-partwise version=\"4.0\">\r\n  <part-list/>\r\n</score-partwise>\r\n",
+                XML_DECLARATION
+            )
+        );
+    }
+
+    #[test]
+    fn extract_part_ok() {
+        let xml = r#"
+            <score-partwise version="4.0">
+                <credit page="1">
+                    <credit-type>title</credit-type>
+                    <credit-words>Quartet</credit-words>
+                </credit>
+                <credit page="1">
+                    <credit-type>part name</credit-type>
+                    <credit-words>Score</credit-words>
+                </credit>
+                <credit page="2">
+                    <credit-words>Quartet - 2</credit-words>
+                </credit>
+                <part-list>
+                    <part-group type="start" number="1" />
+                    <score-part id="P1">
+                        <part-name>Violin</part-name>
+                    </score-part>
+                    <score-part id="P2">
+                        <part-name>Viola</part-name>
+                    </score-part>
+                    <part-group type="stop" number="1" />
+                </part-list>
+                <part id="P1">
+                    <measure number="1" />
+                </part>
+                <part id="P2">
+                    <measure number="1">
+                        <print new-system="yes" />
+                        <note>
+                            <rest />
+                            <duration>4</duration>
+                        </note>
+                    </measure>
+                </part>
+            </score-partwise>"#;
+        let score = Score::from_xml(xml).unwrap();
+
+        let viola = score.extract_part("P2").unwrap();
+        assert_eq!(viola.part_list.items.len(), 1);
+        assert_eq!(viola.parts.len(), 1);
+        assert_eq!(viola.parts[0].id, "P2");
+        assert_eq!(viola.credits.len(), 2);
+        assert_eq!(viola.credit_text("part name"), Some("Viola".to_owned()));
+        match &viola.parts[0].measures[0].contents[0] {
+            MeasureContent::Print(p) => assert_eq!(p.new_system, None),
+            _ => panic!("print expected"),
+        }
+
+        assert!(score.extract_part("P3").is_none());
+    }
+
+    #[test]
+    fn score_version_ok() {
+        let xml = r#"
+            <score-partwise version="2.0">
+                <part-list />
+            </score-partwise>"#;
+        let score = Score::from_xml(xml).unwrap();
+        assert_eq!(score.version, Version::V2_0);
+        assert!(score.version.supports(Version::V1_1));
+        assert!(!score.version.supports(Version::V3_0));
+
+        let score = Score::from_xml("<score-partwise><part-list /></score-partwise>").unwrap();
+        assert_eq!(score.version, Version::V1_0);
+
+        let score =
+            Score::from_xml(r#"<score-partwise version="5.0"><part-list /></score-partwise>"#);
+        assert!(matches!(score, Err(UnsupportedVersion { version }) if version == "5.0"));
+    }
+
+    #[test]
+    fn score_version_write_ok() {
+        let xml = r#"
+            <score-partwise version="4.0">
+                <part-list />
+                <part id="P1">
+                    <measure number="1">
+                        <listening>
+                            <sync type="event" />
+                        </listening>
+                        <note>
+                            <rest />
+                            <duration>4</duration>
+                            <listen>
+                                <assess type="no" />
+                            </listen>
+                        </note>
+                    </measure>
+                </part>
+            </score-partwise>"#;
+        let mut score = Score::from_xml(xml).unwrap();
+        assert!(score.to_xml().contains("<listen>"));
+        assert!(score.to_xml().contains("<listening>"));
+
+        score.version = Version::V3_1;
+        assert!(!Version::V3_1.supports_element("listen"));
+        assert!(!Version::V3_1.supports_element("swing"));
+        assert!(Version::V3_1.supports_element("note"));
+        let written = Score::from_xml(&score.to_xml()).unwrap();
+        match &written.parts[0].measures[0].contents[..] {
+            [MeasureContent::Note(n)] => assert!(n.listen.is_none()),
+            _ => panic!("note expected"),
+        }
+    }
+
+    #[test]
+    fn score_version_write_empty_parent_ok() {
+        let xml = r#"
+            <score-partwise version="3.1">
+                <part-list />
+                <part id="P1">
+                    <measure number="1">
+                        <direction>
+                            <direction-type>
+                                <words>Solo</words>
+                            </direction-type>
+                            <direction-type>
+                                <percussion>
+                                    <timpani />
+                                </percussion>
+                            </direction-type>
+                        </direction>
+                    </measure>
+                </part>
+            </score-partwise>"#;
+        let mut score = Score::from_xml(xml).unwrap();
+        score.version = Version::V2_0;
+
+        let written = score.to_xml();
+        assert!(!written.contains("percussion"));
+        let written = Score::from_xml(&written).unwrap();
+        match &written.parts[0].measures[0].contents[..] {
+            [MeasureContent::Direction(d)] => assert_eq!(d.direction_types.len(), 1),
+            _ => panic!("direction expected"),
+        }
+    }
+
+    #[test]
+    fn score_midi_instrument_ok() {
+        let xml = r#"
+            <score-partwise>
+                <part-list>
+                    <score-part id="P1">
+                        <part-name>Percussion</part-name>
+                        <midi-instrument id="P1-I1">
+                            <midi-unpitched>36</midi-unpitched>
+                        </midi-instrument>
+                        <midi-instrument id="P1-I2">
+                            <midi-unpitched>42</midi-unpitched>
+                        </midi-instrument>
+                    </score-part>
+                </part-list>
+                <part id="P1">
+                    <measure number="1">
+                        <note>
+                            <unpitched />
+                            <duration>1</duration>
+                            <instrument id="P1-I2" />
+                        </note>
+                        <note>
+                            <unpitched />
+                            <duration>1</duration>
+                        </note>
+                    </measure>
+                </part>
+            </score-partwise>"#;
+        let score = Score::from_xml(xml).unwrap();
+        let part = &score.parts[0];
+        let notes: Vec<_> = part.measures[0].notes().collect();
+
+        let hi_hat = score.midi_instrument(part, notes[0]);
+        assert_eq!(hi_hat.and_then(|m| m.midi_unpitched), Some(42));
+        assert_eq!(score.midi_instrument(part, notes[1]), None);
+    }
+
+    #[test]
+    fn score_instrument_change_ok() {
+        let xml = r#"
+            <score-partwise>
+                <part-list>
+                    <score-part id="P1">
+                        <part-name>Flute</part-name>
+                        <midi-instrument id="P1-I1">
+                            <midi-program>74</midi-program>
+                        </midi-instrument>
+                    </score-part>
+                </part-list>
+                <part id="P1">
+                    <measure number="1">
+                        <note>
+                            <rest />
+                            <duration>4</duration>
+                        </note>
+                    </measure>
+                    <measure number="2">
+                        <sound>
+                            <instrument-change id="P1-I2">
+                                <instrument-sound>wind.flutes.flute.piccolo</instrument-sound>
+                                <solo />
+                            </instrument-change>
+                            <midi-instrument id="P1-I2">
+                                <midi-program>73</midi-program>
+                            </midi-instrument>
+                        </sound>
+                        <note>
+                            <rest />
+                            <duration>4</duration>
+                        </note>
+                    </measure>
+                </part>
+            </score-partwise>"#;
+        let score = Score::from_xml(xml).unwrap();
+        let part = &score.parts[0];
+
+        let change = part.measures[1].sounds().next().unwrap();
+        assert_eq!(
+            change.instrument_change,
+            Some(InstrumentChange {
+                id: "P1-I2".to_owned(),
+                instrument_sound: Some("wind.flutes.flute.piccolo".to_owned()),
+                performers: Some(Performers::Solo),
+                unknown: Unknown::default()
+            })
+        );
+
+        let programs: Vec<_> = part
+            .measures
+            .iter()
+            .enumerate()
+            .map(|(i, m)| {
+                let note = m.notes().next().unwrap();
+                score
+                    .midi_instrument_at(part, i, note)
+                    .and_then(|m| m.midi_program)
+            })
+            .collect();
+        assert_eq!(programs, vec![Some(74), Some(73)]);
+
+        let note = part.measures[0].notes().next().unwrap();
+        assert_eq!(score.midi_instrument_at(part, 2, note), None);
+    }
+
+    #[test]
+    fn score_part_list_ok() {
+        let xml = r#"
+            <score-partwise>
+                <part-list>
+                    <score-part id="P1">
+                        <part-name>Violin</part-name>
+                        <part-abbreviation>Vln.</part-abbreviation>
+                    </score-part>
+                    <score-part id="P2">
+                        <part-name />
+                    </score-part>
+                </part-list>
+                <part id="P2" />
+                <part id="P1" />
+            </score-partwise>"#;
+        let score = Score::from_xml(xml).unwrap();
+
+        let violin = score.score_part(&score.parts[1]).unwrap();
+        assert_eq!(violin.part_name, "Violin");
+        assert_eq!(violin.part_abbreviation.as_deref(), Some("Vln."));
+
+        let unnamed = score.score_part(&score.parts[0]).unwrap();
+        assert_eq!(unnamed.id, "P2");
+        assert_eq!(unnamed.part_name, "");
+        assert_eq!(unnamed.part_abbreviation, None);
+    }
+}
@@ -0,0 +1,1349 @@
+use roxmltree::Node;
+
+use super::{
+    children_to_nodes, parse_attr, parse_children, parse_option_chd, parse_optional_attr,
+    parse_optional_text, parse_text, AboveBelow, Bezier, Color, Dynamics, FromNode, LineType,
+    NumberLevel, OverUnder, Position, StartStop, StartStopContinue, Technical, ToNode, TopBottom,
+    Unknown, UpDown, YesNo,
+};
+use crate::{error::Result, writer::Element};
+
+xml_enum! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum TiedType {
+        Start => "start",
+        Stop => "stop",
+        Continue => "continue",
+        /// Tied to nothing, letting the note ring
+        LetRing => "let-ring",
+    }
+}
+
+/// Notated tie, the tie heard in playback is a tie of the note
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tied {
+    pub tied_type: TiedType,
+    /// Distinguishes overlapping ties, e.g. of notes in a chord
+    pub number: Option<NumberLevel>,
+    pub line_type: Option<LineType>,
+    /// Curve above or below the notes, decided by the application if absent
+    pub orientation: Option<OverUnder>,
+    pub position: Position,
+    pub bezier: Bezier,
+    pub color: Option<Color>,
+    pub unknown: Unknown,
+}
+
+impl FromNode for Tied {
+    fn tag() -> &'static str {
+        "tied"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        Ok(Tied {
+            tied_type: parse_attr(node, "type")?,
+            number: parse_optional_attr(node, "number")?,
+            line_type: parse_optional_attr(node, "line-type")?,
+            orientation: parse_optional_attr(node, "orientation")?,
+            position: Position::parse(node)?,
+            bezier: Bezier::parse(node)?,
+            color: parse_optional_attr(node, "color")?,
+            unknown: Unknown::parse(
+                node,
+                &[
+                    "type",
+                    "number",
+                    "line-type",
+                    "default-x",
+                    "default-y",
+                    "relative-x",
+                    "relative-y",
+                    "orientation",
+                    "bezier-x",
+                    "bezier-y",
+                    "bezier-x2",
+                    "bezier-y2",
+                    "bezier-offset",
+                    "bezier-offset2",
+                    "color",
+                ],
+                &[],
+            ),
+        })
+    }
+}
+
+impl ToNode for Tied {
+    fn to_node(&self) -> Element {
+        let node = Element::new(Self::tag())
+            .with_attr("type", self.tied_type)
+            .with_optional_attr("number", self.number)
+            .with_optional_attr("line-type", self.line_type);
+        let node = self
+            .position
+            .write_attrs(node)
+            .with_optional_attr("orientation", self.orientation);
+        let node = self
+            .bezier
+            .write_attrs(node)
+            .with_optional_attr("color", self.color);
+        self.unknown.write(node)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Slur {
+    pub slur_type: StartStopContinue,
+    /// Distinguishes overlapping slurs, pairing a start with its stop
+    pub number: NumberLevel,
+    pub placement: Option<AboveBelow>,
+    pub color: Option<Color>,
+    pub position: Position,
+    pub unknown: Unknown,
+}
+
+impl FromNode for Slur {
+    fn tag() -> &'static str {
+        "slur"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        Ok(Slur {
+            slur_type: parse_attr(node, "type")?,
+            number: parse_optional_attr(node, "number")?.unwrap_or_default(),
+            placement: parse_optional_attr(node, "placement")?,
+            color: parse_optional_attr(node, "color")?,
+            position: Position::parse(node)?,
+            unknown: Unknown::parse(
+                node,
+                &[
+                    "type",
+                    "number",
+                    "placement",
+                    "color",
+                    "default-x",
+                    "default-y",
+                    "relative-x",
+                    "relative-y",
+                ],
+                &[],
+            ),
+        })
+    }
+}
+
+impl ToNode for Slur {
+    fn to_node(&self) -> Element {
+        let node = Element::new(Self::tag())
+            .with_attr("type", self.slur_type)
+            .with_attr("number", self.number)
+            .with_optional_attr("placement", self.placement)
+            .with_optional_attr("color", self.color);
+        let node = self.position.write_attrs(node);
+        self.unknown.write(node)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Tuplet {
+    pub tuplet_type: StartStop,
+    pub number: Option<NumberLevel>,
+    pub bracket: Option<YesNo>,
+    pub placement: Option<AboveBelow>,
+    pub unknown: Unknown,
+}
+
+impl FromNode for Tuplet {
+    fn tag() -> &'static str {
+        "tuplet"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        Ok(Tuplet {
+            tuplet_type: parse_attr(node, "type")?,
+            number: parse_optional_attr(node, "number")?,
+            bracket: parse_optional_attr(node, "bracket")?,
+            placement: parse_optional_attr(node, "placement")?,
+            unknown: Unknown::parse(node, &["type", "number", "bracket", "placement"], &[]),
+        })
+    }
+}
+
+impl ToNode for Tuplet {
+    fn to_node(&self) -> Element {
+        let node = Element::new(Self::tag())
+            .with_attr("type", self.tuplet_type)
+            .with_optional_attr("number", self.number)
+            .with_optional_attr("bracket", self.bracket)
+            .with_optional_attr("placement", self.placement);
+        self.unknown.write(node)
+    }
+}
+
+/// Glide through the pitches between two notes, usually drawn wavy
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Glissando {
+    pub glissando_type: StartStop,
+    /// Distinguishes overlapping glissandos, pairing a start with its stop
+    pub number: NumberLevel,
+    pub line_type: Option<LineType>,
+    pub text: Option<String>,
+    pub unknown: Unknown,
+}
+
+impl FromNode for Glissando {
+    fn tag() -> &'static str {
+        "glissando"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        Ok(Glissando {
+            glissando_type: parse_attr(node, "type")?,
+            number: parse_optional_attr(node, "number")?.unwrap_or_default(),
+            line_type: parse_optional_attr(node, "line-type")?,
+            text: parse_optional_text(node),
+            unknown: Unknown::parse_attrs(node, &["type", "number", "line-type"]),
+        })
+    }
+}
+
+impl ToNode for Glissando {
+    fn to_node(&self) -> Element {
+        let node = Element::new(Self::tag())
+            .with_attr("type", self.glissando_type)
+            .with_attr("number", self.number)
+            .with_optional_attr("line-type", self.line_type)
+            .with_optional_text(self.text.as_ref());
+        self.unknown.write(node)
+    }
+}
+
+/// Continuous pitch slide between two notes, usually drawn solid
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Slide {
+    pub slide_type: StartStop,
+    /// Distinguishes overlapping slides, pairing a start with its stop
+    pub number: NumberLevel,
+    pub line_type: Option<LineType>,
+    pub text: Option<String>,
+    pub unknown: Unknown,
+}
+
+impl FromNode for Slide {
+    fn tag() -> &'static str {
+        "slide"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        Ok(Slide {
+            slide_type: parse_attr(node, "type")?,
+            number: parse_optional_attr(node, "number")?.unwrap_or_default(),
+            line_type: parse_optional_attr(node, "line-type")?,
+            text: parse_optional_text(node),
+            unknown: Unknown::parse_attrs(node, &["type", "number", "line-type"]),
+        })
+    }
+}
+
+impl ToNode for Slide {
+    fn to_node(&self) -> Element {
+        let node = Element::new(Self::tag())
+            .with_attr("type", self.slide_type)
+            .with_attr("number", self.number)
+            .with_optional_attr("line-type", self.line_type)
+            .with_optional_text(self.text.as_ref());
+        self.unknown.write(node)
+    }
+}
+
+xml_enum! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum Articulation {
+        Accent => "accent",
+        StrongAccent => "strong-accent",
+        Staccato => "staccato",
+        Tenuto => "tenuto",
+        DetachedLegato => "detached-legato",
+        Staccatissimo => "staccatissimo",
+        Spiccato => "spiccato",
+        Scoop => "scoop",
+        Plop => "plop",
+        Doit => "doit",
+        Falloff => "falloff",
+        Stress => "stress",
+        Unstress => "unstress",
+        SoftAccent => "soft-accent",
+        BreathMark => "breath-mark",
+        Caesura => "caesura",
+    }
+}
+
+impl Articulation {
+    /// Whether the mark interrupts the sound after the note
+    pub fn is_break(&self) -> bool {
+        matches!(self, Articulation::BreathMark | Articulation::Caesura)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArticulationMark {
+    pub articulation: Articulation,
+    pub placement: Option<AboveBelow>,
+    /// Symbol of a breath mark or caesura, e.g. "comma" or "thick"
+    pub text: Option<String>,
+    pub position: Position,
+    pub unknown: Unknown,
+}
+
+impl ToNode for ArticulationMark {
+    fn to_node(&self) -> Element {
+        let node = Element::new(self.articulation.to_string())
+            .with_optional_attr("placement", self.placement);
+        let node = self
+            .position
+            .write_attrs(node)
+            .with_optional_text(self.text.as_ref());
+        self.unknown.write(node)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Articulations {
+    pub marks: Vec<ArticulationMark>,
+    pub unknown: Unknown,
+}
+
+impl FromNode for Articulations {
+    fn tag() -> &'static str {
+        "articulations"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        Ok(Articulations {
+            marks: node
+                .children()
+                .filter_map(|c| Some((c, c.tag_name().name().parse().ok()?)))
+                .map(|(c, articulation)| {
+                    Ok(ArticulationMark {
+                        articulation,
+                        placement: parse_optional_attr(&c, "placement")?,
+                        text: parse_optional_text(&c),
+                        position: Position::parse(&c)?,
+                        unknown: Unknown::parse_attrs(
+                            &c,
+                            &[
+                                "placement",
+                                "default-x",
+                                "default-y",
+                                "relative-x",
+                                "relative-y",
+                            ],
+                        ),
+                    })
+                })
+                .collect::<Result<_>>()?,
+            unknown: Unknown::parse(
+                node,
+                &[],
+                &[
+                    "accent",
+                    "strong-accent",
+                    "staccato",
+                    "tenuto",
+                    "detached-legato",
+                    "staccatissimo",
+                    "spiccato",
+                    "scoop",
+                    "plop",
+                    "doit",
+                    "falloff",
+                    "stress",
+                    "unstress",
+                    "soft-accent",
+                    "breath-mark",
+                    "caesura",
+                ],
+            ),
+        })
+    }
+}
+
+impl ToNode for Articulations {
+    fn to_node(&self) -> Element {
+        let node = Element::new(Self::tag()).with_children(children_to_nodes(&self.marks));
+        self.unknown.write(node)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WavyLine {
+    pub wavy_line_type: StartStopContinue,
+    pub number: Option<NumberLevel>,
+    pub placement: Option<AboveBelow>,
+    pub unknown: Unknown,
+}
+
+impl FromNode for WavyLine {
+    fn tag() -> &'static str {
+        "wavy-line"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        Ok(WavyLine {
+            wavy_line_type: parse_attr(node, "type")?,
+            number: parse_optional_attr(node, "number")?,
+            placement: parse_optional_attr(node, "placement")?,
+            unknown: Unknown::parse(node, &["type", "number", "placement"], &[]),
+        })
+    }
+}
+
+impl ToNode for WavyLine {
+    fn to_node(&self) -> Element {
+        let node = Element::new(Self::tag())
+            .with_attr("type", self.wavy_line_type)
+            .with_optional_attr("number", self.number)
+            .with_optional_attr("placement", self.placement);
+        self.unknown.write(node)
+    }
+}
+
+xml_enum! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum TremoloType {
+        /// Repeated single note
+        Single => "single",
+        /// First note of a fingered tremolo between two notes
+        Start => "start",
+        Stop => "stop",
+        /// Unmeasured buzz roll
+        Unmeasured => "unmeasured",
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Tremolo {
+    pub tremolo_type: TremoloType,
+    /// Number of tremolo strokes, from 0 to 8
+    pub marks: u8,
+    pub unknown: Unknown,
+}
+
+impl FromNode for Tremolo {
+    fn tag() -> &'static str {
+        "tremolo"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        Ok(Tremolo {
+            tremolo_type: parse_optional_attr(node, "type")?.unwrap_or(TremoloType::Single),
+            marks: parse_optional_text(node).map_or(Ok(0), |_| parse_text(node, Self::tag()))?,
+            // the placement belongs to the ornament mark
+            unknown: Unknown::parse_attrs(node, &["type", "placement"]),
+        })
+    }
+}
+
+impl ToNode for Tremolo {
+    fn to_node(&self) -> Element {
+        let node = Element::new(Self::tag())
+            .with_attr("type", self.tremolo_type)
+            .with_text(self.marks);
+        self.unknown.write(node)
+    }
+}
+
+impl Tremolo {
+    /// Duration of each repeated stroke, none for an unmeasured tremolo
+    ///
+    /// Every mark halves the eighth note, so 3 marks play 32nd notes.
+    pub fn stroke_duration(&self, divisions: u32) -> Option<u32> {
+        match self.tremolo_type {
+            TremoloType::Unmeasured => None,
+            _ => Some(divisions >> self.marks.min(31)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Ornament {
+    TrillMark,
+    Turn {
+        slash: Option<YesNo>,
+    },
+    DelayedTurn {
+        slash: Option<YesNo>,
+    },
+    InvertedTurn {
+        slash: Option<YesNo>,
+    },
+    DelayedInvertedTurn {
+        slash: Option<YesNo>,
+    },
+    VerticalTurn,
+    InvertedVerticalTurn,
+    Shake,
+    WavyLine(WavyLine),
+    Mordent {
+        long: Option<YesNo>,
+        approach: Option<AboveBelow>,
+        departure: Option<AboveBelow>,
+    },
+    InvertedMordent {
+        long: Option<YesNo>,
+        approach: Option<AboveBelow>,
+        departure: Option<AboveBelow>,
+    },
+    Schleifer,
+    Haydn,
+    Tremolo(Tremolo),
+}
+
+impl Ornament {
+    /// Parse an ornament from its element, none if the element is not an ornament
+    fn parse(node: &Node) -> Result<Option<Self>> {
+        Ok(Some(match node.tag_name().name() {
+            "trill-mark" => Ornament::TrillMark,
+            "turn" => Ornament::Turn {
+                slash: parse_optional_attr(node, "slash")?,
+            },
+            "delayed-turn" => Ornament::DelayedTurn {
+                slash: parse_optional_attr(node, "slash")?,
+            },
+            "inverted-turn" => Ornament::InvertedTurn {
+                slash: parse_optional_attr(node, "slash")?,
+            },
+            "delayed-inverted-turn" => Ornament::DelayedInvertedTurn {
+                slash: parse_optional_attr(node, "slash")?,
+            },
+            "vertical-turn" => Ornament::VerticalTurn,
+            "inverted-vertical-turn" => Ornament::InvertedVerticalTurn,
+            "shake" => Ornament::Shake,
+            "wavy-line" => Ornament::WavyLine(WavyLine::from_node(node)?),
+            "mordent" => Ornament::Mordent {
+                long: parse_optional_attr(node, "long")?,
+                approach: parse_optional_attr(node, "approach")?,
+                departure: parse_optional_attr(node, "departure")?,
+            },
+            "inverted-mordent" => Ornament::InvertedMordent {
+                long: parse_optional_attr(node, "long")?,
+                approach: parse_optional_attr(node, "approach")?,
+                departure: parse_optional_attr(node, "departure")?,
+            },
+            "schleifer" => Ornament::Schleifer,
+            "haydn" => Ornament::Haydn,
+            "tremolo" => Ornament::Tremolo(Tremolo::from_node(node)?),
+            _ => return Ok(None),
+        }))
+    }
+
+    fn to_node(&self) -> Element {
+        match self {
+            Ornament::TrillMark => Element::new("trill-mark"),
+            Ornament::Turn { slash } => Element::new("turn").with_optional_attr("slash", *slash),
+            Ornament::DelayedTurn { slash } => {
+                Element::new("delayed-turn").with_optional_attr("slash", *slash)
+            }
+            Ornament::InvertedTurn { slash } => {
+                Element::new("inverted-turn").with_optional_attr("slash", *slash)
+            }
+            Ornament::DelayedInvertedTurn { slash } => {
+                Element::new("delayed-inverted-turn").with_optional_attr("slash", *slash)
+            }
+            Ornament::VerticalTurn => Element::new("vertical-turn"),
+            Ornament::InvertedVerticalTurn => Element::new("inverted-vertical-turn"),
+            Ornament::Shake => Element::new("shake"),
+            Ornament::WavyLine(w) => w.to_node(),
+            Ornament::Mordent {
+                long,
+                approach,
+                departure,
+            } => Element::new("mordent")
+                .with_optional_attr("long", *long)
+                .with_optional_attr("approach", *approach)
+                .with_optional_attr("departure", *departure),
+            Ornament::InvertedMordent {
+                long,
+                approach,
+                departure,
+            } => Element::new("inverted-mordent")
+                .with_optional_attr("long", *long)
+                .with_optional_attr("approach", *approach)
+                .with_optional_attr("departure", *departure),
+            Ornament::Schleifer => Element::new("schleifer"),
+            Ornament::Haydn => Element::new("haydn"),
+            Ornament::Tremolo(t) => t.to_node(),
+        }
+    }
+}
+
+/// Accidental shown above or below a note or ornament, e.g. "sharp"
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccidentalMark {
+    pub value: String,
+    pub placement: Option<AboveBelow>,
+    pub unknown: Unknown,
+}
+
+impl FromNode for AccidentalMark {
+    fn tag() -> &'static str {
+        "accidental-mark"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        Ok(AccidentalMark {
+            value: parse_text(node, Self::tag())?,
+            placement: parse_optional_attr(node, "placement")?,
+            unknown: Unknown::parse_attrs(node, &["placement"]),
+        })
+    }
+}
+
+impl ToNode for AccidentalMark {
+    fn to_node(&self) -> Element {
+        let node = Element::new(Self::tag())
+            .with_optional_attr("placement", self.placement)
+            .with_text(&self.value);
+        self.unknown.write(node)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OrnamentMark {
+    pub ornament: Ornament,
+    pub placement: Option<AboveBelow>,
+    /// Accidentals following the ornament, e.g. the sharp of a trill
+    pub accidental_marks: Vec<AccidentalMark>,
+    /// Kept on the wavy line or tremolo for these ornaments
+    pub unknown: Unknown,
+}
+
+impl ToNode for OrnamentMark {
+    fn to_node(&self) -> Element {
+        let node = self.ornament.to_node();
+
+        // the wavy line carries its own placement
+        let node = match self.ornament {
+            Ornament::WavyLine(_) => node,
+            _ => node.with_optional_attr("placement", self.placement),
+        };
+        self.unknown.write(node)
+    }
+}
+
+impl OrnamentMark {
+    fn to_nodes(&self) -> impl Iterator<Item = Element> + '_ {
+        std::iter::once(self.to_node()).chain(children_to_nodes(&self.accidental_marks))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ornaments {
+    pub marks: Vec<OrnamentMark>,
+    pub unknown: Unknown,
+}
+
+impl FromNode for Ornaments {
+    fn tag() -> &'static str {
+        "ornaments"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        let mut marks: Vec<OrnamentMark> = Vec::new();
+        for c in node.children() {
+            if let Some(ornament) = Ornament::parse(&c)? {
+                let unknown = match ornament {
+                    Ornament::WavyLine(_) | Ornament::Tremolo(_) => Unknown::default(),
+                    _ => Unknown::parse(
+                        &c,
+                        &["placement", "slash", "long", "approach", "departure"],
+                        &[],
+                    ),
+                };
+                marks.push(OrnamentMark {
+                    ornament,
+                    placement: parse_optional_attr(&c, "placement")?,
+                    accidental_marks: Vec::new(),
+                    unknown,
+                });
+            } else if c.tag_name().name() == AccidentalMark::tag() {
+                // an accidental mark belongs to the ornament preceding it
+                if let Some(mark) = marks.last_mut() {
+                    mark.accidental_marks.push(AccidentalMark::from_node(&c)?);
+                }
+            }
+        }
+
+        Ok(Ornaments {
+            marks,
+            unknown: Unknown::parse(
+                node,
+                &[],
+                &[
+                    "trill-mark",
+                    "turn",
+                    "delayed-turn",
+                    "inverted-turn",
+                    "delayed-inverted-turn",
+                    "vertical-turn",
+                    "inverted-vertical-turn",
+                    "shake",
+                    "wavy-line",
+                    "mordent",
+                    "inverted-mordent",
+                    "schleifer",
+                    "haydn",
+                    "tremolo",
+                    "accidental-mark",
+                ],
+            ),
+        })
+    }
+}
+
+impl ToNode for Ornaments {
+    fn to_node(&self) -> Element {
+        let node = Element::new(Self::tag())
+            .with_children(self.marks.iter().flat_map(OrnamentMark::to_nodes));
+        self.unknown.write(node)
+    }
+}
+
+xml_enum! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum UprightInverted {
+        Upright => "upright",
+        Inverted => "inverted",
+    }
+}
+
+xml_enum! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum FermataShape {
+        Normal => "normal",
+        Angled => "angled",
+        Square => "square",
+        DoubleAngled => "double-angled",
+        DoubleSquare => "double-square",
+        DoubleDot => "double-dot",
+        HalfCurve => "half-curve",
+        Curlew => "curlew",
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Fermata {
+    pub fermata_type: Option<UprightInverted>,
+    pub shape: FermataShape,
+    pub position: Position,
+    pub unknown: Unknown,
+}
+
+impl FromNode for Fermata {
+    fn tag() -> &'static str {
+        "fermata"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        Ok(Fermata {
+            fermata_type: parse_optional_attr(node, "type")?,
+            // an empty fermata is a normal one
+            shape: match node.text().map(str::trim).filter(|t| !t.is_empty()) {
+                Some(_) => parse_text(node, Self::tag())?,
+                None => FermataShape::Normal,
+            },
+            position: Position::parse(node)?,
+            unknown: Unknown::parse_attrs(
+                node,
+                &["type", "default-x", "default-y", "relative-x", "relative-y"],
+            ),
+        })
+    }
+}
+
+impl ToNode for Fermata {
+    fn to_node(&self) -> Element {
+        let node = Element::new(Self::tag()).with_optional_attr("type", self.fermata_type);
+        let node = self.position.write_attrs(node).with_text(self.shape);
+        self.unknown.write(node)
+    }
+}
+
+/// Rolled chord, notes sharing a number are rolled together across staves
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Arpeggiate {
+    pub number: Option<NumberLevel>,
+    pub direction: Option<UpDown>,
+    pub unknown: Unknown,
+}
+
+impl FromNode for Arpeggiate {
+    fn tag() -> &'static str {
+        "arpeggiate"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        Ok(Arpeggiate {
+            number: parse_optional_attr(node, "number")?,
+            direction: parse_optional_attr(node, "direction")?,
+            unknown: Unknown::parse(node, &["number", "direction"], &[]),
+        })
+    }
+}
+
+impl ToNode for Arpeggiate {
+    fn to_node(&self) -> Element {
+        let node = Element::new(Self::tag())
+            .with_optional_attr("number", self.number)
+            .with_optional_attr("direction", self.direction);
+        self.unknown.write(node)
+    }
+}
+
+/// Bracket marking a chord not to be rolled, placed at its top or bottom note
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NonArpeggiate {
+    pub non_arpeggiate_type: TopBottom,
+    pub number: Option<NumberLevel>,
+    pub unknown: Unknown,
+}
+
+impl FromNode for NonArpeggiate {
+    fn tag() -> &'static str {
+        "non-arpeggiate"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        Ok(NonArpeggiate {
+            non_arpeggiate_type: parse_attr(node, "type")?,
+            number: parse_optional_attr(node, "number")?,
+            unknown: Unknown::parse(node, &["type", "number"], &[]),
+        })
+    }
+}
+
+impl ToNode for NonArpeggiate {
+    fn to_node(&self) -> Element {
+        let node = Element::new(Self::tag())
+            .with_attr("type", self.non_arpeggiate_type)
+            .with_optional_attr("number", self.number);
+        self.unknown.write(node)
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Notations {
+    pub tied: Vec<Tied>,
+    pub slur: Vec<Slur>,
+    pub tuplet: Vec<Tuplet>,
+    pub glissando: Vec<Glissando>,
+    pub slide: Vec<Slide>,
+    pub ornaments: Vec<Ornaments>,
+    /// Indications of all `<technical>` elements
+    pub technical: Vec<Technical>,
+    pub articulations: Vec<Articulations>,
+    pub dynamics: Vec<Dynamics>,
+    pub fermata: Vec<Fermata>,
+    pub arpeggiate: Option<Arpeggiate>,
+    pub non_arpeggiate: Option<NonArpeggiate>,
+    pub accidental_mark: Vec<AccidentalMark>,
+    pub unknown: Unknown,
+}
+
+impl FromNode for Notations {
+    fn tag() -> &'static str {
+        "notations"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        Ok(Notations {
+            tied: parse_children(node)?,
+            slur: parse_children(node)?,
+            tuplet: parse_children(node)?,
+            glissando: parse_children(node)?,
+            slide: parse_children(node)?,
+            ornaments: parse_children(node)?,
+            technical: node
+                .children()
+                .filter(|c| c.tag_name().name() == "technical")
+                .flat_map(|c| c.children())
+                .filter_map(|c| Technical::parse(&c).transpose())
+                .collect::<Result<_>>()?,
+            articulations: parse_children(node)?,
+            dynamics: parse_children(node)?,
+            fermata: parse_children(node)?,
+            arpeggiate: parse_option_chd(node)?,
+            non_arpeggiate: parse_option_chd(node)?,
+            accidental_mark: parse_children(node)?,
+            unknown: Unknown::parse(
+                node,
+                &[],
+                &[
+                    "tied",
+                    "slur",
+                    "tuplet",
+                    "glissando",
+                    "slide",
+                    "ornaments",
+                    "technical",
+                    "articulations",
+                    "dynamics",
+                    "fermata",
+                    "arpeggiate",
+                    "non-arpeggiate",
+                    "accidental-mark",
+                ],
+            ),
+        })
+    }
+}
+
+impl ToNode for Notations {
+    fn to_node(&self) -> Element {
+        let node = Element::new(Self::tag())
+            .with_children(children_to_nodes(&self.tied))
+            .with_children(children_to_nodes(&self.slur))
+            .with_children(children_to_nodes(&self.tuplet))
+            .with_children(children_to_nodes(&self.glissando))
+            .with_children(children_to_nodes(&self.slide))
+            .with_children(children_to_nodes(&self.ornaments))
+            .with_optional_child((!self.technical.is_empty()).then(|| {
+                Element::new("technical").with_children(children_to_nodes(&self.technical))
+            }))
+            .with_children(children_to_nodes(&self.articulations))
+            .with_children(children_to_nodes(&self.dynamics))
+            .with_children(children_to_nodes(&self.fermata))
+            .with_optional_child(self.arpeggiate.as_ref().map(ToNode::to_node))
+            .with_optional_child(self.non_arpeggiate.as_ref().map(ToNode::to_node))
+            .with_children(children_to_nodes(&self.accidental_mark));
+        self.unknown.write(node)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use roxmltree::Document;
+
+    use super::*;
+    use crate::score::Tenths;
+
+    #[test]
+    fn notations_slur_ok() {
+        let xml = r##"
+            <notations>
+                <slur type="start" placement="above" />
+                <slur type="stop" number="2" color="#0000FF" />
+            </notations>"##;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let notations = Notations::from_node(&node);
+        assert!(notations.is_ok());
+        assert_eq!(
+            notations.unwrap().slur,
+            vec![
+                Slur {
+                    slur_type: StartStopContinue::Start,
+                    number: NumberLevel(1),
+                    placement: Some(AboveBelow::Above),
+                    color: None,
+                    position: Position::default(),
+                    unknown: Unknown::default()
+                },
+                Slur {
+                    slur_type: StartStopContinue::Stop,
+                    number: NumberLevel(2),
+                    placement: None,
+                    color: Some(Color {
+                        alpha: None,
+                        red: 0,
+                        green: 0,
+                        blue: 0xFF
+                    }),
+                    position: Position::default(),
+                    unknown: Unknown::default()
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn notations_tied_ok() {
+        let xml = r#"
+            <notations>
+                <tied type="start" number="1" line-type="dashed" orientation="under" bezier-x="12" bezier-y="-8" />
+                <tied type="let-ring" />
+            </notations>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let notations = Notations::from_node(&node).unwrap();
+        assert_eq!(
+            notations.tied,
+            vec![
+                Tied {
+                    tied_type: TiedType::Start,
+                    number: Some(NumberLevel(1)),
+                    line_type: Some(LineType::Dashed),
+                    orientation: Some(OverUnder::Under),
+                    position: Position::default(),
+                    bezier: Bezier {
+                        x: Some(Tenths(12.)),
+                        y: Some(Tenths(-8.)),
+                        ..Default::default()
+                    },
+                    color: None,
+                    unknown: Unknown::default()
+                },
+                Tied {
+                    tied_type: TiedType::LetRing,
+                    number: None,
+                    line_type: None,
+                    orientation: None,
+                    position: Position::default(),
+                    bezier: Bezier::default(),
+                    color: None,
+                    unknown: Unknown::default()
+                }
+            ]
+        );
+        assert_eq!(notations.to_node(), Element::from(&node));
+    }
+
+    #[test]
+    fn notations_tuplet_ok() {
+        let xml = r#"
+            <notations>
+                <tuplet type="start" bracket="yes" />
+            </notations>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let notations = Notations::from_node(&node).unwrap();
+        assert_eq!(
+            notations.tuplet,
+            vec![Tuplet {
+                tuplet_type: StartStop::Start,
+                number: None,
+                bracket: Some(YesNo::Yes),
+                placement: None,
+                unknown: Unknown::default()
+            }]
+        );
+    }
+
+    #[test]
+    fn notations_articulations_ok() {
+        let xml = r#"
+            <notations>
+                <articulations>
+                    <staccato placement="below" />
+                    <accent />
+                </articulations>
+            </notations>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let notations = Notations::from_node(&node).unwrap();
+        assert_eq!(
+            notations.articulations,
+            vec![Articulations {
+                marks: vec![
+                    ArticulationMark {
+                        articulation: Articulation::Staccato,
+                        placement: Some(AboveBelow::Below),
+                        text: None,
+                        position: Position::default(),
+                        unknown: Unknown::default()
+                    },
+                    ArticulationMark {
+                        articulation: Articulation::Accent,
+                        placement: None,
+                        text: None,
+                        position: Position::default(),
+                        unknown: Unknown::default()
+                    }
+                ],
+                unknown: Unknown::default()
+            }]
+        );
+    }
+
+    #[test]
+    fn notations_breath_mark_caesura_ok() {
+        let xml = r#"
+            <notations>
+                <articulations>
+                    <breath-mark placement="above">comma</breath-mark>
+                    <caesura />
+                </articulations>
+            </notations>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let notations = Notations::from_node(&node).unwrap();
+        let marks = &notations.articulations[0].marks;
+        assert_eq!(
+            marks[0],
+            ArticulationMark {
+                articulation: Articulation::BreathMark,
+                placement: Some(AboveBelow::Above),
+                text: Some("comma".to_owned()),
+                position: Position::default(),
+                unknown: Unknown::default()
+            }
+        );
+        assert_eq!(marks[1].articulation, Articulation::Caesura);
+        assert!(marks.iter().all(|m| m.articulation.is_break()));
+        assert_eq!(
+            marks[0].to_node(),
+            Element::new("breath-mark")
+                .with_attr("placement", "above")
+                .with_text("comma")
+        );
+    }
+
+    #[test]
+    fn notations_ornaments_ok() {
+        let xml = r#"
+            <notations>
+                <ornaments>
+                    <trill-mark placement="above" />
+                    <wavy-line type="start" number="1" />
+                    <inverted-mordent long="yes" />
+                    <turn slash="yes" />
+                </ornaments>
+            </notations>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let notations = Notations::from_node(&node).unwrap();
+        assert_eq!(
+            notations.ornaments,
+            vec![Ornaments {
+                marks: vec![
+                    OrnamentMark {
+                        ornament: Ornament::TrillMark,
+                        placement: Some(AboveBelow::Above),
+                        accidental_marks: vec![],
+                        unknown: Unknown::default()
+                    },
+                    OrnamentMark {
+                        ornament: Ornament::WavyLine(WavyLine {
+                            wavy_line_type: StartStopContinue::Start,
+                            number: Some(NumberLevel(1)),
+                            placement: None,
+                            unknown: Unknown::default()
+                        }),
+                        placement: None,
+                        accidental_marks: vec![],
+                        unknown: Unknown::default()
+                    },
+                    OrnamentMark {
+                        ornament: Ornament::InvertedMordent {
+                            long: Some(YesNo::Yes),
+                            approach: None,
+                            departure: None
+                        },
+                        placement: None,
+                        accidental_marks: vec![],
+                        unknown: Unknown::default()
+                    },
+                    OrnamentMark {
+                        ornament: Ornament::Turn {
+                            slash: Some(YesNo::Yes)
+                        },
+                        placement: None,
+                        accidental_marks: vec![],
+                        unknown: Unknown::default()
+                    }
+                ],
+                unknown: Unknown::default()
+            }]
+        );
+    }
+
+    #[test]
+    fn notations_fermata_ok() {
+        let xml = r#"
+            <notations>
+                <fermata type="upright" />
+                <fermata type="inverted">angled</fermata>
+            </notations>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let notations = Notations::from_node(&node).unwrap();
+        assert_eq!(
+            notations.fermata,
+            vec![
+                Fermata {
+                    fermata_type: Some(UprightInverted::Upright),
+                    shape: FermataShape::Normal,
+                    position: Position::default(),
+                    unknown: Unknown::default()
+                },
+                Fermata {
+                    fermata_type: Some(UprightInverted::Inverted),
+                    shape: FermataShape::Angled,
+                    position: Position::default(),
+                    unknown: Unknown::default()
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn notations_arpeggiate_ok() {
+        let xml = r#"
+            <notations>
+                <arpeggiate number="1" direction="down" />
+            </notations>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let notations = Notations::from_node(&node).unwrap();
+        assert_eq!(
+            notations.arpeggiate,
+            Some(Arpeggiate {
+                number: Some(NumberLevel(1)),
+                direction: Some(UpDown::Down),
+                unknown: Unknown::default()
+            })
+        );
+        assert_eq!(notations.non_arpeggiate, None);
+    }
+
+    #[test]
+    fn notations_non_arpeggiate_ok() {
+        let xml = r#"
+            <notations>
+                <non-arpeggiate type="bottom" />
+            </notations>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let notations = Notations::from_node(&node).unwrap();
+        assert_eq!(
+            notations.non_arpeggiate,
+            Some(NonArpeggiate {
+                non_arpeggiate_type: TopBottom::Bottom,
+                number: None,
+                unknown: Unknown::default()
+            })
+        );
+    }
+
+    #[test]
+    fn notations_glissando_slide_ok() {
+        let xml = r#"
+            <notations>
+                <glissando type="start" line-type="wavy">gliss.</glissando>
+                <slide type="stop" number="2" />
+            </notations>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let notations = Notations::from_node(&node).unwrap();
+        assert_eq!(
+            notations.glissando,
+            vec![Glissando {
+                glissando_type: StartStop::Start,
+                number: NumberLevel(1),
+                line_type: Some(LineType::Wavy),
+                text: Some("gliss.".to_owned()),
+                unknown: Unknown::default()
+            }]
+        );
+        assert_eq!(
+            notations.slide,
+            vec![Slide {
+                slide_type: StartStop::Stop,
+                number: NumberLevel(2),
+                line_type: None,
+                text: None,
+                unknown: Unknown::default()
+            }]
+        );
+    }
+
+    #[test]
+    fn notations_tremolo_ok() {
+        let xml = r#"
+            <notations>
+                <ornaments>
+                    <tremolo type="start">2</tremolo>
+                    <tremolo placement="above">3</tremolo>
+                    <tremolo type="unmeasured">0</tremolo>
+                </ornaments>
+            </notations>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let notations = Notations::from_node(&node).unwrap();
+        let marks = &notations.ornaments[0].marks;
+        assert_eq!(
+            marks[0].ornament,
+            Ornament::Tremolo(Tremolo {
+                tremolo_type: TremoloType::Start,
+                marks: 2,
+                unknown: Unknown::default()
+            })
+        );
+        assert_eq!(marks[1].placement, Some(AboveBelow::Above));
+
+        let tremolos: Vec<_> = marks
+            .iter()
+            .filter_map(|m| match &m.ornament {
+                Ornament::Tremolo(t) => t.stroke_duration(8),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(tremolos, vec![2, 1]);
+    }
+
+    #[test]
+    fn notations_tremolo_round_trip_ok() {
+        let xml = r#"
+            <notations>
+                <ornaments>
+                    <tremolo type="single" placement="below">3</tremolo>
+                    <turn placement="above" />
+                </ornaments>
+            </notations>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let notations = Notations::from_node(&node).unwrap();
+        let marks = &notations.ornaments[0].marks;
+        assert_eq!(marks[0].placement, Some(AboveBelow::Below));
+        assert_eq!(notations.to_node(), Element::from(&node));
+    }
+
+    #[test]
+    fn notations_accidental_mark_ok() {
+        let xml = r#"
+            <notations>
+                <ornaments>
+                    <trill-mark />
+                    <accidental-mark placement="above">sharp</accidental-mark>
+                </ornaments>
+                <accidental-mark>natural</accidental-mark>
+            </notations>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let notations = Notations::from_node(&node).unwrap();
+        assert_eq!(
+            notations.ornaments[0].marks,
+            vec![OrnamentMark {
+                ornament: Ornament::TrillMark,
+                placement: None,
+                accidental_marks: vec![AccidentalMark {
+                    value: "sharp".to_owned(),
+                    placement: Some(AboveBelow::Above),
+                    unknown: Unknown::default()
+                }],
+                unknown: Unknown::default()
+            }]
+        );
+        assert_eq!(
+            notations.accidental_mark,
+            vec![AccidentalMark {
+                value: "natural".to_owned(),
+                placement: None,
+                unknown: Unknown::default()
+            }]
+        );
+
+        let el = notations.ornaments[0].to_node();
+        assert_eq!(el.children.len(), 2);
+        assert_eq!(el.children[1].name, "accidental-mark");
+    }
+}
@@ -0,0 +1,1706 @@
+use std::num::NonZeroU32;
+
+use roxmltree::Node;
+
+use super::{
+    children_to_nodes, count_chd, parse_attr, parse_chd_text, parse_children, parse_option_chd,
+    parse_optional_attr, parse_optional_chd_text, parse_text, ArticulationMark, Color, Duration,
+    Editorial, FromNode, Listen, Lyric, Notations, Play, Position, Printout, StartStop, Step,
+    SymbolSize, Time, ToNode, Transpose, Unknown, YesNo,
+};
+use crate::{
+    error::{
+        Error::{ExclusiveNodeFound, ExclusiveNodeGroupNotFound},
+        Result,
+    },
+    writer::Element,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rest {
+    /// Rest lasting the whole measure regardless of the time signature
+    pub measure: bool,
+    /// Vertical placement on the staff, e.g. to keep voices apart
+    pub display_step: Option<Step>,
+    pub display_octave: Option<u8>,
+    pub unknown: Unknown,
+}
+
+impl FromNode for Rest {
+    fn tag() -> &'static str {
+        "rest"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        Ok(Rest {
+            measure: parse_optional_attr(node, "measure")? == Some(YesNo::Yes),
+            display_step: parse_optional_chd_text(node, "display-step")?,
+            display_octave: parse_optional_chd_text(node, "display-octave")?,
+            unknown: Unknown::parse(node, &["measure"], &["display-step", "display-octave"]),
+        })
+    }
+}
+
+impl ToNode for Rest {
+    fn to_node(&self) -> Element {
+        let node = Element::new(Self::tag())
+            .with_optional_attr("measure", self.measure.then_some(YesNo::Yes))
+            .with_optional_chd_text("display-step", self.display_step)
+            .with_optional_chd_text("display-octave", self.display_octave);
+        self.unknown.write(node)
+    }
+}
+
+const STEPS: [Step; 7] = [
+    Step::C,
+    Step::D,
+    Step::E,
+    Step::F,
+    Step::G,
+    Step::A,
+    Step::B,
+];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Pitch {
+    pub step: Step,
+    /// Semitones, negative for flats and fractional for microtones
+    pub alter: f64,
+    pub octave: u8,
+    pub unknown: Unknown,
+}
+
+impl FromNode for Pitch {
+    fn tag() -> &'static str {
+        "pitch"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        Ok(Pitch {
+            step: parse_chd_text(node, "step")?,
+            alter: parse_optional_chd_text(node, "alter")?.unwrap_or(0.),
+            octave: parse_chd_text(node, "octave")?,
+            unknown: Unknown::parse(node, &[], &["step", "alter", "octave"]),
+        })
+    }
+}
+
+impl ToNode for Pitch {
+    fn to_node(&self) -> Element {
+        let node = Element::new(Self::tag())
+            .with_chd_text("step", self.step)
+            .with_optional_chd_text("alter", Some(self.alter).filter(|a| *a != 0.))
+            .with_chd_text("octave", self.octave);
+        self.unknown.write(node)
+    }
+}
+
+/// Semitones above C of the natural steps C, D, E, F, G, A, B
+const STEP_SEMITONES: [i32; 7] = [0, 2, 4, 5, 7, 9, 11];
+
+impl Pitch {
+    /// Jianpu number of the pitch, ignoring its alter and octave
+    pub fn to_jianpu(&self) -> u8 {
+        self.step.to_jianpu()
+    }
+
+    /// Semitones above C-1, fractional for microtones, e.g. 60 for middle C
+    fn semitones(&self) -> f64 {
+        12. * (self.octave as f64 + 1.) + STEP_SEMITONES[self.step as usize] as f64 + self.alter
+    }
+
+    /// MIDI note number, microtones rounded to the nearest semitone
+    pub fn midi_note(&self) -> u8 {
+        self.semitones().round().clamp(0., 127.) as u8
+    }
+
+    /// Frequency in hertz in equal temperament tuned to the given A4
+    pub fn frequency(&self, a4_hz: f64) -> f64 {
+        a4_hz * 2f64.powf((self.semitones() - 69.) / 12.)
+    }
+
+    /// Pitch transposed by the interval of a transposing instrument,
+    /// none if it leaves the octaves a pitch can be written in
+    pub fn transposed(&self, transpose: &Transpose) -> Option<Pitch> {
+        let octave_change = transpose.octave_change.unwrap_or(0) as i32;
+        let diatonic = transpose.diatonic.map_or_else(
+            || (transpose.chromatic * 7. / 12.).round() as i32,
+            i32::from,
+        );
+
+        let index = self.step as i32;
+        let octave = self.octave as i32 + (index + diatonic).div_euclid(7) + octave_change;
+        let step = (index + diatonic).rem_euclid(7);
+
+        let written = STEP_SEMITONES[index as usize] as f64 + self.alter;
+        let sounding = written + transpose.chromatic + 12. * octave_change as f64;
+        let natural =
+            STEP_SEMITONES[step as usize] as f64 + 12. * (octave - self.octave as i32) as f64;
+
+        Some(Pitch {
+            step: STEPS[step as usize],
+            alter: sounding - natural,
+            octave: u8::try_from(octave).ok()?,
+            unknown: Unknown::default(),
+        })
+    }
+}
+
+/// Percussion note positioned on the staff without a definite pitch
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Unpitched {
+    pub display_step: Option<Step>,
+    pub display_octave: Option<u8>,
+    pub unknown: Unknown,
+}
+
+impl FromNode for Unpitched {
+    fn tag() -> &'static str {
+        "unpitched"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        Ok(Unpitched {
+            display_step: parse_optional_chd_text(node, "display-step")?,
+            display_octave: parse_optional_chd_text(node, "display-octave")?,
+            unknown: Unknown::parse(node, &[], &["display-step", "display-octave"]),
+        })
+    }
+}
+
+impl ToNode for Unpitched {
+    fn to_node(&self) -> Element {
+        let node = Element::new(Self::tag())
+            .with_optional_chd_text("display-step", self.display_step)
+            .with_optional_chd_text("display-octave", self.display_octave);
+        self.unknown.write(node)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum NoteType {
+    Rest(Rest),
+    Pitch(Pitch),
+    Unpitched(Unpitched),
+}
+
+impl NoteType {
+    fn parse(node: &Node) -> Result<Self> {
+        let rest = parse_option_chd(node)?.map(NoteType::Rest);
+        let pitch = parse_option_chd(node)?.map(NoteType::Pitch);
+        let unpitched = parse_option_chd(node)?.map(NoteType::Unpitched);
+
+        let mut note_types = [rest, pitch, unpitched].into_iter().flatten();
+        match (note_types.next(), note_types.next()) {
+            (Some(ty), None) => Ok(ty),
+            (Some(_), Some(_)) => Err(ExclusiveNodeFound {
+                tags: vec![Rest::tag(), Pitch::tag(), Unpitched::tag()],
+                parent_tag: Note::tag(),
+            }),
+            (None, _) => Err(ExclusiveNodeGroupNotFound {
+                tags: vec![Rest::tag(), Pitch::tag(), Unpitched::tag()],
+                parent_tag: Note::tag(),
+            }),
+        }
+    }
+
+    fn to_node(&self) -> Element {
+        match self {
+            NoteType::Rest(r) => r.to_node(),
+            NoteType::Pitch(p) => p.to_node(),
+            NoteType::Unpitched(u) => u.to_node(),
+        }
+    }
+}
+
+/// Playback tie, the notated tie is a tied notation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Tie {
+    pub tie_type: StartStop,
+    pub unknown: Unknown,
+}
+
+impl FromNode for Tie {
+    fn tag() -> &'static str {
+        "tie"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        Ok(Tie {
+            tie_type: parse_attr(node, "type")?,
+            unknown: Unknown::parse(node, &["type"], &[]),
+        })
+    }
+}
+
+impl ToNode for Tie {
+    fn to_node(&self) -> Element {
+        let node = Element::new(Self::tag()).with_attr("type", self.tie_type);
+        self.unknown.write(node)
+    }
+}
+
+/// Tuplet ratio, e.g. 3 actual notes in the time of 2 normal notes for a triplet
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TimeModification {
+    pub actual_notes: u32,
+    pub normal_notes: u32,
+    pub normal_type: Option<NoteTypeValue>,
+    pub normal_dot: u8,
+    pub unknown: Unknown,
+}
+
+impl FromNode for TimeModification {
+    fn tag() -> &'static str {
+        "time-modification"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        Ok(TimeModification {
+            actual_notes: parse_chd_text(node, "actual-notes")?,
+            normal_notes: parse_chd_text(node, "normal-notes")?,
+            normal_type: parse_optional_chd_text(node, "normal-type")?,
+            normal_dot: count_chd(node, "normal-dot") as u8,
+            unknown: Unknown::parse(
+                node,
+                &[],
+                &["actual-notes", "normal-notes", "normal-type", "normal-dot"],
+            ),
+        })
+    }
+}
+
+impl ToNode for TimeModification {
+    fn to_node(&self) -> Element {
+        let node = Element::new(Self::tag())
+            .with_chd_text("actual-notes", self.actual_notes)
+            .with_chd_text("normal-notes", self.normal_notes)
+            .with_optional_chd_text("normal-type", self.normal_type)
+            .with_children((0..self.normal_dot).map(|_| Element::new("normal-dot")));
+        self.unknown.write(node)
+    }
+}
+
+/// Notated rhythm value of a note, independent of its played duration
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NoteValue {
+    pub value: NoteTypeValue,
+    pub size: Option<SymbolSize>,
+    pub unknown: Unknown,
+}
+
+impl FromNode for NoteValue {
+    fn tag() -> &'static str {
+        "type"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        Ok(NoteValue {
+            value: parse_text(node, Self::tag())?,
+            size: parse_optional_attr(node, "size")?,
+            unknown: Unknown::parse_attrs(node, &["size"]),
+        })
+    }
+}
+
+impl ToNode for NoteValue {
+    fn to_node(&self) -> Element {
+        let node = Element::new(Self::tag())
+            .with_optional_attr("size", self.size)
+            .with_text(self.value);
+        self.unknown.write(node)
+    }
+}
+
+xml_enum! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum BeamValue {
+        Begin => "begin",
+        Continue => "continue",
+        End => "end",
+        ForwardHook => "forward hook",
+        BackwardHook => "backward hook",
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Beam {
+    /// Beam level, 1 for eighth notes, 2 for sixteenths and so on, absent for level 1
+    pub number: Option<u8>,
+    pub value: BeamValue,
+    pub unknown: Unknown,
+}
+
+impl FromNode for Beam {
+    fn tag() -> &'static str {
+        "beam"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        Ok(Beam {
+            number: parse_optional_attr(node, "number")?,
+            value: parse_text(node, Self::tag())?,
+            unknown: Unknown::parse_attrs(node, &["number"]),
+        })
+    }
+}
+
+impl ToNode for Beam {
+    fn to_node(&self) -> Element {
+        let node = Element::new(Self::tag())
+            .with_optional_attr("number", self.number)
+            .with_text(self.value);
+        self.unknown.write(node)
+    }
+}
+
+xml_enum! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum StemValue {
+        Down => "down",
+        Up => "up",
+        Double => "double",
+        None => "none",
+    }
+}
+
+xml_enum! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum NoteheadValue {
+        Slash => "slash",
+        Triangle => "triangle",
+        Diamond => "diamond",
+        Square => "square",
+        Cross => "cross",
+        X => "x",
+        CircleX => "circle-x",
+        InvertedTriangle => "inverted triangle",
+        ArrowDown => "arrow down",
+        ArrowUp => "arrow up",
+        Circled => "circled",
+        Slashed => "slashed",
+        BackSlashed => "back slashed",
+        Normal => "normal",
+        Cluster => "cluster",
+        CircleDot => "circle dot",
+        LeftTriangle => "left triangle",
+        Rectangle => "rectangle",
+        None => "none",
+        Do => "do",
+        Re => "re",
+        Mi => "mi",
+        Fa => "fa",
+        FaUp => "fa up",
+        So => "so",
+        La => "la",
+        Ti => "ti",
+        Other => "other",
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Notehead {
+    pub value: NoteheadValue,
+    /// Filled or hollow regardless of the note type if present
+    pub filled: Option<YesNo>,
+    pub parentheses: Option<YesNo>,
+    pub unknown: Unknown,
+}
+
+impl FromNode for Notehead {
+    fn tag() -> &'static str {
+        "notehead"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        Ok(Notehead {
+            value: parse_text(node, Self::tag())?,
+            filled: parse_optional_attr(node, "filled")?,
+            parentheses: parse_optional_attr(node, "parentheses")?,
+            unknown: Unknown::parse_attrs(node, &["filled", "parentheses"]),
+        })
+    }
+}
+
+impl ToNode for Notehead {
+    fn to_node(&self) -> Element {
+        let node = Element::new(Self::tag())
+            .with_optional_attr("filled", self.filled)
+            .with_optional_attr("parentheses", self.parentheses)
+            .with_text(self.value);
+        self.unknown.write(node)
+    }
+}
+
+/// Ornamental note without a duration of its own
+#[derive(Debug, Clone, PartialEq)]
+pub struct Grace {
+    /// Slashed stem, usually an acciaccatura
+    pub slash: Option<YesNo>,
+    /// Percentage of the previous note's duration taken by the grace note
+    pub steal_time_previous: Option<f64>,
+    /// Percentage of the following note's duration taken by the grace note
+    pub steal_time_following: Option<f64>,
+    /// Divisions added for the grace note rather than stolen from a neighbor
+    pub make_time: Option<f64>,
+    pub unknown: Unknown,
+}
+
+impl FromNode for Grace {
+    fn tag() -> &'static str {
+        "grace"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        Ok(Grace {
+            slash: parse_optional_attr(node, "slash")?,
+            steal_time_previous: parse_optional_attr(node, "steal-time-previous")?,
+            steal_time_following: parse_optional_attr(node, "steal-time-following")?,
+            make_time: parse_optional_attr(node, "make-time")?,
+            unknown: Unknown::parse(
+                node,
+                &[
+                    "slash",
+                    "steal-time-previous",
+                    "steal-time-following",
+                    "make-time",
+                ],
+                &[],
+            ),
+        })
+    }
+}
+
+impl ToNode for Grace {
+    fn to_node(&self) -> Element {
+        let node = Element::new(Self::tag())
+            .with_optional_attr("steal-time-previous", self.steal_time_previous)
+            .with_optional_attr("steal-time-following", self.steal_time_following)
+            .with_optional_attr("make-time", self.make_time)
+            .with_optional_attr("slash", self.slash);
+        self.unknown.write(node)
+    }
+}
+
+/// How a grace note is realized in playback
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GraceTiming {
+    /// Takes the percentage from the end of the previous note
+    StealPrevious(f64),
+    /// Takes the percentage from the start of the following note
+    StealFollowing(f64),
+    /// Sounds for the divisions, delaying the following note
+    MakeTime(f64),
+    /// Played as quickly as possible before the beat
+    Acciaccatura,
+    /// Played on the beat, taking time from the following note
+    Appoggiatura,
+}
+
+impl Grace {
+    /// Explicit timing if given, otherwise guessed from the slash
+    pub fn timing(&self) -> GraceTiming {
+        match (
+            self.steal_time_previous,
+            self.steal_time_following,
+            self.make_time,
+        ) {
+            (Some(p), _, _) => GraceTiming::StealPrevious(p),
+            (_, Some(p), _) => GraceTiming::StealFollowing(p),
+            (_, _, Some(d)) => GraceTiming::MakeTime(d),
+            _ if self.slash == Some(YesNo::Yes) => GraceTiming::Acciaccatura,
+            _ => GraceTiming::Appoggiatura,
+        }
+    }
+}
+
+/// Flavor of a note with what it sounds, only normal and non-cue grace notes can be tied
+#[derive(Debug, Clone, PartialEq)]
+pub enum NoteKind {
+    Normal {
+        note_type: NoteType,
+        /// Divisions, absent in files that omit it, e.g. on rests
+        duration: Option<u32>,
+        tie: Vec<Tie>,
+    },
+    /// Takes no time of its own
+    Grace {
+        grace: Grace,
+        note_type: NoteType,
+        tie: Vec<Tie>,
+    },
+    /// Grace note shown for reference, not played back
+    CueGrace { grace: Grace, note_type: NoteType },
+    /// Small note shown for reference, not played back
+    Cue {
+        note_type: NoteType,
+        duration: Option<u32>,
+    },
+}
+
+impl NoteKind {
+    fn parse(node: &Node) -> Result<Self> {
+        let cue = count_chd(node, "cue") > 0;
+        let note_type = NoteType::parse(node)?;
+        // a missing duration is tolerated as many files omit it on rests
+        let duration = || parse_optional_chd_text(node, "duration");
+        Ok(match parse_option_chd(node)? {
+            Some(grace) if cue => NoteKind::CueGrace { grace, note_type },
+            Some(grace) => NoteKind::Grace {
+                grace,
+                note_type,
+                tie: parse_children(node)?,
+            },
+            None if cue => NoteKind::Cue {
+                note_type,
+                duration: duration()?,
+            },
+            None => NoteKind::Normal {
+                note_type,
+                duration: duration()?,
+                tie: parse_children(node)?,
+            },
+        })
+    }
+
+    pub fn note_type(&self) -> &NoteType {
+        match self {
+            NoteKind::Normal { note_type, .. }
+            | NoteKind::Grace { note_type, .. }
+            | NoteKind::CueGrace { note_type, .. }
+            | NoteKind::Cue { note_type, .. } => note_type,
+        }
+    }
+
+    /// Nodes written before and after the chord node
+    fn to_nodes(&self) -> (Vec<Element>, Vec<Element>) {
+        match self {
+            NoteKind::Normal {
+                note_type,
+                duration,
+                tie,
+            } => {
+                let mut after = vec![note_type.to_node()];
+                after.extend(duration.map(|d| Element::new("duration").with_text(d)));
+                after.extend(children_to_nodes(tie));
+                (Vec::new(), after)
+            }
+            NoteKind::Grace {
+                grace,
+                note_type,
+                tie,
+            } => {
+                let mut after = vec![note_type.to_node()];
+                after.extend(children_to_nodes(tie));
+                (vec![grace.to_node()], after)
+            }
+            NoteKind::CueGrace { grace, note_type } => (
+                vec![grace.to_node(), Element::new("cue")],
+                vec![note_type.to_node()],
+            ),
+            NoteKind::Cue {
+                note_type,
+                duration,
+            } => (
+                vec![Element::new("cue")],
+                std::iter::once(note_type.to_node())
+                    .chain(duration.map(|d| Element::new("duration").with_text(d)))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Note {
+    pub kind: NoteKind,
+    /// Sounds at the same time as the previous note
+    pub chord: bool,
+    /// Ids of the score instruments playing this note
+    pub instrument: Vec<String>,
+    pub editorial: Editorial,
+    pub voice: Option<String>,
+    pub note_value: Option<NoteValue>,
+    /// Number of augmentation dots
+    pub dot: u8,
+    pub time_modification: Option<TimeModification>,
+    pub stem: Option<StemValue>,
+    pub notehead: Option<Notehead>,
+    pub staff: Option<u8>,
+    pub beam: Vec<Beam>,
+    pub notations: Vec<Notations>,
+    pub lyric: Vec<Lyric>,
+    pub color: Option<Color>,
+    pub position: Position,
+    /// Hidden notes are skipped by renderers but still played back
+    pub printout: Printout,
+    pub play: Option<Play>,
+    pub listen: Option<Listen>,
+    pub unknown: Unknown,
+}
+
+impl FromNode for Note {
+    fn tag() -> &'static str {
+        "note"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        Ok(Note {
+            kind: NoteKind::parse(node)?,
+            chord: count_chd(node, "chord") > 0,
+            instrument: node
+                .children()
+                .filter(|c| c.tag_name().name() == "instrument")
+                .map(|c| parse_attr(&c, "id"))
+                .collect::<Result<_>>()?,
+            editorial: Editorial::parse(node)?,
+            voice: parse_optional_chd_text(node, "voice")?,
+            note_value: parse_option_chd(node)?,
+            dot: count_chd(node, "dot") as u8,
+            time_modification: parse_option_chd(node)?,
+            stem: parse_optional_chd_text(node, "stem")?,
+            notehead: parse_option_chd(node)?,
+            staff: parse_optional_chd_text(node, "staff")?,
+            beam: parse_children(node)?,
+            notations: parse_children(node)?,
+            lyric: parse_children(node)?,
+            color: parse_optional_attr(node, "color")?,
+            position: Position::parse(node)?,
+            printout: Printout::parse(node)?,
+            play: parse_option_chd(node)?,
+            listen: parse_option_chd(node)?,
+            unknown: Unknown::parse(
+                node,
+                &[
+                    "color",
+                    "default-x",
+                    "default-y",
+                    "relative-x",
+                    "relative-y",
+                    "print-object",
+                    "print-spacing",
+                    "print-dot",
+                    "print-lyric",
+                ],
+                &[
+                    "grace",
+                    "cue",
+                    "chord",
+                    "pitch",
+                    "rest",
+                    "unpitched",
+                    "duration",
+                    "tie",
+                    "instrument",
+                    "footnote",
+                    "level",
+                    "voice",
+                    "type",
+                    "dot",
+                    "time-modification",
+                    "stem",
+                    "notehead",
+                    "staff",
+                    "beam",
+                    "notations",
+                    "lyric",
+                    "play",
+                    "listen",
+                ],
+            ),
+        })
+    }
+}
+
+impl ToNode for Note {
+    fn to_node(&self) -> Element {
+        let (before, after) = self.kind.to_nodes();
+
+        let node = Element::new(Self::tag()).with_optional_attr("color", self.color);
+        let node = self.position.write_attrs(node);
+        let node = self
+            .printout
+            .write_attrs(node)
+            .with_children(before)
+            .with_optional_child(self.chord.then(|| Element::new("chord")))
+            .with_children(after)
+            .with_children(
+                self.instrument
+                    .iter()
+                    .map(|id| Element::new("instrument").with_attr("id", id)),
+            )
+            .with_children(self.editorial.to_nodes())
+            .with_optional_chd_text("voice", self.voice.as_ref())
+            .with_optional_child(self.note_value.as_ref().map(ToNode::to_node))
+            .with_children((0..self.dot).map(|_| Element::new("dot")))
+            .with_optional_child(self.time_modification.as_ref().map(ToNode::to_node))
+            .with_optional_chd_text("stem", self.stem)
+            .with_optional_child(self.notehead.as_ref().map(ToNode::to_node))
+            .with_optional_chd_text("staff", self.staff)
+            .with_children(children_to_nodes(&self.beam))
+            .with_children(children_to_nodes(&self.notations))
+            .with_children(children_to_nodes(&self.lyric))
+            .with_optional_child(self.play.as_ref().map(ToNode::to_node))
+            .with_optional_child(self.listen.as_ref().map(ToNode::to_node));
+        self.unknown.write(node)
+    }
+}
+
+impl Note {
+    /// Duration in divisions, zero for grace notes and notes without one
+    pub fn duration(&self) -> u32 {
+        match self.kind {
+            NoteKind::Normal { duration, .. } | NoteKind::Cue { duration, .. } => {
+                duration.unwrap_or(0)
+            }
+            NoteKind::Grace { .. } | NoteKind::CueGrace { .. } => 0,
+        }
+    }
+
+    pub fn grace(&self) -> Option<&Grace> {
+        match &self.kind {
+            NoteKind::Grace { grace, .. } | NoteKind::CueGrace { grace, .. } => Some(grace),
+            _ => None,
+        }
+    }
+
+    /// Whether the note is shown for reference only, including cue grace notes
+    pub fn is_cue(&self) -> bool {
+        matches!(self.kind, NoteKind::Cue { .. } | NoteKind::CueGrace { .. })
+    }
+
+    /// Playback ties, cue notes have none
+    pub fn ties(&self) -> &[Tie] {
+        match &self.kind {
+            NoteKind::Normal { tie, .. } | NoteKind::Grace { tie, .. } => tie,
+            NoteKind::CueGrace { .. } | NoteKind::Cue { .. } => &[],
+        }
+    }
+
+    /// Concert pitch of a note written for a transposing instrument
+    pub fn sounding_pitch(&self, transpose: Option<&Transpose>) -> Option<Pitch> {
+        match (self.kind.note_type(), transpose) {
+            (NoteType::Pitch(p), Some(t)) => p.transposed(t),
+            (NoteType::Pitch(p), None) => Some(p.clone()),
+            _ => None,
+        }
+    }
+
+    /// Notated length in quarter notes from the type, dots and tuplet ratio
+    pub fn notated_quarters(&self) -> Option<f64> {
+        let quarters = self.note_value.as_ref()?.value.quarters();
+        let dotted = quarters * (2. - 0.5f64.powi(self.dot as i32));
+
+        Some(match &self.time_modification {
+            Some(t) => dotted * t.normal_notes as f64 / t.actual_notes as f64,
+            None => dotted,
+        })
+    }
+
+    /// Playback timing of a grace note, none for other notes
+    pub fn grace_timing(&self) -> Option<GraceTiming> {
+        self.grace().map(Grace::timing)
+    }
+
+    /// Whether an assessment application evaluates the note for the player
+    pub fn is_assessed(&self, player: &str) -> bool {
+        self.listen.as_ref().is_none_or(|l| l.is_assessed(player))
+    }
+
+    /// Whether the note is a rest lasting the whole measure
+    pub fn is_measure_rest(&self) -> bool {
+        matches!(
+            self.kind.note_type(),
+            NoteType::Rest(Rest { measure: true, .. })
+        )
+    }
+
+    /// Duration in divisions, resolving a whole-measure rest from the time signature
+    /// since its notated duration is often not meaningful
+    pub fn effective_duration(&self, divisions: u32, time: Option<&Time>) -> u32 {
+        match time.and_then(|t| t.measure_duration(divisions)) {
+            Some(d) if self.is_measure_rest() => d,
+            _ => self.duration(),
+        }
+    }
+
+    /// Duration as a fraction of a whole note at the given divisions per quarter note
+    pub fn whole_duration(&self, divisions: NonZeroU32) -> Duration {
+        Duration::from_divisions(self.duration(), divisions)
+    }
+
+    /// Duration actually sounding, cue notes are silent
+    pub fn playback_duration(&self) -> u32 {
+        match self.kind {
+            NoteKind::Normal { duration, .. } => duration.unwrap_or(0),
+            _ => 0,
+        }
+    }
+
+    /// Whether the sound continues into the next note
+    pub fn is_tie_start(&self) -> bool {
+        self.ties().iter().any(|t| t.tie_type == StartStop::Start)
+    }
+
+    /// Whether the sound is continued from the previous note
+    pub fn is_tie_stop(&self) -> bool {
+        self.ties().iter().any(|t| t.tie_type == StartStop::Stop)
+    }
+
+    /// Articulation marks of all notations on this note
+    pub fn articulations(&self) -> impl Iterator<Item = &ArticulationMark> {
+        self.notations
+            .iter()
+            .flat_map(|n| &n.articulations)
+            .flat_map(|a| &a.marks)
+    }
+
+    /// Whether a breath mark or caesura shortens the sound after the note
+    pub fn has_break(&self) -> bool {
+        self.articulations().any(|m| m.articulation.is_break())
+    }
+
+    /// Whether the note is held longer than its duration
+    pub fn has_fermata(&self) -> bool {
+        self.notations.iter().any(|n| !n.fermata.is_empty())
+    }
+}
+
+xml_enum! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum NoteTypeValue {
+        N1024th => "1024th",
+        N512th => "512th",
+        N256th => "256th",
+        N128th => "128th",
+        N64th => "64th",
+        N32nd => "32nd",
+        N16th => "16th",
+        Eighth => "eighth",
+        Quarter => "quarter",
+        Half => "half",
+        Whole => "whole",
+        Breve => "breve",
+        Long => "long",
+        Maxima => "maxima",
+    }
+}
+
+impl NoteTypeValue {
+    /// Length of the note value measured in quarter notes
+    pub fn quarters(&self) -> f64 {
+        match self {
+            NoteTypeValue::N1024th => 1. / 256.,
+            NoteTypeValue::N512th => 1. / 128.,
+            NoteTypeValue::N256th => 1. / 64.,
+            NoteTypeValue::N128th => 1. / 32.,
+            NoteTypeValue::N64th => 1. / 16.,
+            NoteTypeValue::N32nd => 1. / 8.,
+            NoteTypeValue::N16th => 1. / 4.,
+            NoteTypeValue::Eighth => 1. / 2.,
+            NoteTypeValue::Quarter => 1.,
+            NoteTypeValue::Half => 2.,
+            NoteTypeValue::Whole => 4.,
+            NoteTypeValue::Breve => 8.,
+            NoteTypeValue::Long => 16.,
+            NoteTypeValue::Maxima => 32.,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroU64;
+
+    use roxmltree::Document;
+
+    use super::*;
+    use crate::score::{AboveBelow, Dynamic, Dynamics, Level};
+
+    #[test]
+    fn note_pitch_ok() {
+        let xml = r#"
+            <note>
+                <pitch>
+                    <step>E</step>
+                    <octave>4</octave>
+                </pitch>
+                <duration>60</duration>
+            </note>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let note = Note::from_node(&node);
+        assert!(note.is_ok());
+        assert_eq!(
+            note.unwrap(),
+            Note {
+                kind: NoteKind::Normal {
+                    note_type: NoteType::Pitch(Pitch {
+                        step: Step::E,
+                        alter: 0.,
+                        octave: 4,
+                        unknown: Unknown::default()
+                    }),
+                    duration: Some(60),
+                    tie: vec![]
+                },
+                chord: false,
+                instrument: vec![],
+                editorial: Editorial::default(),
+                voice: None,
+                note_value: None,
+                dot: 0,
+                time_modification: None,
+                stem: None,
+                notehead: None,
+                staff: None,
+                beam: vec![],
+                notations: vec![],
+                lyric: vec![],
+                color: None,
+                position: Position::default(),
+                printout: Printout::default(),
+                play: None,
+                listen: None,
+                unknown: Unknown::default()
+            }
+        );
+    }
+
+    #[test]
+    fn note_notations_dynamics_ok() {
+        let xml = r#"
+            <note>
+                <pitch>
+                    <step>G</step>
+                    <octave>5</octave>
+                </pitch>
+                <duration>1</duration>
+                <notations>
+                    <dynamics placement="below">
+                        <sf />
+                    </dynamics>
+                </notations>
+            </note>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let note = Note::from_node(&node).unwrap();
+        assert_eq!(
+            note.notations,
+            vec![Notations {
+                dynamics: vec![Dynamics {
+                    placement: Some(AboveBelow::Below),
+                    color: None,
+                    marks: vec![Dynamic::Sf],
+                    other_dynamics: None,
+                    position: Position::default(),
+                    unknown: Unknown::default()
+                }],
+                ..Default::default()
+            }]
+        );
+    }
+
+    #[test]
+    fn note_long_duration_ok() {
+        // a dotted whole note at 1024 divisions per quarter
+        let xml = r#"
+            <note>
+                <pitch>
+                    <step>C</step>
+                    <octave>4</octave>
+                </pitch>
+                <duration>6144</duration>
+            </note>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let note = Note::from_node(&node).unwrap();
+        assert_eq!(note.duration(), 6144);
+        assert_eq!(note.playback_duration(), 6144);
+        assert_eq!(
+            note.whole_duration(NonZeroU32::new(1024).unwrap()),
+            Duration::new(3, NonZeroU64::new(2).unwrap())
+        );
+        assert_eq!(note.to_node(), Element::from(&node));
+    }
+
+    #[test]
+    fn note_tie_ok() {
+        let xml = r#"
+            <note>
+                <pitch>
+                    <step>C</step>
+                    <octave>4</octave>
+                </pitch>
+                <duration>4</duration>
+                <tie type="stop" />
+                <tie type="start" />
+            </note>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let note = Note::from_node(&node).unwrap();
+        assert_eq!(
+            note.ties(),
+            vec![
+                Tie {
+                    tie_type: StartStop::Stop,
+                    unknown: Unknown::default()
+                },
+                Tie {
+                    tie_type: StartStop::Start,
+                    unknown: Unknown::default()
+                }
+            ]
+        );
+        assert!(note.is_tie_start());
+        assert!(note.is_tie_stop());
+    }
+
+    #[test]
+    fn note_time_modification_ok() {
+        let xml = r#"
+            <note>
+                <pitch>
+                    <step>A</step>
+                    <octave>4</octave>
+                </pitch>
+                <duration>2</duration>
+                <time-modification>
+                    <actual-notes>3</actual-notes>
+                    <normal-notes>2</normal-notes>
+                    <normal-type>eighth</normal-type>
+                </time-modification>
+            </note>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let note = Note::from_node(&node).unwrap();
+        assert_eq!(
+            note.time_modification,
+            Some(TimeModification {
+                actual_notes: 3,
+                normal_notes: 2,
+                normal_type: Some(NoteTypeValue::Eighth),
+                normal_dot: 0,
+                unknown: Unknown::default()
+            })
+        );
+    }
+
+    #[test]
+    fn note_beam_ok() {
+        let xml = r#"
+            <note>
+                <pitch>
+                    <step>D</step>
+                    <octave>5</octave>
+                </pitch>
+                <duration>1</duration>
+                <beam>continue</beam>
+                <beam number="2">backward hook</beam>
+            </note>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let note = Note::from_node(&node).unwrap();
+        assert_eq!(
+            note.beam,
+            vec![
+                Beam {
+                    number: None,
+                    value: BeamValue::Continue,
+                    unknown: Unknown::default()
+                },
+                Beam {
+                    number: Some(2),
+                    value: BeamValue::BackwardHook,
+                    unknown: Unknown::default()
+                }
+            ]
+        );
+        assert_eq!(note.to_node(), Element::from(&node));
+    }
+
+    #[test]
+    fn note_dot_ok() {
+        let xml = r#"
+            <note>
+                <rest />
+                <duration>7</duration>
+                <dot />
+                <dot />
+            </note>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let note = Note::from_node(&node).unwrap();
+        assert_eq!(note.dot, 2);
+    }
+
+    #[test]
+    fn note_chord_ok() {
+        let xml = r#"
+            <note>
+                <chord />
+                <pitch>
+                    <step>E</step>
+                    <octave>4</octave>
+                </pitch>
+                <duration>4</duration>
+            </note>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let note = Note::from_node(&node).unwrap();
+        assert!(note.chord);
+    }
+
+    #[test]
+    fn note_stem_ok() {
+        let xml = r#"
+            <note>
+                <pitch>
+                    <step>F</step>
+                    <octave>5</octave>
+                </pitch>
+                <duration>4</duration>
+                <stem default-y="-55">down</stem>
+            </note>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let note = Note::from_node(&node).unwrap();
+        assert_eq!(note.stem, Some(StemValue::Down));
+    }
+
+    #[test]
+    fn note_unpitched_ok() {
+        let xml = r#"
+            <note>
+                <unpitched>
+                    <display-step>C</display-step>
+                    <display-octave>5</display-octave>
+                </unpitched>
+                <duration>1</duration>
+            </note>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let note = Note::from_node(&node).unwrap();
+        assert_eq!(
+            *note.kind.note_type(),
+            NoteType::Unpitched(Unpitched {
+                display_step: Some(Step::C),
+                display_octave: Some(5),
+                unknown: Unknown::default()
+            })
+        );
+    }
+
+    #[test]
+    fn pitch_flat_alter_ok() {
+        for (alter, value) in [("-1", -1.), ("-2", -2.)] {
+            let xml = format!(
+                "<pitch><step>B</step><alter>{}</alter><octave>3</octave></pitch>",
+                alter
+            );
+            let doc = Document::parse(&xml).unwrap();
+            let node = doc.root_element();
+
+            let pitch = Pitch::from_node(&node).unwrap();
+            assert_eq!(
+                pitch,
+                Pitch {
+                    step: Step::B,
+                    alter: value,
+                    octave: 3,
+                    unknown: Unknown::default()
+                }
+            );
+            assert_eq!(pitch.to_node(), Element::from(&node));
+        }
+    }
+
+    #[test]
+    fn pitch_midi_note_frequency_ok() {
+        let a4 = Pitch {
+            step: Step::A,
+            alter: 0.,
+            octave: 4,
+            unknown: Unknown::default(),
+        };
+        assert_eq!(a4.midi_note(), 69);
+        assert_eq!(a4.frequency(440.), 440.);
+        assert_eq!(a4.frequency(415.), 415.);
+
+        let middle_c = Pitch {
+            step: Step::C,
+            alter: 0.,
+            octave: 4,
+            unknown: Unknown::default(),
+        };
+        assert_eq!(middle_c.midi_note(), 60);
+        assert!((middle_c.frequency(440.) - 261.6256).abs() < 1e-4);
+
+        let b_flat = Pitch {
+            step: Step::B,
+            alter: -1.,
+            octave: 3,
+            unknown: Unknown::default(),
+        };
+        assert_eq!(b_flat.midi_note(), 58);
+        let c_flat = Pitch {
+            step: Step::C,
+            alter: -1.,
+            octave: 4,
+            unknown: Unknown::default(),
+        };
+        assert_eq!(c_flat.midi_note(), 59);
+
+        let quarter_sharp = Pitch { alter: 0.5, ..a4 };
+        assert_eq!(quarter_sharp.midi_note(), 70);
+        assert!((quarter_sharp.frequency(440.) - 452.8929).abs() < 1e-4);
+    }
+
+    #[test]
+    fn note_microtonal_alter_ok() {
+        let xml = r#"
+            <note>
+                <pitch>
+                    <step>E</step>
+                    <alter>-0.5</alter>
+                    <octave>4</octave>
+                </pitch>
+                <duration>1</duration>
+            </note>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let note = Note::from_node(&node).unwrap();
+        match note.kind.note_type() {
+            NoteType::Pitch(p) => assert_eq!(p.alter, -0.5),
+            _ => panic!("pitch expected"),
+        }
+        let pitch = note.to_node().children.remove(0);
+        assert_eq!(pitch.children[1], Element::new("alter").with_text(-0.5));
+    }
+
+    #[test]
+    fn note_sounding_pitch_ok() {
+        let xml = r#"
+            <note>
+                <pitch>
+                    <step>D</step>
+                    <octave>5</octave>
+                </pitch>
+                <duration>1</duration>
+            </note>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+        let note = Note::from_node(&node).unwrap();
+
+        // Bb clarinet sounds a major second lower
+        let clarinet = Transpose {
+            number: None,
+            diatonic: Some(-1),
+            chromatic: -2.,
+            octave_change: None,
+            double: false,
+            unknown: Unknown::default(),
+        };
+        assert_eq!(
+            note.sounding_pitch(Some(&clarinet)),
+            Some(Pitch {
+                step: Step::C,
+                alter: 0.,
+                octave: 5,
+                unknown: Unknown::default()
+            })
+        );
+
+        // horn in F sounds a perfect fifth lower
+        let horn = Transpose {
+            diatonic: Some(-4),
+            chromatic: -7.,
+            ..clarinet.clone()
+        };
+        assert_eq!(
+            note.sounding_pitch(Some(&horn)),
+            Some(Pitch {
+                step: Step::G,
+                alter: 0.,
+                octave: 4,
+                unknown: Unknown::default()
+            })
+        );
+
+        // guitar sounds an octave lower, spelled without a diatonic step
+        let guitar = Transpose {
+            diatonic: None,
+            chromatic: 0.,
+            octave_change: Some(-1),
+            ..horn
+        };
+        assert_eq!(note.sounding_pitch(Some(&guitar)).unwrap().octave, 4);
+        assert_eq!(note.sounding_pitch(None).unwrap().octave, 5);
+
+        // no octave below 0 to write the pitch in
+        let low = Pitch {
+            step: Step::C,
+            alter: 0.,
+            octave: 0,
+            unknown: Unknown::default(),
+        };
+        assert_eq!(low.transposed(&clarinet), None);
+        assert_eq!(low.transposed(&guitar), None);
+    }
+
+    #[test]
+    fn note_value_ok() {
+        let xml = r#"
+            <note>
+                <pitch>
+                    <step>G</step>
+                    <octave>4</octave>
+                </pitch>
+                <duration>5</duration>
+                <voice>1</voice>
+                <type size="cue">eighth</type>
+                <dot />
+                <time-modification>
+                    <actual-notes>3</actual-notes>
+                    <normal-notes>2</normal-notes>
+                </time-modification>
+            </note>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let note = Note::from_node(&node).unwrap();
+        assert_eq!(
+            note.note_value,
+            Some(NoteValue {
+                value: NoteTypeValue::Eighth,
+                size: Some(SymbolSize::Cue),
+                unknown: Unknown::default()
+            })
+        );
+        assert_eq!(note.notated_quarters(), Some(0.5));
+
+        let names: Vec<_> = note
+            .to_node()
+            .children
+            .into_iter()
+            .map(|c| c.name)
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                "pitch",
+                "duration",
+                "voice",
+                "type",
+                "dot",
+                "time-modification"
+            ]
+        );
+    }
+
+    #[test]
+    fn note_notehead_ok() {
+        let xml = r#"
+            <note>
+                <unpitched>
+                    <display-step>G</display-step>
+                    <display-octave>5</display-octave>
+                </unpitched>
+                <duration>1</duration>
+                <stem>up</stem>
+                <notehead filled="no" parentheses="yes">circle-x</notehead>
+            </note>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let note = Note::from_node(&node).unwrap();
+        assert_eq!(
+            note.notehead,
+            Some(Notehead {
+                value: NoteheadValue::CircleX,
+                filled: Some(YesNo::No),
+                parentheses: Some(YesNo::Yes),
+                unknown: Unknown::default()
+            })
+        );
+    }
+
+    #[test]
+    fn note_grace_ok() {
+        let xml = r#"
+            <note>
+                <grace slash="yes" />
+                <pitch>
+                    <step>D</step>
+                    <octave>5</octave>
+                </pitch>
+                <voice>1</voice>
+                <type>eighth</type>
+            </note>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let note = Note::from_node(&node).unwrap();
+        assert_eq!(note.duration(), 0);
+        assert_eq!(note.grace_timing(), Some(GraceTiming::Acciaccatura));
+
+        let el = note.to_node();
+        assert_eq!(
+            el.children[0],
+            Element::new("grace").with_attr("slash", "yes")
+        );
+        assert!(el.children.iter().all(|c| c.name != "duration"));
+
+        let xml = r#"<grace steal-time-following="33.3" />"#;
+        let doc = Document::parse(xml).unwrap();
+        let grace = Grace::from_node(&doc.root_element()).unwrap();
+        assert_eq!(grace.timing(), GraceTiming::StealFollowing(33.3));
+    }
+
+    #[test]
+    fn note_cue_grace_ok() {
+        let xml = r#"
+            <note>
+                <grace />
+                <cue />
+                <rest />
+                <tie type="start" />
+            </note>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let note = Note::from_node(&node).unwrap();
+        assert!(matches!(note.kind, NoteKind::CueGrace { .. }));
+        assert!(note.is_cue());
+        assert!(note.ties().is_empty());
+
+        let names: Vec<_> = note
+            .to_node()
+            .children
+            .into_iter()
+            .map(|c| c.name)
+            .collect();
+        assert_eq!(names, vec!["grace", "cue", "rest"]);
+    }
+
+    #[test]
+    fn note_editorial_ok() {
+        let xml = r#"
+            <note>
+                <pitch>
+                    <step>F</step>
+                    <alter>1</alter>
+                    <octave>4</octave>
+                </pitch>
+                <duration>2</duration>
+                <footnote>Sharp missing in the autograph</footnote>
+                <level parentheses="yes">Ed.</level>
+                <voice>1</voice>
+            </note>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let note = Note::from_node(&node).unwrap();
+        assert_eq!(
+            note.editorial,
+            Editorial {
+                footnote: Some("Sharp missing in the autograph".to_owned()),
+                level: Some(Level {
+                    text: Some("Ed.".to_owned()),
+                    reference: None,
+                    level_type: None,
+                    parentheses: Some(YesNo::Yes),
+                    bracket: None,
+                    unknown: Unknown::default()
+                })
+            }
+        );
+
+        let names: Vec<_> = note
+            .to_node()
+            .children
+            .into_iter()
+            .map(|c| c.name)
+            .collect();
+        assert_eq!(
+            names,
+            vec!["pitch", "duration", "footnote", "level", "voice"]
+        );
+    }
+
+    #[test]
+    fn note_cue_ok() {
+        let xml = r#"
+            <note>
+                <cue />
+                <pitch>
+                    <step>A</step>
+                    <octave>4</octave>
+                </pitch>
+                <duration>2</duration>
+            </note>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let note = Note::from_node(&node).unwrap();
+        assert!(matches!(
+            note.kind,
+            NoteKind::Cue {
+                duration: Some(2),
+                ..
+            }
+        ));
+        assert!(note.is_cue());
+        assert_eq!(note.playback_duration(), 0);
+        assert_eq!(note.to_node().children[0], Element::new("cue"));
+
+        let xml = "<note><cue /><rest /></note>";
+        let doc = Document::parse(xml).unwrap();
+        let note = Note::from_node(&doc.root_element()).unwrap();
+        assert_eq!(note.duration(), 0);
+        assert_eq!(note.to_node(), Element::from(&doc.root_element()));
+
+        let xml = "<note><rest /><duration>1.5</duration></note>";
+        let doc = Document::parse(xml).unwrap();
+        assert!(Note::from_node(&doc.root_element()).is_err());
+
+        let xml = "<note><rest /><duration>1</duration><duration>2</duration></note>";
+        let doc = Document::parse(xml).unwrap();
+        assert!(Note::from_node(&doc.root_element()).is_err());
+    }
+
+    #[test]
+    fn note_measure_rest_ok() {
+        let xml = r#"
+            <note>
+                <rest measure="yes" />
+                <duration>4</duration>
+            </note>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let note = Note::from_node(&node).unwrap();
+        assert!(note.is_measure_rest());
+
+        let time = Time::new(3, 8);
+        assert_eq!(note.effective_duration(2, Some(&time)), 3);
+        assert_eq!(note.effective_duration(2, None), 4);
+    }
+
+    #[test]
+    fn note_rest_display_ok() {
+        let xml = r#"
+            <note>
+                <rest>
+                    <display-step>E</display-step>
+                    <display-octave>5</display-octave>
+                </rest>
+                <duration>2</duration>
+                <voice>1</voice>
+            </note>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let note = Note::from_node(&node).unwrap();
+        assert_eq!(
+            *note.kind.note_type(),
+            NoteType::Rest(Rest {
+                measure: false,
+                display_step: Some(Step::E),
+                display_octave: Some(5),
+                unknown: Unknown::default()
+            })
+        );
+
+        let el = note.to_node();
+        assert_eq!(
+            el.children[0],
+            Element::new("rest")
+                .with_chd_text("display-step", 'E')
+                .with_chd_text("display-octave", 5)
+        );
+    }
+
+    #[test]
+    fn note_rest_and_unpitched_err() {
+        let xml = r#"
+            <note>
+                <rest />
+                <unpitched />
+                <duration>1</duration>
+            </note>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let note = Note::from_node(&node);
+        assert!(matches!(note, Err(ExclusiveNodeFound { .. })));
+    }
+
+    #[test]
+    fn note_rest_ok() {
+        let xml = r#"
+            <note>
+                <rest />
+                <duration>60</duration>
+            </note>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let note = Note::from_node(&node);
+        assert!(note.is_ok());
+        assert_eq!(
+            note.unwrap(),
+            Note {
+                kind: NoteKind::Normal {
+                    note_type: NoteType::Rest(Rest {
+                        measure: false,
+                        display_step: None,
+                        display_octave: None,
+                        unknown: Unknown::default()
+                    }),
+                    duration: Some(60),
+                    tie: vec![]
+                },
+                chord: false,
+                instrument: vec![],
+                editorial: Editorial::default(),
+                voice: None,
+                note_value: None,
+                dot: 0,
+                time_modification: None,
+                stem: None,
+                notehead: None,
+                staff: None,
+                beam: vec![],
+                notations: vec![],
+                lyric: vec![],
+                color: None,
+                position: Position::default(),
+                printout: Printout::default(),
+                play: None,
+                listen: None,
+                unknown: Unknown::default()
+            }
+        );
+    }
+
+    #[test]
+    fn note_printout_ok() {
+        let xml = r#"
+            <note print-object="no" print-spacing="yes">
+                <rest />
+                <duration>2</duration>
+                <voice>2</voice>
+            </note>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let note = Note::from_node(&node).unwrap();
+        assert!(!note.printout.is_printed());
+        assert!(note.printout.is_spaced());
+        assert_eq!(note.printout.print_dot, None);
+        assert_eq!(note.to_node(), Element::from(&node));
+        assert!(Printout::default().is_spaced());
+    }
+}
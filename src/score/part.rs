@@ -0,0 +1,1539 @@
+use std::{borrow::Cow, num::NonZeroU32};
+
+use roxmltree::Node;
+
+use super::{
+    children_to_nodes, parse_attr, parse_chd_text, parse_children, parse_optional_attr,
+    parse_optional_chd_text, parse_optional_text, Attribute, Barline, BeamValue, Direction,
+    Duration, FromNode, Harmony, Listening, MeasureStyleType, Metronome, Note, PrincipalVoice,
+    Print, Rehearsal, Sound, StartStop, StartStopSingle, Tenths, Time, ToNode, Unknown, YesNo,
+};
+use crate::{error::Result, writer::Element};
+
+/// Move the musical position back, e.g. to write another voice in the measure
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Backup {
+    pub duration: u32,
+    pub unknown: Unknown,
+}
+
+impl FromNode for Backup {
+    fn tag() -> &'static str {
+        "backup"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        Ok(Backup {
+            duration: parse_chd_text(node, "duration")?,
+            unknown: Unknown::parse(node, &[], &["duration"]),
+        })
+    }
+}
+
+impl ToNode for Backup {
+    fn to_node(&self) -> Element {
+        let node = Element::new(Self::tag()).with_chd_text("duration", self.duration);
+        self.unknown.write(node)
+    }
+}
+
+/// Move the musical position forward, e.g. an invisible rest of a voice
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Forward {
+    pub duration: u32,
+    pub voice: Option<String>,
+    pub staff: Option<u8>,
+    pub unknown: Unknown,
+}
+
+impl FromNode for Forward {
+    fn tag() -> &'static str {
+        "forward"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        Ok(Forward {
+            duration: parse_chd_text(node, "duration")?,
+            voice: parse_optional_chd_text(node, "voice")?,
+            staff: parse_optional_chd_text(node, "staff")?,
+            unknown: Unknown::parse(node, &[], &["duration", "voice", "staff"]),
+        })
+    }
+}
+
+impl ToNode for Forward {
+    fn to_node(&self) -> Element {
+        let node = Element::new(Self::tag())
+            .with_chd_text("duration", self.duration)
+            .with_optional_chd_text("voice", self.voice.as_ref())
+            .with_optional_chd_text("staff", self.staff);
+        self.unknown.write(node)
+    }
+}
+
+/// Analytic feature of a grouping, e.g. a harmonic function
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Feature {
+    pub feature_type: Option<String>,
+    pub text: Option<String>,
+    pub unknown: Unknown,
+}
+
+impl FromNode for Feature {
+    fn tag() -> &'static str {
+        "feature"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        Ok(Feature {
+            feature_type: parse_optional_attr(node, "type")?,
+            text: parse_optional_text(node),
+            unknown: Unknown::parse_attrs(node, &["type"]),
+        })
+    }
+}
+
+impl ToNode for Feature {
+    fn to_node(&self) -> Element {
+        let node = Element::new(Self::tag())
+            .with_optional_attr("type", self.feature_type.as_ref())
+            .with_optional_text(self.text.as_ref());
+        self.unknown.write(node)
+    }
+}
+
+/// Analysis annotation grouping the musical events between a start and a stop
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grouping {
+    pub grouping_type: StartStopSingle,
+    /// Distinguishes overlapping groupings, pairing a start with its stop
+    pub number: String,
+    /// Type of the enclosing grouping for hierarchical analysis
+    pub member_of: Option<String>,
+    pub features: Vec<Feature>,
+    pub unknown: Unknown,
+}
+
+impl FromNode for Grouping {
+    fn tag() -> &'static str {
+        "grouping"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        Ok(Grouping {
+            grouping_type: parse_attr(node, "type")?,
+            number: parse_optional_attr(node, "number")?.unwrap_or_else(|| "1".to_owned()),
+            member_of: parse_optional_attr(node, "member-of")?,
+            features: parse_children(node)?,
+            unknown: Unknown::parse(node, &["type", "number", "member-of"], &["feature"]),
+        })
+    }
+}
+
+impl ToNode for Grouping {
+    fn to_node(&self) -> Element {
+        let node = Element::new(Self::tag())
+            .with_attr("type", self.grouping_type)
+            .with_attr("number", &self.number)
+            .with_optional_attr("member-of", self.member_of.as_ref())
+            .with_children(children_to_nodes(&self.features));
+        self.unknown.write(node)
+    }
+}
+
+/// Content of a measure, kept in document order
+#[derive(Debug, Clone, PartialEq)]
+pub enum MeasureContent {
+    Note(Note),
+    Backup(Backup),
+    Forward(Forward),
+    Direction(Box<Direction>),
+    Attributes(Attribute),
+    Barline(Barline),
+    Print(Print),
+    Harmony(Harmony),
+    Sound(Sound),
+    Grouping(Grouping),
+    Listening(Listening),
+}
+
+impl MeasureContent {
+    pub(super) const TAGS: &'static [&'static str] = &[
+        "note",
+        "backup",
+        "forward",
+        "direction",
+        "attributes",
+        "barline",
+        "print",
+        "harmony",
+        "sound",
+        "grouping",
+        "listening",
+    ];
+
+    pub(super) fn parse(node: &Node) -> Result<Option<Self>> {
+        let content = match node.tag_name().name() {
+            "note" => MeasureContent::Note(Note::from_node(node)?),
+            "backup" => MeasureContent::Backup(Backup::from_node(node)?),
+            "forward" => MeasureContent::Forward(Forward::from_node(node)?),
+            "direction" => MeasureContent::Direction(Box::new(Direction::from_node(node)?)),
+            "attributes" => MeasureContent::Attributes(Attribute::from_node(node)?),
+            "barline" => MeasureContent::Barline(Barline::from_node(node)?),
+            "print" => MeasureContent::Print(Print::from_node(node)?),
+            "harmony" => MeasureContent::Harmony(Harmony::from_node(node)?),
+            "sound" => MeasureContent::Sound(Sound::from_node(node)?),
+            "grouping" => MeasureContent::Grouping(Grouping::from_node(node)?),
+            "listening" => MeasureContent::Listening(Listening::from_node(node)?),
+            _ => return Ok(None),
+        };
+
+        Ok(Some(content))
+    }
+
+    pub(super) fn to_node(&self) -> Element {
+        match self {
+            MeasureContent::Note(n) => n.to_node(),
+            MeasureContent::Backup(b) => b.to_node(),
+            MeasureContent::Forward(f) => f.to_node(),
+            MeasureContent::Direction(d) => d.to_node(),
+            MeasureContent::Attributes(a) => a.to_node(),
+            MeasureContent::Barline(b) => b.to_node(),
+            MeasureContent::Print(p) => p.to_node(),
+            MeasureContent::Harmony(h) => h.to_node(),
+            MeasureContent::Sound(s) => s.to_node(),
+            MeasureContent::Grouping(g) => g.to_node(),
+            MeasureContent::Listening(l) => l.to_node(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Measure {
+    /// Token identifying the measure, e.g. "12", "X1" or "4a"
+    pub number: String,
+    /// Not counted in measure numbering, e.g. a pickup measure
+    pub implicit: Option<YesNo>,
+    /// Barlines of this measure are not synchronized with other parts
+    pub non_controlling: Option<YesNo>,
+    /// Width in tenths
+    pub width: Option<Tenths>,
+    pub contents: Vec<MeasureContent>,
+    pub unknown: Unknown,
+}
+
+impl FromNode for Measure {
+    fn tag() -> &'static str {
+        "measure"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        Ok(Measure {
+            number: parse_attr(node, "number")?,
+            implicit: parse_optional_attr(node, "implicit")?,
+            non_controlling: parse_optional_attr(node, "non-controlling")?,
+            width: parse_optional_attr(node, "width")?,
+            contents: node
+                .children()
+                .filter_map(|c| MeasureContent::parse(&c).transpose())
+                .collect::<Result<_>>()?,
+            unknown: Unknown::parse(
+                node,
+                &["number", "implicit", "non-controlling", "width"],
+                MeasureContent::TAGS,
+            ),
+        })
+    }
+}
+
+impl ToNode for Measure {
+    fn to_node(&self) -> Element {
+        let node = Element::new(Self::tag())
+            .with_attr("number", &self.number)
+            .with_optional_attr("implicit", self.implicit)
+            .with_optional_attr("non-controlling", self.non_controlling)
+            .with_optional_attr("width", self.width)
+            .with_children(self.contents.iter().map(MeasureContent::to_node));
+        self.unknown.write(node)
+    }
+}
+
+impl Measure {
+    pub fn is_implicit(&self) -> bool {
+        self.implicit == Some(YesNo::Yes)
+    }
+
+    /// Notes of this measure in document order
+    pub fn notes(&self) -> impl Iterator<Item = &Note> {
+        self.contents.iter().filter_map(|c| match c {
+            MeasureContent::Note(n) => Some(n),
+            _ => None,
+        })
+    }
+
+    /// Attributes of this measure, later ones change the earlier mid-measure
+    pub fn attributes(&self) -> impl Iterator<Item = &Attribute> {
+        self.contents.iter().filter_map(|c| match c {
+            MeasureContent::Attributes(a) => Some(a),
+            _ => None,
+        })
+    }
+
+    pub fn directions(&self) -> impl Iterator<Item = &Direction> {
+        self.contents.iter().filter_map(|c| match c {
+            MeasureContent::Direction(d) => Some(d.as_ref()),
+            _ => None,
+        })
+    }
+
+    pub fn barlines(&self) -> impl Iterator<Item = &Barline> {
+        self.contents.iter().filter_map(|c| match c {
+            MeasureContent::Barline(b) => Some(b),
+            _ => None,
+        })
+    }
+
+    /// Sounds of this measure, standalone or in directions, in document order
+    pub fn sounds(&self) -> impl Iterator<Item = &Sound> {
+        self.contents.iter().filter_map(|c| match c {
+            MeasureContent::Sound(s) => Some(s),
+            MeasureContent::Direction(d) => d.sound.as_ref(),
+            _ => None,
+        })
+    }
+
+    /// Chord symbols of this measure in document order
+    pub fn harmonies(&self) -> impl Iterator<Item = &Harmony> {
+        self.contents.iter().filter_map(|c| match c {
+            MeasureContent::Harmony(h) => Some(h),
+            _ => None,
+        })
+    }
+
+    /// Analysis groupings of this measure in document order
+    pub fn groupings(&self) -> impl Iterator<Item = &Grouping> {
+        self.contents.iter().filter_map(|c| match c {
+            MeasureContent::Grouping(g) => Some(g),
+            _ => None,
+        })
+    }
+
+    /// Layout changes and breaks of this measure
+    pub fn print(&self) -> Option<&Print> {
+        self.contents.iter().find_map(|c| match c {
+            MeasureContent::Print(p) => Some(p),
+            _ => None,
+        })
+    }
+
+    /// Notes with their onsets in divisions from the start of the measure,
+    /// following backup and forward moves of the musical position
+    pub fn note_onsets(&self) -> Vec<(u32, &Note)> {
+        self.positioned_contents()
+            .into_iter()
+            .filter_map(|(p, c)| match c {
+                MeasureContent::Note(n) => Some((p, n)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Directions with the divisions from the start of the measure at which they sound,
+    /// including offsets that affect playback
+    pub fn direction_onsets(&self) -> Vec<(u32, &Direction)> {
+        self.positioned_contents()
+            .into_iter()
+            .filter_map(|(p, c)| match c {
+                MeasureContent::Direction(d) => {
+                    let onset = (p as f64 + d.playback_offset()).round().max(0.);
+                    Some((onset as u32, d.as_ref()))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Length in divisions, the furthest position reached by the contents
+    pub fn duration(&self) -> u32 {
+        self.positioned_contents()
+            .into_iter()
+            .map(|(p, c)| match c {
+                MeasureContent::Note(n) => p.saturating_add(n.duration()),
+                MeasureContent::Forward(f) => p.saturating_add(f.duration),
+                _ => p,
+            })
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Copy without notes and moves of the musical position, silent for the given length
+    fn silenced(&self, length: u32) -> Measure {
+        let mut contents: Vec<_> = self
+            .contents
+            .iter()
+            .filter(|c| {
+                !matches!(
+                    c,
+                    MeasureContent::Note(_)
+                        | MeasureContent::Backup(_)
+                        | MeasureContent::Forward(_)
+                )
+            })
+            .cloned()
+            .collect();
+        contents.push(MeasureContent::Forward(Forward {
+            duration: length,
+            voice: None,
+            staff: None,
+            unknown: Unknown::default(),
+        }));
+
+        Measure {
+            contents,
+            ..self.clone()
+        }
+    }
+
+    /// Copy filled with the notes of the last beat of the previous measure,
+    /// repeated for the given length
+    fn with_repeated_beat(&self, previous: &Measure, beat: u32, length: u32) -> Measure {
+        let start = previous.duration().saturating_sub(beat);
+        let notes: Vec<_> = previous
+            .note_onsets()
+            .into_iter()
+            .filter(|(p, _)| *p >= start)
+            .collect();
+
+        let mut contents = self.contents.clone();
+        let mut position = 0u32;
+        for k in 0..length / beat {
+            for (p, note) in &notes {
+                if !note.chord {
+                    let target = k * beat + (p - start);
+                    if target > position {
+                        contents.push(MeasureContent::Forward(Forward {
+                            duration: target - position,
+                            voice: None,
+                            staff: None,
+                            unknown: Unknown::default(),
+                        }));
+                    } else if target < position {
+                        contents.push(MeasureContent::Backup(Backup {
+                            duration: position - target,
+                            unknown: Unknown::default(),
+                        }));
+                    }
+                    position = target.saturating_add(note.duration());
+                }
+                contents.push(MeasureContent::Note((*note).clone()));
+            }
+        }
+
+        Measure {
+            contents,
+            ..self.clone()
+        }
+    }
+
+    /// Contents with the musical position they occur at
+    fn positioned_contents(&self) -> Vec<(u32, &MeasureContent)> {
+        let mut contents = Vec::new();
+        let mut position = 0u32;
+        let mut last_onset = 0u32;
+        for content in &self.contents {
+            match content {
+                MeasureContent::Note(n) if n.chord => contents.push((last_onset, content)),
+                MeasureContent::Note(n) => {
+                    contents.push((position, content));
+                    last_onset = position;
+                    position = position.saturating_add(n.duration());
+                }
+                MeasureContent::Backup(b) => {
+                    contents.push((position, content));
+                    position = position.saturating_sub(b.duration);
+                }
+                MeasureContent::Forward(f) => {
+                    contents.push((position, content));
+                    position = position.saturating_add(f.duration);
+                }
+                _ => contents.push((position, content)),
+            }
+        }
+
+        contents
+    }
+
+    /// Notes on the given staff, notes without a staff belong to staff 1
+    pub fn staff_notes(&self, staff: u8) -> impl Iterator<Item = &Note> {
+        self.notes().filter(move |n| n.staff.unwrap_or(1) == staff)
+    }
+
+    /// Directions on the given staff, directions without a staff belong to staff 1
+    pub fn staff_directions(&self, staff: u8) -> impl Iterator<Item = &Direction> {
+        self.directions()
+            .filter(move |d| d.staff.unwrap_or(1) == staff)
+    }
+
+    /// Notes of the given voice, notes without a voice belong to voice "1"
+    pub fn voice_notes<'a>(&'a self, voice: &'a str) -> impl Iterator<Item = &'a Note> {
+        self.notes()
+            .filter(move |n| n.voice.as_deref().unwrap_or("1") == voice)
+    }
+
+    /// Staff a voice is written on, the staff of its first note
+    pub fn home_staff(&self, voice: &str) -> Option<u8> {
+        self.voice_notes(voice).next().map(|n| n.staff.unwrap_or(1))
+    }
+
+    /// Notes written on another staff than the home staff of their voice
+    pub fn cross_staff_notes(&self) -> Vec<&Note> {
+        self.notes()
+            .filter(|n| {
+                let voice = n.voice.as_deref().unwrap_or("1");
+                self.home_staff(voice) != Some(n.staff.unwrap_or(1))
+            })
+            .collect()
+    }
+
+    /// Notes joined by a primary beam, grouped per voice in the order the beams end
+    pub fn beam_groups(&self) -> Vec<Vec<&Note>> {
+        let mut groups = Vec::new();
+        let mut open: Vec<(&str, Vec<&Note>)> = Vec::new();
+        for note in self.notes() {
+            let voice = note.voice.as_deref().unwrap_or("1");
+            let i = open.iter().position(|(v, _)| *v == voice);
+            match (note.beam.iter().find(|b| b.number.unwrap_or(1) == 1), i) {
+                (Some(b), _) if b.value == BeamValue::Begin => {
+                    if let Some(i) = i {
+                        open.remove(i);
+                    }
+                    open.push((voice, vec![note]));
+                }
+                (Some(b), Some(i)) if b.value == BeamValue::End => {
+                    let (_, mut group) = open.remove(i);
+                    group.push(note);
+                    groups.push(group);
+                }
+                (Some(_), Some(i)) => open[i].1.push(note),
+                // chord notes often leave out the beams of the chord
+                (None, Some(i)) if note.chord => open[i].1.push(note),
+                _ => (),
+            }
+        }
+
+        groups
+    }
+
+    /// Beam groups spanning more than one staff, e.g. in piano music
+    pub fn cross_staff_beams(&self) -> Vec<Vec<&Note>> {
+        self.beam_groups()
+            .into_iter()
+            .filter(|g| {
+                let staff = g[0].staff.unwrap_or(1);
+                g.iter().any(|n| n.staff.unwrap_or(1) != staff)
+            })
+            .collect()
+    }
+
+    /// Group notes sounding at the same time, a note followed by its chord notes
+    pub fn chords(&self) -> Vec<Vec<&Note>> {
+        let mut chords: Vec<Vec<&Note>> = Vec::new();
+        for note in self.notes() {
+            match chords.last_mut() {
+                Some(chord) if note.chord => chord.push(note),
+                _ => chords.push(vec![note]),
+            }
+        }
+
+        chords
+    }
+
+    /// Metronome marks of the directions in this measure
+    pub fn metronomes(&self) -> impl Iterator<Item = &Metronome> {
+        self.directions().filter_map(Direction::metronome)
+    }
+
+    /// Tempo in quarter notes per minute set in this measure,
+    /// preferring the playback tempo of sound over the metronome mark
+    pub fn tempo(&self) -> Option<f64> {
+        self.sounds()
+            .find_map(|s| s.tempo)
+            .or_else(|| self.metronomes().find_map(Metronome::quarters_per_minute))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Part {
+    pub id: String,
+    pub measures: Vec<Measure>,
+    pub unknown: Unknown,
+}
+
+impl FromNode for Part {
+    fn tag() -> &'static str {
+        "part"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        Ok(Part {
+            id: parse_attr(node, "id")?,
+            measures: parse_children(node)?,
+            unknown: Unknown::parse(node, &["id"], &["measure"]),
+        })
+    }
+}
+
+impl ToNode for Part {
+    fn to_node(&self) -> Element {
+        let node = Element::new(Self::tag())
+            .with_attr("id", &self.id)
+            .with_children(children_to_nodes(&self.measures));
+        self.unknown.write(node)
+    }
+}
+
+impl Part {
+    /// Position of the measure with the given number in this part
+    pub fn measure_index(&self, number: &str) -> Option<usize> {
+        self.measures.iter().position(|m| m.number == number)
+    }
+
+    /// Measures in playback order of their content, materializing condensed notation:
+    /// a measure inside a measure repeat is replaced by the measure it repeats,
+    /// a measure under a multiple rest by a silent one, and an empty measure inside
+    /// a beat repeat is filled with the last beat of the previous measure.
+    /// Slash notation only changes how the written notes look, they are played as is
+    pub fn playback_measures(&self) -> Vec<Cow<'_, Measure>> {
+        let mut measures: Vec<Cow<Measure>> = Vec::new();
+        let mut measure_repeat: Option<(usize, usize)> = None;
+        let mut beat_repeat = false;
+        // index of the first measure after a multiple rest
+        let mut rest_end = 0;
+        let mut divisions: Option<NonZeroU32> = None;
+        let mut time: Option<&Time> = None;
+        for (i, measure) in self.measures.iter().enumerate() {
+            for attributes in measure.attributes() {
+                divisions = attributes.divisions.or(divisions);
+                time = attributes.time.first().or(time);
+                for style in &attributes.measure_style {
+                    match style.style {
+                        MeasureStyleType::MeasureRepeat {
+                            repeat_type,
+                            measures: len,
+                            ..
+                        } => {
+                            measure_repeat = match (repeat_type, len.unwrap_or(1)) {
+                                // nothing to repeat
+                                (StartStop::Start, 0) => None,
+                                (StartStop::Start, len) => Some((i, len as usize)),
+                                (StartStop::Stop, _) => None,
+                            }
+                        }
+                        MeasureStyleType::MultipleRest { count, .. } => {
+                            rest_end = i.saturating_add(count as usize)
+                        }
+                        MeasureStyleType::BeatRepeat { repeat_type, .. } => {
+                            beat_repeat = repeat_type == StartStop::Start
+                        }
+                        MeasureStyleType::Slash { .. } => {}
+                    }
+                }
+            }
+
+            let length = divisions
+                .zip(time)
+                .and_then(|(d, t)| t.measure_duration(d.get()))
+                .unwrap_or_else(|| measure.duration());
+            let beat = divisions.zip(time).and_then(|(d, t)| {
+                let beat_type = t.signatures().first()?.beat_type;
+                d.get().checked_mul(4)?.checked_div(beat_type as u32)
+            });
+
+            let played = match (measure_repeat, beat, measures.last()) {
+                (Some((start, len)), _, _) if len <= start => {
+                    measures[start - len + (i - start) % len].clone()
+                }
+                _ if i < rest_end => Cow::Owned(measure.silenced(length)),
+                (_, Some(beat), Some(previous))
+                    if beat_repeat && beat > 0 && measure.notes().next().is_none() =>
+                {
+                    Cow::Owned(measure.with_repeated_beat(previous, beat, length))
+                }
+                _ => Cow::Borrowed(measure),
+            };
+            measures.push(played);
+        }
+
+        measures
+    }
+
+    /// Rehearsal marks with the index of the measure they appear in
+    pub fn rehearsals(&self) -> Vec<(usize, &Rehearsal)> {
+        self.measures
+            .iter()
+            .enumerate()
+            .flat_map(|(i, m)| {
+                m.directions()
+                    .flat_map(Direction::rehearsals)
+                    .map(move |r| (i, r))
+            })
+            .collect()
+    }
+
+    /// Principal voice sections with the indices of the measures they start and
+    /// stop in, a section left open stops in the last measure
+    pub fn principal_voice_sections(&self) -> Vec<(usize, usize, &PrincipalVoice)> {
+        let mut sections = Vec::new();
+        let mut open = Vec::new();
+        for (i, measure) in self.measures.iter().enumerate() {
+            for voice in measure.directions().filter_map(Direction::principal_voice) {
+                match voice.principal_voice_type {
+                    StartStop::Start => open.push((i, voice)),
+                    StartStop::Stop => {
+                        if let Some((start, v)) = open.pop() {
+                            sections.push((start, i, v));
+                        }
+                    }
+                }
+            }
+        }
+        let last = self.measures.len().saturating_sub(1);
+        sections.extend(open.into_iter().rev().map(|(start, v)| (start, last, v)));
+
+        sections
+    }
+
+    /// Notes with their onsets from the start of the part, following the
+    /// divisions set by the attributes of each measure
+    pub fn note_times(&self) -> Vec<(Duration, &Note)> {
+        let mut times = Vec::new();
+        let mut start = Duration::ZERO;
+        let mut divisions = NonZeroU32::MIN;
+        for measure in &self.measures {
+            if let Some(d) = measure.attributes().find_map(|a| a.divisions) {
+                divisions = d;
+            }
+            times.extend(
+                measure
+                    .note_onsets()
+                    .into_iter()
+                    .map(|(p, n)| (start + Duration::from_divisions(p, divisions), n)),
+            );
+            start = start + Duration::from_divisions(measure.duration(), divisions);
+        }
+
+        times
+    }
+
+    /// Sequential bar number of each measure, implicit measures continue
+    /// the previous bar number, so a leading pickup is bar 0
+    pub fn bar_numbers(&self) -> Vec<u32> {
+        let mut bar = 0;
+        self.measures
+            .iter()
+            .map(|m| {
+                if !m.is_implicit() {
+                    bar += 1;
+                }
+                bar
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use roxmltree::Document;
+
+    use super::*;
+    use crate::score::{BeatUnit, MetronomeKind, NoteTypeValue, PrincipalVoiceSymbol, Step};
+
+    #[test]
+    fn measure_tempo_ok() {
+        let xml = r#"
+            <measure number="1">
+                <direction placement="above">
+                    <direction-type>
+                        <metronome parentheses="no">
+                            <beat-unit>quarter</beat-unit>
+                            <beat-unit-dot />
+                            <per-minute>80</per-minute>
+                        </metronome>
+                    </direction-type>
+                </direction>
+            </measure>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let measure = Measure::from_node(&node).unwrap();
+        assert_eq!(
+            measure.metronomes().collect::<Vec<_>>(),
+            vec![&Metronome {
+                kind: MetronomeKind::PerMinute {
+                    beat_unit: BeatUnit {
+                        value: NoteTypeValue::Quarter,
+                        dot: 1,
+                        tied: Vec::new()
+                    },
+                    per_minute: "80".to_owned()
+                },
+                unknown: Unknown {
+                    attrs: vec![("parentheses".to_owned(), "no".to_owned())],
+                    children: vec![],
+                }
+            }]
+        );
+        assert_eq!(measure.tempo(), Some(120.));
+    }
+
+    #[test]
+    fn measure_unknown_ok() {
+        let xml = r#"
+            <measure number="1" text="i">
+                <figured-bass>
+                    <figure>
+                        <figure-number>6</figure-number>
+                    </figure>
+                    <duration>4</duration>
+                </figured-bass>
+                <note attack="-5">
+                    <pitch>
+                        <step>C</step>
+                        <alter>1</alter>
+                        <octave>4</octave>
+                    </pitch>
+                    <duration>4</duration>
+                    <voice>1</voice>
+                    <type>quarter</type>
+                    <accidental>sharp</accidental>
+                    <stem>up</stem>
+                </note>
+            </measure>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let measure = Measure::from_node(&node).unwrap();
+        assert_eq!(
+            measure.unknown.attrs,
+            vec![("text".to_owned(), "i".to_owned())]
+        );
+        assert_eq!(measure.unknown.children[0].0, None);
+        assert_eq!(measure.unknown.children[0].1.name, "figured-bass");
+        let note = measure.notes().next().unwrap();
+        assert_eq!(
+            note.unknown.children,
+            vec![(
+                Some(("type".to_owned(), 0)),
+                Element::new("accidental").with_text("sharp")
+            )]
+        );
+        assert_eq!(measure.to_node(), Element::from(&node));
+    }
+
+    #[test]
+    fn measure_nested_unknown_ok() {
+        let xml = r#"
+            <measure number="1">
+                <print new-system="yes">
+                    <system-layout>
+                        <system-distance>120</system-distance>
+                    </system-layout>
+                    <part-name-display>
+                        <display-text>Fl.</display-text>
+                    </part-name-display>
+                </print>
+                <note>
+                    <pitch>
+                        <step>C</step>
+                        <octave>5</octave>
+                    </pitch>
+                    <duration>4</duration>
+                    <type>quarter</type>
+                    <notations>
+                        <tied type="start" />
+                        <other-notation type="single" smufl="pictSwishStem">swish</other-notation>
+                        <fermata>normal</fermata>
+                    </notations>
+                </note>
+            </measure>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let measure = Measure::from_node(&node).unwrap();
+        match &measure.contents[0] {
+            MeasureContent::Print(print) => {
+                assert_eq!(print.unknown.children.len(), 1);
+                assert_eq!(
+                    print.unknown.children[0].0,
+                    Some(("system-layout".to_owned(), 0))
+                );
+            }
+            _ => panic!("print expected"),
+        }
+        let note = measure.notes().next().unwrap();
+        assert_eq!(
+            note.notations[0].unknown.children[0].0,
+            Some(("tied".to_owned(), 0))
+        );
+        assert_eq!(measure.to_node(), Element::from(&node));
+    }
+
+    #[test]
+    fn measure_chords_ok() {
+        let xml = r#"
+            <measure number="1">
+                <note>
+                    <pitch><step>C</step><octave>4</octave></pitch>
+                    <duration>2</duration>
+                </note>
+                <note>
+                    <chord />
+                    <pitch><step>E</step><octave>4</octave></pitch>
+                    <duration>2</duration>
+                </note>
+                <note>
+                    <rest />
+                    <duration>2</duration>
+                </note>
+            </measure>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let measure = Measure::from_node(&node).unwrap();
+        let chords = measure.chords();
+        assert_eq!(chords.len(), 2);
+        assert_eq!(chords[0].len(), 2);
+        assert_eq!(chords[1].len(), 1);
+    }
+
+    #[test]
+    fn measure_voice_notes_ok() {
+        let xml = r#"
+            <measure number="1">
+                <note>
+                    <pitch><step>G</step><octave>4</octave></pitch>
+                    <duration>4</duration>
+                    <voice>1</voice>
+                </note>
+                <note>
+                    <pitch><step>C</step><octave>4</octave></pitch>
+                    <duration>2</duration>
+                    <voice>2</voice>
+                </note>
+                <note>
+                    <pitch><step>D</step><octave>4</octave></pitch>
+                    <duration>2</duration>
+                    <voice>2</voice>
+                </note>
+            </measure>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let measure = Measure::from_node(&node).unwrap();
+        assert_eq!(measure.voice_notes("1").count(), 1);
+        assert_eq!(measure.voice_notes("2").count(), 2);
+        assert_eq!(measure.voice_notes("3").count(), 0);
+    }
+
+    #[test]
+    fn measure_cross_staff_beams_ok() {
+        let xml = r#"
+            <measure number="1">
+                <note>
+                    <pitch><step>C</step><octave>3</octave></pitch>
+                    <duration>1</duration>
+                    <voice>5</voice>
+                    <staff>2</staff>
+                    <beam number="1">begin</beam>
+                </note>
+                <note>
+                    <pitch><step>G</step><octave>4</octave></pitch>
+                    <duration>1</duration>
+                    <voice>5</voice>
+                    <staff>1</staff>
+                    <beam number="1">end</beam>
+                </note>
+                <note>
+                    <pitch><step>E</step><octave>3</octave></pitch>
+                    <duration>1</duration>
+                    <voice>5</voice>
+                    <staff>2</staff>
+                    <beam number="1">begin</beam>
+                </note>
+                <note>
+                    <pitch><step>F</step><octave>3</octave></pitch>
+                    <duration>1</duration>
+                    <voice>5</voice>
+                    <staff>2</staff>
+                    <beam number="1">end</beam>
+                </note>
+            </measure>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let measure = Measure::from_node(&node).unwrap();
+        assert_eq!(measure.home_staff("5"), Some(2));
+        let cross = measure.cross_staff_notes();
+        assert_eq!(cross.len(), 1);
+        assert_eq!(cross[0].staff, Some(1));
+
+        assert_eq!(measure.beam_groups().len(), 2);
+        let beams = measure.cross_staff_beams();
+        assert_eq!(beams.len(), 1);
+        assert_eq!(beams[0].len(), 2);
+    }
+
+    #[test]
+    fn measure_staff_notes_ok() {
+        let xml = r#"
+            <measure number="1">
+                <direction>
+                    <direction-type>
+                        <words>legato</words>
+                    </direction-type>
+                    <staff>2</staff>
+                </direction>
+                <note>
+                    <pitch><step>E</step><octave>5</octave></pitch>
+                    <duration>4</duration>
+                    <staff>1</staff>
+                </note>
+                <note>
+                    <pitch><step>C</step><octave>3</octave></pitch>
+                    <duration>4</duration>
+                    <staff>2</staff>
+                </note>
+            </measure>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let measure = Measure::from_node(&node).unwrap();
+        let bass: Vec<_> = measure.staff_notes(2).collect();
+        assert_eq!(bass.len(), 1);
+        assert_eq!(bass[0].staff, Some(2));
+        assert_eq!(measure.staff_notes(1).count(), 1);
+        assert_eq!(measure.staff_directions(1).count(), 0);
+        assert_eq!(measure.staff_directions(2).count(), 1);
+    }
+
+    #[test]
+    fn measure_backup_forward_ok() {
+        let xml = r#"
+            <measure number="1">
+                <note>
+                    <pitch><step>E</step><octave>5</octave></pitch>
+                    <duration>4</duration>
+                    <voice>1</voice>
+                </note>
+                <note>
+                    <pitch><step>G</step><octave>5</octave></pitch>
+                    <duration>4</duration>
+                    <voice>1</voice>
+                </note>
+                <backup>
+                    <duration>8</duration>
+                </backup>
+                <forward>
+                    <duration>4</duration>
+                    <voice>2</voice>
+                </forward>
+                <note>
+                    <pitch><step>C</step><octave>4</octave></pitch>
+                    <duration>4</duration>
+                    <voice>2</voice>
+                </note>
+                <note>
+                    <chord />
+                    <pitch><step>E</step><octave>4</octave></pitch>
+                    <duration>4</duration>
+                    <voice>2</voice>
+                </note>
+            </measure>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let measure = Measure::from_node(&node).unwrap();
+        assert_eq!(measure.contents.len(), 6);
+        assert_eq!(
+            measure.contents[2],
+            MeasureContent::Backup(Backup {
+                duration: 8,
+                unknown: Unknown::default()
+            })
+        );
+        let onsets: Vec<_> = measure.note_onsets().iter().map(|(o, _)| *o).collect();
+        assert_eq!(onsets, vec![0, 4, 4, 4]);
+    }
+
+    #[test]
+    fn measure_contents_order_ok() {
+        let xml = r#"
+            <measure number="1">
+                <barline location="left">
+                    <repeat direction="forward" />
+                </barline>
+                <note>
+                    <pitch><step>C</step><octave>4</octave></pitch>
+                    <duration>1</duration>
+                </note>
+                <attributes>
+                    <clef><sign>F</sign><line>4</line></clef>
+                </attributes>
+                <note>
+                    <pitch><step>C</step><octave>3</octave></pitch>
+                    <duration>1</duration>
+                </note>
+            </measure>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let measure = Measure::from_node(&node).unwrap();
+        assert!(matches!(measure.contents[0], MeasureContent::Barline(_)));
+        assert!(matches!(measure.contents[2], MeasureContent::Attributes(_)));
+        assert_eq!(measure.attributes().count(), 1);
+
+        let el = measure.to_node();
+        let names: Vec<_> = el.children.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, vec!["barline", "note", "attributes", "note"]);
+    }
+
+    #[test]
+    fn measure_direction_onsets_ok() {
+        let xml = r#"
+            <measure number="1">
+                <note>
+                    <rest />
+                    <duration>4</duration>
+                </note>
+                <direction>
+                    <direction-type>
+                        <words>rit.</words>
+                    </direction-type>
+                    <offset>2</offset>
+                </direction>
+                <direction>
+                    <direction-type>
+                        <words>a tempo</words>
+                    </direction-type>
+                    <offset sound="yes">2</offset>
+                </direction>
+                <note>
+                    <rest />
+                    <duration>4</duration>
+                </note>
+            </measure>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let measure = Measure::from_node(&node).unwrap();
+        let onsets: Vec<u32> = measure.direction_onsets().iter().map(|(o, _)| *o).collect();
+        assert_eq!(onsets, vec![4, 6]);
+    }
+
+    #[test]
+    fn measure_grouping_ok() {
+        let xml = r#"
+            <measure number="1">
+                <grouping type="start" number="2" member-of="1">
+                    <feature type="phrase">antecedent</feature>
+                </grouping>
+                <note>
+                    <rest />
+                    <duration>4</duration>
+                </note>
+                <grouping type="stop" number="2" />
+            </measure>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let measure = Measure::from_node(&node).unwrap();
+        let groupings: Vec<_> = measure.groupings().collect();
+        assert_eq!(
+            groupings[0],
+            &Grouping {
+                grouping_type: StartStopSingle::Start,
+                number: "2".to_owned(),
+                member_of: Some("1".to_owned()),
+                features: vec![Feature {
+                    feature_type: Some("phrase".to_owned()),
+                    text: Some("antecedent".to_owned()),
+                    unknown: Unknown::default()
+                }],
+                unknown: Unknown::default()
+            }
+        );
+        assert_eq!(groupings[1].grouping_type, StartStopSingle::Stop);
+        assert_eq!(measure.to_node().children[2].name, "grouping");
+    }
+
+    #[test]
+    fn measure_standalone_sound_ok() {
+        let xml = r#"
+            <measure number="1">
+                <sound tempo="72" damper-pedal="yes" />
+                <note>
+                    <rest />
+                    <duration>4</duration>
+                </note>
+            </measure>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let measure = Measure::from_node(&node).unwrap();
+        assert!(matches!(measure.contents[0], MeasureContent::Sound(_)));
+        assert_eq!(measure.sounds().count(), 1);
+        assert_eq!(measure.tempo(), Some(72.));
+    }
+
+    #[test]
+    fn measure_sound_tempo_ok() {
+        let xml = r#"
+            <measure number="1">
+                <direction>
+                    <direction-type>
+                        <metronome>
+                            <beat-unit>half</beat-unit>
+                            <per-minute>c. 60</per-minute>
+                        </metronome>
+                    </direction-type>
+                    <sound tempo="116.5" />
+                </direction>
+            </measure>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let measure = Measure::from_node(&node).unwrap();
+        assert_eq!(measure.directions().count(), 1);
+        assert_eq!(
+            measure.metronomes().next().unwrap().quarters_per_minute(),
+            None
+        );
+        assert_eq!(measure.tempo(), Some(116.5));
+    }
+
+    #[test]
+    fn part_bar_numbers_ok() {
+        let xml = r#"
+            <part id="P1">
+                <measure number="0" implicit="yes" width="120.5" />
+                <measure number="1" />
+                <measure number="2" />
+                <measure number="X1" implicit="yes" non-controlling="yes" />
+                <measure number="3" />
+            </part>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let part = Part::from_node(&node).unwrap();
+        assert!(part.measures[0].is_implicit());
+        assert_eq!(part.measures[0].width, Some(Tenths(120.5)));
+        assert_eq!(part.measures[3].non_controlling, Some(YesNo::Yes));
+        assert_eq!(part.measure_index("X1"), Some(3));
+        assert_eq!(part.measure_index("4"), None);
+        assert_eq!(part.bar_numbers(), vec![0, 1, 2, 2, 3]);
+    }
+
+    #[test]
+    fn measure_huge_durations_ok() {
+        let xml = r#"
+            <measure number="1">
+                <note>
+                    <rest />
+                    <duration>4294967295</duration>
+                </note>
+                <forward>
+                    <duration>4294967295</duration>
+                </forward>
+                <note>
+                    <rest />
+                    <duration>1</duration>
+                </note>
+            </measure>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let measure = Measure::from_node(&node).unwrap();
+        assert_eq!(measure.duration(), u32::MAX);
+        assert_eq!(measure.note_onsets()[1].0, u32::MAX);
+    }
+
+    #[test]
+    fn part_playback_measures_ok() {
+        let xml = r#"
+            <part id="P1">
+                <measure number="1" />
+                <measure number="2" />
+                <measure number="3">
+                    <attributes>
+                        <measure-style>
+                            <measure-repeat type="start">2</measure-repeat>
+                        </measure-style>
+                    </attributes>
+                </measure>
+                <measure number="4" />
+                <measure number="5" />
+                <measure number="6">
+                    <attributes>
+                        <measure-style>
+                            <measure-repeat type="stop" />
+                        </measure-style>
+                    </attributes>
+                </measure>
+            </part>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let part = Part::from_node(&node).unwrap();
+        let measures = part.playback_measures();
+        let numbers: Vec<_> = measures.iter().map(|m| m.number.as_str()).collect();
+        assert_eq!(numbers, vec!["1", "2", "1", "2", "1", "6"]);
+
+        let xml = r#"
+            <part id="P1">
+                <measure number="1" />
+                <measure number="2">
+                    <attributes>
+                        <measure-style>
+                            <measure-repeat type="start">0</measure-repeat>
+                        </measure-style>
+                    </attributes>
+                </measure>
+            </part>"#;
+        let doc = Document::parse(xml).unwrap();
+        let part = Part::from_node(&doc.root_element()).unwrap();
+        let measures = part.playback_measures();
+        let numbers: Vec<_> = measures.iter().map(|m| m.number.as_str()).collect();
+        assert_eq!(numbers, vec!["1", "2"]);
+    }
+
+    #[test]
+    fn part_playback_multiple_rest_ok() {
+        let xml = r#"
+            <part id="P1">
+                <measure number="1">
+                    <attributes>
+                        <divisions>2</divisions>
+                        <time>
+                            <beats>3</beats>
+                            <beat-type>4</beat-type>
+                        </time>
+                        <measure-style>
+                            <multiple-rest>2</multiple-rest>
+                        </measure-style>
+                    </attributes>
+                </measure>
+                <measure number="2">
+                    <note>
+                        <rest measure="yes" />
+                        <duration>6</duration>
+                    </note>
+                </measure>
+                <measure number="3">
+                    <note>
+                        <rest />
+                        <duration>6</duration>
+                    </note>
+                </measure>
+            </part>"#;
+        let doc = Document::parse(xml).unwrap();
+        let part = Part::from_node(&doc.root_element()).unwrap();
+
+        let measures = part.playback_measures();
+        for m in &measures[..2] {
+            assert_eq!(m.notes().count(), 0);
+            assert_eq!(m.duration(), 6);
+        }
+        assert!(matches!(measures[2], Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn part_playback_beat_repeat_ok() {
+        let xml = r#"
+            <part id="P1">
+                <measure number="1">
+                    <attributes>
+                        <divisions>2</divisions>
+                        <time>
+                            <beats>2</beats>
+                            <beat-type>4</beat-type>
+                        </time>
+                    </attributes>
+                    <note>
+                        <pitch>
+                            <step>C</step>
+                            <octave>4</octave>
+                        </pitch>
+                        <duration>2</duration>
+                    </note>
+                    <note>
+                        <pitch>
+                            <step>E</step>
+                            <octave>4</octave>
+                        </pitch>
+                        <duration>1</duration>
+                    </note>
+                    <note>
+                        <chord />
+                        <pitch>
+                            <step>G</step>
+                            <octave>4</octave>
+                        </pitch>
+                        <duration>1</duration>
+                    </note>
+                    <note>
+                        <pitch>
+                            <step>F</step>
+                            <octave>4</octave>
+                        </pitch>
+                        <duration>1</duration>
+                    </note>
+                </measure>
+                <measure number="2">
+                    <attributes>
+                        <measure-style>
+                            <beat-repeat type="start" slashes="1" />
+                        </measure-style>
+                    </attributes>
+                </measure>
+                <measure number="3">
+                    <attributes>
+                        <measure-style>
+                            <beat-repeat type="stop" />
+                        </measure-style>
+                    </attributes>
+                </measure>
+            </part>"#;
+        let doc = Document::parse(xml).unwrap();
+        let part = Part::from_node(&doc.root_element()).unwrap();
+
+        let measures = part.playback_measures();
+        let onsets: Vec<_> = measures[1]
+            .note_onsets()
+            .into_iter()
+            .map(|(p, n)| (p, n.sounding_pitch(None).unwrap().step))
+            .collect();
+        assert_eq!(
+            onsets,
+            vec![
+                (0, Step::E),
+                (0, Step::G),
+                (1, Step::F),
+                (2, Step::E),
+                (2, Step::G),
+                (3, Step::F)
+            ]
+        );
+        assert_eq!(measures[2].notes().count(), 0);
+    }
+
+    #[test]
+    fn part_playback_slash_ok() {
+        let xml = r#"
+            <part id="P1">
+                <measure number="1">
+                    <note>
+                        <rest />
+                        <duration>4</duration>
+                    </note>
+                </measure>
+                <measure number="2">
+                    <attributes>
+                        <measure-style>
+                            <slash type="start" use-stems="no" />
+                        </measure-style>
+                    </attributes>
+                    <note>
+                        <pitch>
+                            <step>B</step>
+                            <octave>4</octave>
+                        </pitch>
+                        <duration>4</duration>
+                    </note>
+                </measure>
+                <measure number="3" />
+            </part>"#;
+        let doc = Document::parse(xml).unwrap();
+        let part = Part::from_node(&doc.root_element()).unwrap();
+
+        let measures = part.playback_measures();
+        assert!(measures.iter().all(|m| matches!(m, Cow::Borrowed(_))));
+        assert_eq!(measures[2].notes().count(), 0);
+    }
+
+    #[test]
+    fn part_principal_voice_sections_ok() {
+        let xml = r#"
+            <part id="P1">
+                <measure number="1">
+                    <direction>
+                        <direction-type>
+                            <principal-voice type="start" symbol="Hauptstimme" />
+                        </direction-type>
+                    </direction>
+                </measure>
+                <measure number="2">
+                    <direction>
+                        <direction-type>
+                            <principal-voice type="stop" symbol="none" />
+                        </direction-type>
+                    </direction>
+                    <direction>
+                        <direction-type>
+                            <principal-voice type="start" symbol="Nebenstimme" />
+                        </direction-type>
+                    </direction>
+                </measure>
+                <measure number="3" />
+            </part>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let part = Part::from_node(&node).unwrap();
+        let sections: Vec<_> = part
+            .principal_voice_sections()
+            .into_iter()
+            .map(|(start, stop, v)| (start, stop, v.symbol))
+            .collect();
+        assert_eq!(
+            sections,
+            vec![
+                (0, 1, PrincipalVoiceSymbol::Hauptstimme),
+                (1, 2, PrincipalVoiceSymbol::Nebenstimme)
+            ]
+        );
+    }
+
+    #[test]
+    fn part_note_times_ok() {
+        let xml = r#"
+            <part id="P1">
+                <measure number="1">
+                    <attributes>
+                        <divisions>2</divisions>
+                    </attributes>
+                    <note>
+                        <pitch><step>C</step><octave>4</octave></pitch>
+                        <duration>8</duration>
+                    </note>
+                </measure>
+                <measure number="2">
+                    <attributes>
+                        <divisions>768</divisions>
+                    </attributes>
+                    <note>
+                        <pitch><step>D</step><octave>4</octave></pitch>
+                        <duration>1536</duration>
+                    </note>
+                    <note>
+                        <pitch><step>E</step><octave>4</octave></pitch>
+                        <duration>1536</duration>
+                    </note>
+                </measure>
+            </part>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let part = Part::from_node(&node).unwrap();
+        let times: Vec<_> = part
+            .note_times()
+            .into_iter()
+            .map(|(t, _)| t.to_ticks(480))
+            .collect();
+        assert_eq!(times, vec![0, 1920, 2880]);
+        assert_eq!(part.measures[1].duration(), 3072);
+    }
+}
@@ -0,0 +1,534 @@
+use roxmltree::Node;
+
+use super::{
+    children_to_nodes, count_chd, parse_attr, parse_chd_text, parse_children, parse_optional_attr,
+    parse_optional_chd_text, FromNode, StartStop, ToNode, Unknown,
+};
+use crate::{
+    error::{Error::NodeTextEmpty, Result},
+    writer::Element,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScoreInstrument {
+    pub id: String,
+    pub instrument_name: String,
+    pub instrument_abbreviation: Option<String>,
+    pub unknown: Unknown,
+}
+
+impl FromNode for ScoreInstrument {
+    fn tag() -> &'static str {
+        "score-instrument"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        Ok(ScoreInstrument {
+            id: parse_attr(node, "id")?,
+            instrument_name: parse_chd_text(node, "instrument-name")?,
+            instrument_abbreviation: parse_optional_chd_text(node, "instrument-abbreviation")?,
+            unknown: Unknown::parse(
+                node,
+                &["id"],
+                &["instrument-name", "instrument-abbreviation"],
+            ),
+        })
+    }
+}
+
+impl ToNode for ScoreInstrument {
+    fn to_node(&self) -> Element {
+        let node = Element::new(Self::tag())
+            .with_attr("id", &self.id)
+            .with_chd_text("instrument-name", &self.instrument_name)
+            .with_optional_chd_text(
+                "instrument-abbreviation",
+                self.instrument_abbreviation.as_ref(),
+            );
+        self.unknown.write(node)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Performers {
+    Solo,
+    /// Section of the given size, unknown if absent
+    Ensemble(Option<u32>),
+}
+
+/// Switch of a score instrument to another sound, e.g. a flute player doubling on piccolo
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InstrumentChange {
+    /// Id of the score instrument that changes
+    pub id: String,
+    /// Standard sound name, e.g. "wind.flutes.flute.piccolo"
+    pub instrument_sound: Option<String>,
+    pub performers: Option<Performers>,
+    pub unknown: Unknown,
+}
+
+impl FromNode for InstrumentChange {
+    fn tag() -> &'static str {
+        "instrument-change"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        let performers = if count_chd(node, "solo") > 0 {
+            Some(Performers::Solo)
+        } else if count_chd(node, "ensemble") > 0 {
+            Some(Performers::Ensemble(parse_optional_chd_text(
+                node, "ensemble",
+            )?))
+        } else {
+            None
+        };
+
+        Ok(InstrumentChange {
+            id: parse_attr(node, "id")?,
+            instrument_sound: parse_optional_chd_text(node, "instrument-sound")?,
+            performers,
+            unknown: Unknown::parse(node, &["id"], &["instrument-sound", "solo", "ensemble"]),
+        })
+    }
+}
+
+impl ToNode for InstrumentChange {
+    fn to_node(&self) -> Element {
+        let node = Element::new(Self::tag())
+            .with_attr("id", &self.id)
+            .with_optional_chd_text("instrument-sound", self.instrument_sound.as_ref())
+            .with_optional_child(self.performers.map(|p| match p {
+                Performers::Solo => Element::new("solo"),
+                Performers::Ensemble(size) => Element::new("ensemble").with_optional_text(size),
+            }));
+        self.unknown.write(node)
+    }
+}
+
+/// Performer of a part shared by several players, e.g. the desks of a violin section
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Player {
+    pub id: String,
+    pub player_name: String,
+    pub unknown: Unknown,
+}
+
+impl FromNode for Player {
+    fn tag() -> &'static str {
+        "player"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        Ok(Player {
+            id: parse_attr(node, "id")?,
+            player_name: parse_chd_text(node, "player-name")?,
+            unknown: Unknown::parse(node, &["id"], &["player-name"]),
+        })
+    }
+}
+
+impl ToNode for Player {
+    fn to_node(&self) -> Element {
+        let node = Element::new(Self::tag())
+            .with_attr("id", &self.id)
+            .with_chd_text("player-name", &self.player_name);
+        self.unknown.write(node)
+    }
+}
+
+/// MIDI playback setup of a score instrument, referenced by the same id
+#[derive(Debug, Clone, PartialEq)]
+pub struct MidiInstrument {
+    pub id: String,
+    /// Channel from 1 to 16
+    pub midi_channel: Option<u8>,
+    /// General MIDI program from 1 to 128
+    pub midi_program: Option<u8>,
+    /// General MIDI percussion key from 1 to 128, for unpitched instruments
+    pub midi_unpitched: Option<u8>,
+    /// Percentage of the maximum volume
+    pub volume: Option<f64>,
+    /// Degrees from -180 to 180, 0 at the center
+    pub pan: Option<f64>,
+    pub unknown: Unknown,
+}
+
+impl FromNode for MidiInstrument {
+    fn tag() -> &'static str {
+        "midi-instrument"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        Ok(MidiInstrument {
+            id: parse_attr(node, "id")?,
+            midi_channel: parse_optional_chd_text(node, "midi-channel")?,
+            midi_program: parse_optional_chd_text(node, "midi-program")?,
+            midi_unpitched: parse_optional_chd_text(node, "midi-unpitched")?,
+            volume: parse_optional_chd_text(node, "volume")?,
+            pan: parse_optional_chd_text(node, "pan")?,
+            unknown: Unknown::parse(
+                node,
+                &["id"],
+                &[
+                    "midi-channel",
+                    "midi-program",
+                    "midi-unpitched",
+                    "volume",
+                    "pan",
+                ],
+            ),
+        })
+    }
+}
+
+impl ToNode for MidiInstrument {
+    fn to_node(&self) -> Element {
+        let node = Element::new(Self::tag())
+            .with_attr("id", &self.id)
+            .with_optional_chd_text("midi-channel", self.midi_channel)
+            .with_optional_chd_text("midi-program", self.midi_program)
+            .with_optional_chd_text("midi-unpitched", self.midi_unpitched)
+            .with_optional_chd_text("volume", self.volume)
+            .with_optional_chd_text("pan", self.pan);
+        self.unknown.write(node)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScorePart {
+    pub id: String,
+    pub part_name: String,
+    pub part_abbreviation: Option<String>,
+    pub score_instrument: Vec<ScoreInstrument>,
+    pub player: Vec<Player>,
+    pub midi_instrument: Vec<MidiInstrument>,
+    pub unknown: Unknown,
+}
+
+impl FromNode for ScorePart {
+    fn tag() -> &'static str {
+        "score-part"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        Ok(ScorePart {
+            id: parse_attr(node, "id")?,
+            // part-name is required but may be left empty
+            part_name: parse_chd_text(node, "part-name").or_else(|e| match e {
+                NodeTextEmpty { .. } => Ok(String::new()),
+                e => Err(e),
+            })?,
+            part_abbreviation: parse_optional_chd_text(node, "part-abbreviation")?,
+            score_instrument: parse_children(node)?,
+            player: parse_children(node)?,
+            midi_instrument: parse_children(node)?,
+            unknown: Unknown::parse(
+                node,
+                &["id"],
+                &[
+                    "part-name",
+                    "part-abbreviation",
+                    "score-instrument",
+                    "player",
+                    "midi-instrument",
+                ],
+            ),
+        })
+    }
+}
+
+impl ToNode for ScorePart {
+    fn to_node(&self) -> Element {
+        let node = Element::new(Self::tag())
+            .with_attr("id", &self.id)
+            .with_chd_text("part-name", &self.part_name)
+            .with_optional_chd_text("part-abbreviation", self.part_abbreviation.as_ref())
+            .with_children(children_to_nodes(&self.score_instrument))
+            .with_children(children_to_nodes(&self.player))
+            .with_children(children_to_nodes(&self.midi_instrument));
+        self.unknown.write(node)
+    }
+}
+
+impl ScorePart {
+    /// MIDI setup of the instrument with the given id
+    pub fn midi_instrument(&self, id: &str) -> Option<&MidiInstrument> {
+        self.midi_instrument.iter().find(|m| m.id == id)
+    }
+
+    pub fn player(&self, id: &str) -> Option<&Player> {
+        self.player.iter().find(|p| p.id == id)
+    }
+}
+
+xml_enum! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum GroupSymbolValue {
+        None => "none",
+        Brace => "brace",
+        Line => "line",
+        Bracket => "bracket",
+        Square => "square",
+    }
+}
+
+xml_enum! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum GroupBarlineValue {
+        Yes => "yes",
+        No => "no",
+        Mensurstrich => "Mensurstrich",
+    }
+}
+
+/// Start or stop of a group of the score parts in between, e.g. a piano grand staff
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartGroup {
+    pub group_type: StartStop,
+    /// Distinguishes overlapping groups
+    pub number: String,
+    pub group_name: Option<String>,
+    pub group_abbreviation: Option<String>,
+    pub group_symbol: Option<GroupSymbolValue>,
+    /// Whether barlines connect the staves of the group
+    pub group_barline: Option<GroupBarlineValue>,
+    pub unknown: Unknown,
+}
+
+impl FromNode for PartGroup {
+    fn tag() -> &'static str {
+        "part-group"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        Ok(PartGroup {
+            group_type: parse_attr(node, "type")?,
+            number: parse_optional_attr(node, "number")?.unwrap_or_else(|| "1".to_owned()),
+            group_name: parse_optional_chd_text(node, "group-name")?,
+            group_abbreviation: parse_optional_chd_text(node, "group-abbreviation")?,
+            group_symbol: parse_optional_chd_text(node, "group-symbol")?,
+            group_barline: parse_optional_chd_text(node, "group-barline")?,
+            unknown: Unknown::parse(
+                node,
+                &["type", "number"],
+                &[
+                    "group-name",
+                    "group-abbreviation",
+                    "group-symbol",
+                    "group-barline",
+                ],
+            ),
+        })
+    }
+}
+
+impl ToNode for PartGroup {
+    fn to_node(&self) -> Element {
+        let node = Element::new(Self::tag())
+            .with_attr("type", self.group_type)
+            .with_attr("number", &self.number)
+            .with_optional_chd_text("group-name", self.group_name.as_ref())
+            .with_optional_chd_text("group-abbreviation", self.group_abbreviation.as_ref())
+            .with_optional_chd_text("group-symbol", self.group_symbol)
+            .with_optional_chd_text("group-barline", self.group_barline);
+        self.unknown.write(node)
+    }
+}
+
+/// Content of the part-list, kept in document order to delimit part groups
+#[derive(Debug, Clone, PartialEq)]
+pub enum PartListItem {
+    PartGroup(PartGroup),
+    ScorePart(ScorePart),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PartList {
+    pub items: Vec<PartListItem>,
+    pub unknown: Unknown,
+}
+
+impl FromNode for PartList {
+    fn tag() -> &'static str {
+        "part-list"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        let items = node
+            .children()
+            .filter_map(|c| match c.tag_name().name() {
+                "part-group" => Some(PartGroup::from_node(&c).map(PartListItem::PartGroup)),
+                "score-part" => Some(ScorePart::from_node(&c).map(PartListItem::ScorePart)),
+                _ => None,
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(PartList {
+            items,
+            unknown: Unknown::parse(node, &[], &["part-group", "score-part"]),
+        })
+    }
+}
+
+impl ToNode for PartList {
+    fn to_node(&self) -> Element {
+        let node = Element::new(Self::tag()).with_children(self.items.iter().map(|i| match i {
+            PartListItem::PartGroup(g) => g.to_node(),
+            PartListItem::ScorePart(p) => p.to_node(),
+        }));
+        self.unknown.write(node)
+    }
+}
+
+impl PartList {
+    pub fn score_parts(&self) -> impl Iterator<Item = &ScorePart> {
+        self.items.iter().filter_map(|i| match i {
+            PartListItem::ScorePart(p) => Some(p),
+            _ => None,
+        })
+    }
+
+    /// Part groups by their start element, with the score parts until the matching stop
+    pub fn groups(&self) -> Vec<(&PartGroup, Vec<&ScorePart>)> {
+        let mut open: Vec<(&PartGroup, Vec<&ScorePart>)> = Vec::new();
+        let mut groups = Vec::new();
+        for item in &self.items {
+            match item {
+                PartListItem::PartGroup(g) if g.group_type == StartStop::Start => {
+                    open.push((g, Vec::new()))
+                }
+                PartListItem::PartGroup(g) => {
+                    if let Some(i) = open.iter().position(|(o, _)| o.number == g.number) {
+                        groups.push(open.remove(i));
+                    }
+                }
+                PartListItem::ScorePart(p) => open.iter_mut().for_each(|(_, parts)| parts.push(p)),
+            }
+        }
+
+        groups
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use roxmltree::Document;
+
+    use super::*;
+
+    #[test]
+    fn score_part_instruments_ok() {
+        let xml = r#"
+            <score-part id="P1">
+                <part-name>Drumset</part-name>
+                <score-instrument id="P1-I36">
+                    <instrument-name>Bass Drum 1</instrument-name>
+                </score-instrument>
+                <score-instrument id="P1-I39">
+                    <instrument-name>Acoustic Snare</instrument-name>
+                </score-instrument>
+                <midi-instrument id="P1-I36">
+                    <midi-channel>10</midi-channel>
+                    <midi-program>1</midi-program>
+                    <midi-unpitched>36</midi-unpitched>
+                    <volume>78.7402</volume>
+                    <pan>0</pan>
+                </midi-instrument>
+                <midi-instrument id="P1-I39">
+                    <midi-channel>10</midi-channel>
+                    <midi-unpitched>39</midi-unpitched>
+                </midi-instrument>
+            </score-part>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let part = ScorePart::from_node(&node);
+        assert!(part.is_ok());
+        let part = part.unwrap();
+        assert_eq!(part.score_instrument.len(), 2);
+        assert_eq!(part.score_instrument[1].instrument_name, "Acoustic Snare");
+        assert_eq!(
+            part.midi_instrument("P1-I36"),
+            Some(&MidiInstrument {
+                id: "P1-I36".to_owned(),
+                midi_channel: Some(10),
+                midi_program: Some(1),
+                midi_unpitched: Some(36),
+                volume: Some(78.7402),
+                pan: Some(0.),
+                unknown: Unknown::default()
+            })
+        );
+        assert_eq!(
+            part.midi_instrument("P1-I39").unwrap().midi_unpitched,
+            Some(39)
+        );
+    }
+
+    #[test]
+    fn part_list_groups_ok() {
+        let xml = r#"
+            <part-list>
+                <part-group type="start" number="1">
+                    <group-name>Strings</group-name>
+                    <group-symbol>bracket</group-symbol>
+                    <group-barline>yes</group-barline>
+                </part-group>
+                <score-part id="P1">
+                    <part-name>Violin</part-name>
+                </score-part>
+                <part-group type="start" number="2">
+                    <group-symbol>brace</group-symbol>
+                </part-group>
+                <score-part id="P2">
+                    <part-name>Cello</part-name>
+                </score-part>
+                <part-group type="stop" number="2" />
+                <part-group type="stop" number="1" />
+                <score-part id="P3">
+                    <part-name>Piano</part-name>
+                </score-part>
+            </part-list>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let part_list = PartList::from_node(&node);
+        assert!(part_list.is_ok());
+        let part_list = part_list.unwrap();
+        assert_eq!(part_list.items.len(), 7);
+        assert_eq!(part_list.score_parts().count(), 3);
+
+        let groups = part_list.groups();
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0].0.group_symbol, Some(GroupSymbolValue::Brace));
+        assert_eq!(groups[0].1.len(), 1);
+        assert_eq!(groups[1].0.group_name.as_deref(), Some("Strings"));
+        assert_eq!(groups[1].0.group_barline, Some(GroupBarlineValue::Yes));
+        let ids: Vec<_> = groups[1].1.iter().map(|p| p.id.as_str()).collect();
+        assert_eq!(ids, vec!["P1", "P2"]);
+    }
+
+    #[test]
+    fn score_part_players_ok() {
+        let xml = r#"
+            <score-part id="P1">
+                <part-name>Violin I</part-name>
+                <score-instrument id="P1-I1">
+                    <instrument-name>Violin</instrument-name>
+                </score-instrument>
+                <player id="P1-M1">
+                    <player-name>Desk 1</player-name>
+                </player>
+                <player id="P1-M2">
+                    <player-name>Desk 2</player-name>
+                </player>
+            </score-part>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let score_part = ScorePart::from_node(&node).unwrap();
+        assert_eq!(score_part.player.len(), 2);
+        assert_eq!(
+            score_part.player("P1-M2"),
+            Some(&Player {
+                id: "P1-M2".to_owned(),
+                player_name: "Desk 2".to_owned(),
+                unknown: Unknown::default()
+            })
+        );
+        assert_eq!(score_part.to_node(), Element::from(&node));
+    }
+}
@@ -0,0 +1,379 @@
+use roxmltree::Node;
+
+use super::{
+    count_chd, parse_attr, parse_chd_text, parse_optional_attr, parse_optional_chd_text,
+    parse_optional_text, parse_text, NumberLevel, StartStop, ToNode, Unknown, YesNo,
+};
+use crate::{error::Result, writer::Element};
+
+/// Hammer-on or pull-off between two notes on the same string
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HammerOnPullOff {
+    pub hammer_on_pull_off_type: StartStop,
+    pub number: NumberLevel,
+    /// Usually "H" or "P"
+    pub text: Option<String>,
+    pub unknown: Unknown,
+}
+
+impl HammerOnPullOff {
+    fn parse(node: &Node) -> Result<Self> {
+        Ok(HammerOnPullOff {
+            hammer_on_pull_off_type: parse_attr(node, "type")?,
+            number: parse_optional_attr(node, "number")?.unwrap_or_default(),
+            text: parse_optional_text(node),
+            unknown: Unknown::parse_attrs(node, &["type", "number"]),
+        })
+    }
+
+    fn to_node(&self, tag: &str) -> Element {
+        let node = Element::new(tag)
+            .with_attr("type", self.hammer_on_pull_off_type)
+            .with_attr("number", self.number)
+            .with_optional_text(self.text.as_ref());
+        self.unknown.write(node)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Bend {
+    /// Bend amount in semitones, negative for a downward bend
+    pub bend_alter: f64,
+    /// Bent before the note is struck
+    pub pre_bend: bool,
+    /// Released back to the original pitch
+    pub release: bool,
+    /// Text of a whammy bar bend, e.g. "scoop" or "dip"
+    pub with_bar: Option<String>,
+    pub unknown: Unknown,
+}
+
+impl Bend {
+    fn parse(node: &Node) -> Result<Self> {
+        Ok(Bend {
+            bend_alter: parse_chd_text(node, "bend-alter")?,
+            pre_bend: count_chd(node, "pre-bend") > 0,
+            release: count_chd(node, "release") > 0,
+            with_bar: parse_optional_chd_text(node, "with-bar")?,
+            unknown: Unknown::parse(
+                node,
+                &[],
+                &["bend-alter", "pre-bend", "release", "with-bar"],
+            ),
+        })
+    }
+}
+
+impl ToNode for Bend {
+    fn to_node(&self) -> Element {
+        let node = Element::new("bend")
+            .with_chd_text("bend-alter", self.bend_alter)
+            .with_optional_child(self.pre_bend.then(|| Element::new("pre-bend")))
+            .with_optional_child(self.release.then(|| Element::new("release")))
+            .with_optional_chd_text("with-bar", self.with_bar.as_ref());
+        self.unknown.write(node)
+    }
+}
+
+xml_enum! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum HarmonicPitch {
+        BasePitch => "base-pitch",
+        TouchingPitch => "touching-pitch",
+        SoundingPitch => "sounding-pitch",
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Harmonic {
+    /// Natural harmonic unless artificial is set
+    pub artificial: bool,
+    /// Which pitch of the harmonic the note represents
+    pub pitch: Option<HarmonicPitch>,
+    pub unknown: Unknown,
+}
+
+impl Harmonic {
+    fn parse(node: &Node) -> Result<Self> {
+        Ok(Harmonic {
+            artificial: count_chd(node, "artificial") > 0,
+            pitch: node
+                .children()
+                .find_map(|c| c.tag_name().name().parse().ok()),
+            unknown: Unknown::parse(
+                node,
+                &[],
+                &[
+                    "natural",
+                    "artificial",
+                    "base-pitch",
+                    "touching-pitch",
+                    "sounding-pitch",
+                ],
+            ),
+        })
+    }
+}
+
+impl ToNode for Harmonic {
+    fn to_node(&self) -> Element {
+        let node = Element::new("harmonic")
+            .with_child(Element::new(if self.artificial {
+                "artificial"
+            } else {
+                "natural"
+            }))
+            .with_optional_child(self.pitch.map(|p| Element::new(p.to_string())));
+        self.unknown.write(node)
+    }
+}
+
+/// Finger to play a note with, e.g. "1" for the thumb or index finger
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fingering {
+    pub text: Option<String>,
+    /// Finger substituted on a note that is already sounding
+    pub substitution: bool,
+    /// Alternative to the preferred fingering
+    pub alternate: bool,
+    pub unknown: Unknown,
+}
+
+impl Fingering {
+    fn parse(node: &Node) -> Result<Self> {
+        Ok(Fingering {
+            text: parse_optional_text(node),
+            substitution: parse_optional_attr(node, "substitution")? == Some(YesNo::Yes),
+            alternate: parse_optional_attr(node, "alternate")? == Some(YesNo::Yes),
+            unknown: Unknown::parse_attrs(node, &["substitution", "alternate"]),
+        })
+    }
+}
+
+impl ToNode for Fingering {
+    fn to_node(&self) -> Element {
+        let node = Element::new("fingering")
+            .with_optional_attr("substitution", self.substitution.then_some(YesNo::Yes))
+            .with_optional_attr("alternate", self.alternate.then_some(YesNo::Yes))
+            .with_optional_text(self.text.as_ref());
+        self.unknown.write(node)
+    }
+}
+
+/// Organ pedal played with the heel or toe
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HeelToe {
+    /// Heel or toe substituted on a pedal that is already held
+    pub substitution: bool,
+    pub unknown: Unknown,
+}
+
+impl HeelToe {
+    fn parse(node: &Node) -> Result<Self> {
+        Ok(HeelToe {
+            substitution: parse_optional_attr(node, "substitution")? == Some(YesNo::Yes),
+            unknown: Unknown::parse(node, &["substitution"], &[]),
+        })
+    }
+
+    fn to_node(&self, tag: &str) -> Element {
+        let node = Element::new(tag)
+            .with_optional_attr("substitution", self.substitution.then_some(YesNo::Yes));
+        self.unknown.write(node)
+    }
+}
+
+/// Technical indication of a `<technical>` notation element
+#[derive(Debug, Clone, PartialEq)]
+pub enum Technical {
+    /// String number, 1 is the highest pitched string
+    String(u8),
+    Fret(u8),
+    HammerOn(HammerOnPullOff),
+    PullOff(HammerOnPullOff),
+    Bend(Bend),
+    /// Tapping with the text to display, e.g. "T"
+    Tap(Option<String>),
+    Harmonic(Harmonic),
+    Fingering(Fingering),
+    /// Plucking finger of the right hand, e.g. "p", "i", "m" or "a"
+    Pluck(Option<String>),
+    /// Cello thumb position
+    ThumbPosition,
+    Heel(HeelToe),
+    Toe(HeelToe),
+    /// Indication not modeled yet, kept as raw xml
+    Other(Element),
+}
+
+impl Technical {
+    /// Parse a technical indication from its element, none if it is not an element
+    pub(super) fn parse(node: &Node) -> Result<Option<Self>> {
+        Ok(Some(match node.tag_name().name() {
+            "string" => Technical::String(parse_text(node, "string")?),
+            "fret" => Technical::Fret(parse_text(node, "fret")?),
+            "hammer-on" => Technical::HammerOn(HammerOnPullOff::parse(node)?),
+            "pull-off" => Technical::PullOff(HammerOnPullOff::parse(node)?),
+            "bend" => Technical::Bend(Bend::parse(node)?),
+            "tap" => Technical::Tap(parse_optional_text(node)),
+            "harmonic" => Technical::Harmonic(Harmonic::parse(node)?),
+            "fingering" => Technical::Fingering(Fingering::parse(node)?),
+            "pluck" => Technical::Pluck(parse_optional_text(node)),
+            "thumb-position" => Technical::ThumbPosition,
+            "heel" => Technical::Heel(HeelToe::parse(node)?),
+            "toe" => Technical::Toe(HeelToe::parse(node)?),
+            _ if node.is_element() => Technical::Other(Element::from(node)),
+            _ => return Ok(None),
+        }))
+    }
+}
+
+impl ToNode for Technical {
+    fn to_node(&self) -> Element {
+        match self {
+            Technical::String(s) => Element::new("string").with_text(s),
+            Technical::Fret(f) => Element::new("fret").with_text(f),
+            Technical::HammerOn(h) => h.to_node("hammer-on"),
+            Technical::PullOff(p) => p.to_node("pull-off"),
+            Technical::Bend(b) => b.to_node(),
+            Technical::Tap(t) => Element::new("tap").with_optional_text(t.as_ref()),
+            Technical::Harmonic(h) => h.to_node(),
+            Technical::Fingering(f) => f.to_node(),
+            Technical::Pluck(p) => Element::new("pluck").with_optional_text(p.as_ref()),
+            Technical::ThumbPosition => Element::new("thumb-position"),
+            Technical::Heel(h) => h.to_node("heel"),
+            Technical::Toe(t) => t.to_node("toe"),
+            Technical::Other(o) => o.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use roxmltree::Document;
+
+    use super::*;
+    use crate::score::{FromNode, Notations};
+
+    #[test]
+    fn technical_string_fret_ok() {
+        let xml = r#"
+            <notations>
+                <technical>
+                    <string>5</string>
+                    <fret>3</fret>
+                </technical>
+            </notations>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let notations = Notations::from_node(&node).unwrap();
+        assert_eq!(
+            notations.technical,
+            vec![Technical::String(5), Technical::Fret(3)]
+        );
+    }
+
+    #[test]
+    fn technical_guitar_ok() {
+        let xml = r#"
+            <notations>
+                <technical>
+                    <hammer-on type="start">H</hammer-on>
+                    <bend>
+                        <bend-alter>2</bend-alter>
+                        <release />
+                    </bend>
+                    <harmonic>
+                        <natural />
+                        <touching-pitch />
+                    </harmonic>
+                    <tap />
+                </technical>
+            </notations>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let notations = Notations::from_node(&node).unwrap();
+        assert_eq!(
+            notations.technical,
+            vec![
+                Technical::HammerOn(HammerOnPullOff {
+                    hammer_on_pull_off_type: StartStop::Start,
+                    number: NumberLevel(1),
+                    text: Some("H".to_owned()),
+                    unknown: Unknown::default()
+                }),
+                Technical::Bend(Bend {
+                    bend_alter: 2.,
+                    pre_bend: false,
+                    release: true,
+                    with_bar: None,
+                    unknown: Unknown::default()
+                }),
+                Technical::Harmonic(Harmonic {
+                    artificial: false,
+                    pitch: Some(HarmonicPitch::TouchingPitch),
+                    unknown: Unknown::default()
+                }),
+                Technical::Tap(None)
+            ]
+        );
+    }
+
+    #[test]
+    fn technical_fingering_ok() {
+        let xml = r#"
+            <notations>
+                <technical>
+                    <fingering>2</fingering>
+                    <fingering substitution="yes">4</fingering>
+                    <fingering alternate="yes">3</fingering>
+                    <pluck>i</pluck>
+                    <thumb-position />
+                    <toe substitution="yes" />
+                </technical>
+            </notations>"#;
+        let doc = Document::parse(xml).unwrap();
+        let node = doc.root_element();
+
+        let notations = Notations::from_node(&node).unwrap();
+        assert_eq!(
+            notations.technical,
+            vec![
+                Technical::Fingering(Fingering {
+                    text: Some("2".to_owned()),
+                    substitution: false,
+                    alternate: false,
+                    unknown: Unknown::default()
+                }),
+                Technical::Fingering(Fingering {
+                    text: Some("4".to_owned()),
+                    substitution: true,
+                    alternate: false,
+                    unknown: Unknown::default()
+                }),
+                Technical::Fingering(Fingering {
+                    text: Some("3".to_owned()),
+                    substitution: false,
+                    alternate: true,
+                    unknown: Unknown::default()
+                }),
+                Technical::Pluck(Some("i".to_owned())),
+                Technical::ThumbPosition,
+                Technical::Toe(HeelToe {
+                    substitution: true,
+                    unknown: Unknown::default()
+                })
+            ]
+        );
+
+        let el = notations.technical[1].to_node();
+        assert_eq!(
+            el,
+            Element::new("fingering")
+                .with_attr("substitution", "yes")
+                .with_text(4)
+        );
+    }
+}
@@ -0,0 +1,343 @@
+use roxmltree::{Document, Node};
+
+use super::{
+    children_to_nodes, parse_attr, parse_chd, parse_children, parse_option_chd,
+    parse_optional_attr, Credit, Defaults, FromNode, Measure, MeasureContent, Metadata, Part,
+    PartList, Score, Tenths, ToNode, Unknown, Version, YesNo, XML_DECLARATION,
+};
+use crate::{
+    error::Result,
+    writer::{Element, WriteOptions},
+};
+
+/// Content of a part in a measure of a timewise score
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimewisePart {
+    pub id: String,
+    pub contents: Vec<MeasureContent>,
+    pub unknown: Unknown,
+}
+
+impl FromNode for TimewisePart {
+    fn tag() -> &'static str {
+        "part"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        Ok(TimewisePart {
+            id: parse_attr(node, "id")?,
+            contents: node
+                .children()
+                .filter_map(|c| MeasureContent::parse(&c).transpose())
+                .collect::<Result<_>>()?,
+            unknown: Unknown::parse(node, &["id"], MeasureContent::TAGS),
+        })
+    }
+}
+
+impl ToNode for TimewisePart {
+    fn to_node(&self) -> Element {
+        let node = Element::new(Self::tag())
+            .with_attr("id", &self.id)
+            .with_children(self.contents.iter().map(MeasureContent::to_node));
+        self.unknown.write(node)
+    }
+}
+
+/// Measure of a timewise score, holding the content of all parts
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimewiseMeasure {
+    pub number: String,
+    pub implicit: Option<YesNo>,
+    pub non_controlling: Option<YesNo>,
+    pub width: Option<Tenths>,
+    pub parts: Vec<TimewisePart>,
+    pub unknown: Unknown,
+}
+
+impl FromNode for TimewiseMeasure {
+    fn tag() -> &'static str {
+        "measure"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        Ok(TimewiseMeasure {
+            number: parse_attr(node, "number")?,
+            implicit: parse_optional_attr(node, "implicit")?,
+            non_controlling: parse_optional_attr(node, "non-controlling")?,
+            width: parse_optional_attr(node, "width")?,
+            parts: parse_children(node)?,
+            unknown: Unknown::parse(
+                node,
+                &["number", "implicit", "non-controlling", "width"],
+                &["part"],
+            ),
+        })
+    }
+}
+
+impl ToNode for TimewiseMeasure {
+    fn to_node(&self) -> Element {
+        let node = Element::new(Self::tag())
+            .with_attr("number", &self.number)
+            .with_optional_attr("implicit", self.implicit)
+            .with_optional_attr("non-controlling", self.non_controlling)
+            .with_optional_attr("width", self.width)
+            .with_children(children_to_nodes(&self.parts));
+        self.unknown.write(node)
+    }
+}
+
+impl TimewiseMeasure {
+    /// Content of the part with the given id in this measure
+    pub fn part(&self, id: &str) -> Option<&TimewisePart> {
+        self.parts.iter().find(|p| p.id == id)
+    }
+}
+
+/// Score organized by measures, each holding all parts, rather than by parts
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimewiseScore {
+    /// 1.0 if the document does not declare its version
+    pub version: Version,
+    pub metadata: Metadata,
+    pub defaults: Option<Defaults>,
+    pub credits: Vec<Credit>,
+    pub part_list: PartList,
+    pub measures: Vec<TimewiseMeasure>,
+    pub unknown: Unknown,
+}
+
+impl FromNode for TimewiseScore {
+    fn tag() -> &'static str {
+        "score-timewise"
+    }
+    fn from_node(node: &Node) -> Result<Self> {
+        Ok(TimewiseScore {
+            version: Version::parse(node)?,
+            metadata: Metadata::parse(node)?,
+            defaults: parse_option_chd(node)?,
+            credits: parse_children(node)?,
+            part_list: parse_chd(node)?,
+            measures: parse_children(node)?,
+            unknown: Unknown::parse(
+                node,
+                &["version"],
+                &[
+                    "work",
+                    "movement-number",
+                    "movement-title",
+                    "identification",
+                    "defaults",
+                    "credit",
+                    "part-list",
+                    "measure",
+                ],
+            ),
+        })
+    }
+}
+
+impl ToNode for TimewiseScore {
+    fn to_node(&self) -> Element {
+        let node = Element::new(Self::tag())
+            .with_attr("version", self.version)
+            .with_children(self.metadata.to_nodes())
+            .with_optional_child(self.defaults.as_ref().map(ToNode::to_node))
+            .with_children(children_to_nodes(&self.credits))
+            .with_child(self.part_list.to_node())
+            .with_children(children_to_nodes(&self.measures));
+        self.version.retain_supported(self.unknown.write(node))
+    }
+}
+
+impl TimewiseScore {
+    pub fn from_xml(xml: &str) -> Result<Self> {
+        let doc = Document::parse(xml)?;
+
+        TimewiseScore::from_node(&doc.root_element())
+    }
+
+    pub fn to_xml(&self) -> String {
+        self.to_xml_with(&WriteOptions::default())
+    }
+
+    pub fn to_xml_with(&self, options: &WriteOptions) -> String {
+        let newline = options.newline.as_str();
+
+        let mut xml = format!("{}{}", XML_DECLARATION, newline);
+        if options.doctype {
+            xml.push_str(&self.version.timewise_doctype());
+            xml.push_str(newline);
+        }
+        self.to_node().write_with(&mut xml, 0, options);
+
+        xml
+    }
+
+    /// Regroup the measures by part, taking the measure attributes of every part
+    /// from the timewise measure and the part attributes from its first measure.
+    /// Unknown children of a timewise measure outside its parts have no place
+    /// in a partwise score and are dropped
+    pub fn into_partwise(self) -> Score {
+        let mut parts: Vec<Part> = Vec::new();
+        for measure in self.measures {
+            for p in measure.parts {
+                let m = Measure {
+                    number: measure.number.clone(),
+                    implicit: measure.implicit,
+                    non_controlling: measure.non_controlling,
+                    width: measure.width,
+                    contents: p.contents,
+                    unknown: Unknown {
+                        attrs: measure.unknown.attrs.clone(),
+                        children: p.unknown.children,
+                    },
+                };
+                match parts.iter_mut().find(|part| part.id == p.id) {
+                    Some(part) => part.measures.push(m),
+                    None => parts.push(Part {
+                        id: p.id,
+                        measures: vec![m],
+                        unknown: Unknown {
+                            attrs: p.unknown.attrs,
+                            children: Vec::new(),
+                        },
+                    }),
+                }
+            }
+        }
+
+        Score {
+            version: self.version,
+            metadata: self.metadata,
+            defaults: self.defaults,
+            credits: self.credits,
+            part_list: self.part_list,
+            parts,
+            unknown: self.unknown,
+        }
+    }
+}
+
+impl Score {
+    /// Regroup the parts by measure, matching measures by position and
+    /// taking the measure attributes from the first part having the measure.
+    /// Unknown children of a part outside its measures are dropped
+    pub fn into_timewise(self) -> TimewiseScore {
+        let mut parts: Vec<_> = self
+            .parts
+            .into_iter()
+            .map(|p| (p.id, p.unknown.attrs, p.measures.into_iter()))
+            .collect();
+
+        let mut measures = Vec::new();
+        loop {
+            let mut measure: Option<TimewiseMeasure> = None;
+            let next = parts
+                .iter_mut()
+                .filter_map(|(id, attrs, ms)| Some((id.clone(), attrs.clone(), ms.next()?)));
+            for (id, attrs, m) in next {
+                let part = TimewisePart {
+                    id,
+                    contents: m.contents,
+                    unknown: Unknown {
+                        attrs,
+                        children: m.unknown.children,
+                    },
+                };
+                match &mut measure {
+                    Some(measure) => measure.parts.push(part),
+                    None => {
+                        measure = Some(TimewiseMeasure {
+                            number: m.number,
+                            implicit: m.implicit,
+                            non_controlling: m.non_controlling,
+                            width: m.width,
+                            parts: vec![part],
+                            unknown: Unknown {
+                                attrs: m.unknown.attrs,
+                                children: Vec::new(),
+                            },
+                        })
+                    }
+                }
+            }
+
+            match measure {
+                Some(m) => measures.push(m),
+                None => break,
+            }
+        }
+
+        TimewiseScore {
+            version: self.version,
+            metadata: self.metadata,
+            defaults: self.defaults,
+            credits: self.credits,
+            part_list: self.part_list,
+            measures,
+            unknown: self.unknown,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn timewise_round_trip_ok() {
+        let xml = r#"
+            <score-timewise version="4.0">
+                <part-list>
+                    <score-part id="P1">
+                        <part-name>Flute</part-name>
+                    </score-part>
+                    <score-part id="P2">
+                        <part-name>Cello</part-name>
+                    </score-part>
+                </part-list>
+                <measure number="1">
+                    <part id="P1">
+                        <note>
+                            <rest measure="yes" />
+                            <duration>4</duration>
+                        </note>
+                    </part>
+                    <part id="P2">
+                        <note>
+                            <pitch>
+                                <step>C</step>
+                                <octave>3</octave>
+                            </pitch>
+                            <duration>4</duration>
+                        </note>
+                    </part>
+                </measure>
+                <measure number="2" implicit="yes">
+                    <part id="P1">
+                        <backup>
+                            <duration>4</duration>
+                        </backup>
+                    </part>
+                    <part id="P2" />
+                </measure>
+            </score-timewise>"#;
+        let timewise = TimewiseScore::from_xml(xml).unwrap();
+        assert_eq!(timewise.measures.len(), 2);
+        assert_eq!(timewise.measures[0].part("P2").unwrap().contents.len(), 1);
+
+        let partwise = timewise.into_partwise();
+        assert_eq!(partwise.parts.len(), 2);
+        assert_eq!(partwise.parts[1].id, "P2");
+        assert_eq!(partwise.parts[1].measures.len(), 2);
+        assert!(partwise.parts[1].measures[1].is_implicit());
+        assert!(partwise.parts[1].measures[1].contents.is_empty());
+
+        let written = partwise.into_timewise().to_xml();
+        assert!(written.contains("DTD MusicXML 4.0 Timewise"));
+        assert_eq!(
+            TimewiseScore::from_xml(&written).unwrap(),
+            TimewiseScore::from_xml(xml).unwrap()
+        );
+    }
+}
@@ -0,0 +1,308 @@
+use std::fmt::Display;
+
+use roxmltree::{Attribute, Node};
+
+const XML_NAMESPACE: &str = "http://www.w3.org/XML/1998/namespace";
+
+/// Line ending written after each element
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Newline {
+    #[default]
+    Lf,
+    CrLf,
+}
+
+impl Newline {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Newline::Lf => "\n",
+            Newline::CrLf => "\r\n",
+        }
+    }
+}
+
+/// Order the attributes of an element are written in
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum AttrOrder {
+    /// Order of the fields in the score model
+    #[default]
+    Model,
+    /// Sorted by name, as some other tools write them
+    Alphabetical,
+}
+
+/// Formatting of written xml, e.g. to keep diffs against files of other tools readable
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WriteOptions {
+    /// Spaces per nesting level
+    pub indent: usize,
+    pub attr_order: AttrOrder,
+    /// Whether to write the DOCTYPE declaration of the document
+    pub doctype: bool,
+    pub newline: Newline,
+}
+
+impl Default for WriteOptions {
+    fn default() -> Self {
+        WriteOptions {
+            indent: 2,
+            attr_order: AttrOrder::Model,
+            doctype: true,
+            newline: Newline::Lf,
+        }
+    }
+}
+
+/// An in-memory xml element used when serializing the score model.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Element {
+    pub name: String,
+    pub attrs: Vec<(String, String)>,
+    pub text: Option<String>,
+    pub children: Vec<Element>,
+}
+
+impl Element {
+    pub fn new(name: impl Into<String>) -> Self {
+        Element {
+            name: name.into(),
+            attrs: Vec::new(),
+            text: None,
+            children: Vec::new(),
+        }
+    }
+
+    pub fn with_attr(mut self, attr: &str, v: impl Display) -> Self {
+        self.attrs.push((attr.to_owned(), v.to_string()));
+        self
+    }
+
+    pub fn with_optional_attr(self, attr: &str, v: Option<impl Display>) -> Self {
+        match v {
+            Some(v) => self.with_attr(attr, v),
+            None => self,
+        }
+    }
+
+    pub fn with_text(mut self, text: impl Display) -> Self {
+        self.text = Some(text.to_string());
+        self
+    }
+
+    pub fn with_optional_text(self, text: Option<impl Display>) -> Self {
+        match text {
+            Some(t) => self.with_text(t),
+            None => self,
+        }
+    }
+
+    pub fn with_child(mut self, child: Element) -> Self {
+        self.children.push(child);
+        self
+    }
+
+    pub fn with_optional_child(self, child: Option<Element>) -> Self {
+        match child {
+            Some(c) => self.with_child(c),
+            None => self,
+        }
+    }
+
+    pub fn with_children(mut self, children: impl IntoIterator<Item = Element>) -> Self {
+        self.children.extend(children);
+        self
+    }
+
+    pub fn with_chd_text(self, name: &str, text: impl Display) -> Self {
+        self.with_child(Element::new(name).with_text(text))
+    }
+
+    pub fn with_optional_chd_text(self, name: &str, text: Option<impl Display>) -> Self {
+        match text {
+            Some(t) => self.with_chd_text(name, t),
+            None => self,
+        }
+    }
+
+    /// Remove the descendants for which the predicate is false, with their subtrees,
+    /// and the descendants left without content once all their children are removed
+    pub fn retain_descendants(&mut self, f: &impl Fn(&Element) -> bool) {
+        self.children.retain(f);
+        self.children.retain_mut(|c| {
+            let had_children = !c.children.is_empty();
+            c.retain_descendants(f);
+            !had_children || !c.children.is_empty() || c.text.is_some()
+        });
+    }
+
+    pub fn write_to(&self, out: &mut String, depth: usize) {
+        self.write_with(out, depth, &WriteOptions::default());
+    }
+
+    pub fn write_with(&self, out: &mut String, depth: usize, options: &WriteOptions) {
+        let indent = " ".repeat(depth * options.indent);
+        let newline = options.newline.as_str();
+
+        let mut attrs: Vec<_> = self.attrs.iter().collect();
+        if options.attr_order == AttrOrder::Alphabetical {
+            attrs.sort_by(|(a, _), (b, _)| a.cmp(b));
+        }
+
+        out.push_str(&indent);
+        out.push('<');
+        out.push_str(&self.name);
+        for (attr, v) in attrs {
+            out.push(' ');
+            out.push_str(attr);
+            out.push_str("=\"");
+            escape_to(out, v);
+            out.push('"');
+        }
+
+        if self.text.is_none() && self.children.is_empty() {
+            out.push_str("/>");
+            out.push_str(newline);
+            return;
+        }
+        out.push('>');
+
+        if let Some(text) = &self.text {
+            escape_to(out, text);
+        }
+
+        if !self.children.is_empty() {
+            out.push_str(newline);
+            for c in &self.children {
+                c.write_with(out, depth + 1, options);
+            }
+            out.push_str(&indent);
+        }
+
+        out.push_str("</");
+        out.push_str(&self.name);
+        out.push('>');
+        out.push_str(newline);
+    }
+}
+
+impl From<&Node<'_, '_>> for Element {
+    /// Keep a parsed node as a raw element, e.g. content not modeled yet
+    fn from(node: &Node) -> Self {
+        Element {
+            name: node.tag_name().name().to_owned(),
+            attrs: node
+                .attributes()
+                .map(|a| (attr_name(&a), a.value().to_owned()))
+                .collect(),
+            text: node
+                .text()
+                .map(str::trim)
+                .filter(|t| !t.is_empty())
+                .map(str::to_owned),
+            children: node
+                .children()
+                .filter(Node::is_element)
+                .map(|c| Element::from(&c))
+                .collect(),
+        }
+    }
+}
+
+/// Qualified name of an attribute, keeping the xml prefix, e.g. "xml:lang"
+pub(crate) fn attr_name(attr: &Attribute) -> String {
+    match attr.namespace() {
+        Some(XML_NAMESPACE) => format!("xml:{}", attr.name()),
+        _ => attr.name().to_owned(),
+    }
+}
+
+fn escape_to(out: &mut String, s: &str) {
+    for c in s.chars() {
+        match c {
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '&' => out.push_str("&amp;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            _ => out.push(c),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn write_nested_ok() {
+        let el = Element::new("pitch")
+            .with_attr("color", "#FF0000")
+            .with_chd_text("step", 'E')
+            .with_optional_chd_text("alter", None::<u8>)
+            .with_chd_text("octave", 4);
+
+        let mut xml = String::new();
+        el.write_to(&mut xml, 0);
+        assert_eq!(
+            xml,
+            "<pitch color=\"#FF0000\">\n  <step>E</step>\n  <octave>4</octave>\n</pitch>\n"
+        );
+    }
+
+    #[test]
+    fn write_options_ok() {
+        let el = Element::new("pitch")
+            .with_attr("color", "#FF0000")
+            .with_attr("default-x", 12)
+            .with_chd_text("step", 'E');
+        let options = WriteOptions {
+            indent: 4,
+            attr_order: AttrOrder::Alphabetical,
+            newline: Newline::CrLf,
+            ..Default::default()
+        };
+
+        let mut xml = String::new();
+        el.write_with(&mut xml, 0, &options);
+        assert_eq!(
+            xml,
+            "<pitch color=\"#FF0000\" default-x=\"12\">\r\n    <step>E</step>\r\n</pitch>\r\n"
+        );
+    }
+
+    #[test]
+    fn from_node_ok() {
+        let xml = r#"
+            <harp-pedals>
+                <pedal-tuning>
+                    <pedal-step>D</pedal-step>
+                    <pedal-alter>0</pedal-alter>
+                </pedal-tuning>
+            </harp-pedals>"#;
+        let doc = roxmltree::Document::parse(xml).unwrap();
+
+        let el = Element::from(&doc.root_element());
+        assert_eq!(
+            el,
+            Element::new("harp-pedals").with_child(
+                Element::new("pedal-tuning")
+                    .with_chd_text("pedal-step", 'D')
+                    .with_chd_text("pedal-alter", 0)
+            )
+        );
+    }
+
+    #[test]
+    fn write_escaped_ok() {
+        let el = Element::new("words")
+            .with_attr("xml:lang", "\"en\"")
+            .with_text("Allegro & <vivace>");
+
+        let mut xml = String::new();
+        el.write_to(&mut xml, 0);
+        assert_eq!(
+            xml,
+            "<words xml:lang=\"&quot;en&quot;\">Allegro &amp; &lt;vivace&gt;</words>\n"
+        );
+    }
+}